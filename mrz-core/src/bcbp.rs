@@ -0,0 +1,455 @@
+//! IATA Bar Coded Boarding Pass (BCBP) parsing.
+//!
+//! This module implements the boarding pass format defined by IATA Resolution 792.
+//! A BCBP is a single line made of:
+//!
+//! - the mandatory unique items (format code, number of legs, passenger name,
+//!   electronic ticket indicator),
+//! - one block of mandatory repeated items per flight leg, each followed by a
+//!   hex-length-prefixed variable-size field holding the conditional items and
+//!   data for individual airline use,
+//! - an optional security data block introduced by `^`.
+//!
+//! All data is stored in fixed-capacity `heapless` containers so the parser stays
+//! `no_std` compatible.
+
+use crate::{DocumentKind, ErrorPosition, MRZParseError};
+use heapless::String;
+use heapless::Vec;
+
+/// Maximum number of flight legs that can be encoded in a single BCBP (`M1`–`M4`).
+pub const BCBP_MAX_LEGS: usize = 4;
+/// Length of the passenger name field.
+pub const BCBP_PASSENGER_NAME_LEN: usize = 20;
+/// Length of the operating carrier PNR code field.
+pub const BCBP_PNR_LEN: usize = 7;
+/// Length of the airport code fields.
+pub const BCBP_AIRPORT_CODE_LEN: usize = 3;
+/// Length of airline designator fields.
+pub const BCBP_CARRIER_LEN: usize = 3;
+/// Length of the flight number field.
+pub const BCBP_FLIGHT_NUMBER_LEN: usize = 5;
+/// Length of the date of flight field (Julian day of year).
+pub const BCBP_FLIGHT_DATE_LEN: usize = 3;
+/// Length of the seat number field.
+pub const BCBP_SEAT_LEN: usize = 4;
+/// Length of the check-in sequence number field.
+pub const BCBP_SEQUENCE_NUMBER_LEN: usize = 5;
+/// Length of the date of issue of boarding pass field (last digit of year + Julian day).
+pub const BCBP_ISSUE_DATE_LEN: usize = 4;
+/// Length of a baggage tag licence plate number field.
+pub const BCBP_BAG_TAG_LEN: usize = 13;
+/// Length of the airline numeric code field.
+pub const BCBP_AIRLINE_NUMERIC_CODE_LEN: usize = 3;
+/// Length of the document form/serial number field.
+pub const BCBP_DOCUMENT_SERIAL_LEN: usize = 10;
+/// Length of the frequent flyer number field.
+pub const BCBP_FREQUENT_FLYER_NUMBER_LEN: usize = 16;
+/// Length of the free baggage allowance field.
+pub const BCBP_FREE_BAGGAGE_LEN: usize = 3;
+/// Maximum length of a hex-length-prefixed variable-size field (`FF`).
+pub const BCBP_VARIABLE_FIELD_MAX_LEN: usize = 255;
+
+const FORMAT_CODE: u8 = b'M';
+const VERSION_MARKER: u8 = b'>';
+const SECURITY_MARKER: u8 = b'^';
+
+/// Conditional items that appear once per boarding pass, in the first leg's variable-size field.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct BcbpConditional {
+    /// Version number of the BCBP standard used to encode the pass.
+    pub version: u8,
+    /// Passenger description (e.g. `0` adult, `1` male, `2` female).
+    pub passenger_description: Option<u8>,
+    /// Source of check-in (e.g. `W` web, `K` kiosk, `O` airport).
+    pub source_of_check_in: Option<u8>,
+    /// Source of boarding pass issuance.
+    pub source_of_issuance: Option<u8>,
+    /// Date of issue of boarding pass (last digit of year followed by Julian day).
+    pub issue_date: String<BCBP_ISSUE_DATE_LEN>,
+    /// Document type (`B` boarding pass, `I` itinerary receipt).
+    pub document_type: Option<u8>,
+    /// Airline designator of the boarding pass issuer.
+    pub issuer_designator: String<BCBP_CARRIER_LEN>,
+    /// Baggage tag licence plate number(s).
+    pub baggage_tag: String<BCBP_BAG_TAG_LEN>,
+    /// First non-consecutive baggage tag licence plate number.
+    pub first_non_consecutive_baggage_tag: String<BCBP_BAG_TAG_LEN>,
+    /// Second non-consecutive baggage tag licence plate number.
+    pub second_non_consecutive_baggage_tag: String<BCBP_BAG_TAG_LEN>,
+}
+
+/// Conditional items that may be repeated for each flight leg.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct BcbpLegConditional {
+    /// Airline numeric code (ticketing prefix).
+    pub airline_numeric_code: String<BCBP_AIRLINE_NUMERIC_CODE_LEN>,
+    /// Document form/serial number.
+    pub document_serial_number: String<BCBP_DOCUMENT_SERIAL_LEN>,
+    /// Selectee indicator.
+    pub selectee_indicator: Option<u8>,
+    /// International documentation verification.
+    pub international_doc_verification: Option<u8>,
+    /// Marketing carrier designator.
+    pub marketing_carrier: String<BCBP_CARRIER_LEN>,
+    /// Frequent flyer airline designator.
+    pub frequent_flyer_airline: String<BCBP_CARRIER_LEN>,
+    /// Frequent flyer number.
+    pub frequent_flyer_number: String<BCBP_FREQUENT_FLYER_NUMBER_LEN>,
+    /// ID/AD indicator.
+    pub id_ad_indicator: Option<u8>,
+    /// Free baggage allowance.
+    pub free_baggage_allowance: String<BCBP_FREE_BAGGAGE_LEN>,
+    /// Fast track indicator.
+    pub fast_track: Option<u8>,
+}
+
+/// A single flight leg of a boarding pass.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BcbpLeg {
+    /// Operating carrier PNR code.
+    pub pnr: String<BCBP_PNR_LEN>,
+    /// IATA code of the departure airport.
+    pub from_airport: [u8; BCBP_AIRPORT_CODE_LEN],
+    /// IATA code of the arrival airport.
+    pub to_airport: [u8; BCBP_AIRPORT_CODE_LEN],
+    /// Operating carrier designator.
+    pub operating_carrier: String<BCBP_CARRIER_LEN>,
+    /// Flight number.
+    pub flight_number: String<BCBP_FLIGHT_NUMBER_LEN>,
+    /// Date of flight as a Julian day of year (raw, e.g. `326`).
+    pub flight_date: [u8; BCBP_FLIGHT_DATE_LEN],
+    /// Compartment code (e.g. `Y`, `J`, `F`).
+    pub compartment: u8,
+    /// Seat number.
+    pub seat: String<BCBP_SEAT_LEN>,
+    /// Check-in sequence number.
+    pub sequence_number: String<BCBP_SEQUENCE_NUMBER_LEN>,
+    /// Passenger status.
+    pub passenger_status: u8,
+    /// Repeated conditional items, if encoded for this leg.
+    pub conditional: Option<BcbpLegConditional>,
+    /// Free-form data for individual airline use.
+    pub airline_data: String<BCBP_VARIABLE_FIELD_MAX_LEN>,
+}
+
+impl BcbpLeg {
+    /// Returns the date of flight as a day of year (1–366), if it is numeric.
+    pub fn flight_day_of_year(&self) -> Option<u16> {
+        parse_decimal(&self.flight_date)
+    }
+}
+
+/// The security data block that may terminate a boarding pass.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BcbpSecurityData {
+    /// Type of security data.
+    pub kind: u8,
+    /// Raw security data (typically a digital signature).
+    pub data: String<BCBP_VARIABLE_FIELD_MAX_LEN>,
+}
+
+/// A parsed IATA Bar Coded Boarding Pass.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Bcbp {
+    /// Raw passenger name field (`SURNAME/GIVEN`, space padded).
+    pub passenger_name: String<BCBP_PASSENGER_NAME_LEN>,
+    /// Electronic ticket indicator (`E` for electronic ticket).
+    pub electronic_ticket_indicator: u8,
+    /// Unique conditional items, if present.
+    pub conditional: Option<BcbpConditional>,
+    /// Flight legs, in encoding order.
+    pub legs: Vec<BcbpLeg, BCBP_MAX_LEGS>,
+    /// Security data block, if present.
+    pub security_data: Option<BcbpSecurityData>,
+}
+
+impl Bcbp {
+    /// Returns [`DocumentKind::BoardingPass`]; boarding passes carry no document code.
+    pub fn document_kind(&self) -> DocumentKind {
+        DocumentKind::BoardingPass
+    }
+
+    /// Returns the passenger surname (the part of the name field before `/`).
+    pub fn surname(&self) -> &str {
+        let name = self.passenger_name.trim_end();
+        name.split('/').next().unwrap_or("")
+    }
+
+    /// Returns the passenger given names and title (the part of the name field after `/`).
+    pub fn given_names(&self) -> &str {
+        let name = self.passenger_name.trim_end();
+        name.split_once('/').map(|(_, given)| given).unwrap_or("")
+    }
+
+    /// Returns whether the ticket is an electronic ticket.
+    pub fn is_electronic_ticket(&self) -> bool {
+        self.electronic_ticket_indicator == b'E'
+    }
+}
+
+/// Sequential reader over the bytes of a boarding pass.
+struct Cursor<'a> {
+    data: &'a [u8],
+    pos: usize,
+    /// Column of `data[0]` in the boarding pass, for error positions.
+    base: usize,
+}
+
+impl<'a> Cursor<'a> {
+    fn new(data: &'a [u8]) -> Self {
+        Cursor {
+            data,
+            pos: 0,
+            base: 0,
+        }
+    }
+
+    /// Position of the next `n` bytes in the boarding pass.
+    fn position(&self, n: usize) -> ErrorPosition {
+        let start = self.base + self.pos;
+        ErrorPosition::new(0, start, start + n)
+    }
+
+    fn remaining(&self) -> usize {
+        self.data.len() - self.pos
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.data.get(self.pos).copied()
+    }
+
+    /// Takes exactly `n` bytes, failing if the input is too short.
+    fn take(&mut self, n: usize) -> Result<&'a [u8], MRZParseError> {
+        if self.remaining() < n {
+            return Err(MRZParseError::InvalidLength(Some(self.position(n))));
+        }
+        let slice = &self.data[self.pos..self.pos + n];
+        self.pos += n;
+        Ok(slice)
+    }
+
+    /// Takes up to `n` bytes; conditional items may be truncated by their field size.
+    fn take_up_to(&mut self, n: usize) -> &'a [u8] {
+        let n = n.min(self.remaining());
+        let slice = &self.data[self.pos..self.pos + n];
+        self.pos += n;
+        slice
+    }
+
+    fn take_byte(&mut self) -> Result<u8, MRZParseError> {
+        self.take(1).map(|b| b[0])
+    }
+
+    fn take_optional_byte(&mut self) -> Option<u8> {
+        self.take_up_to(1).first().copied()
+    }
+
+    /// Takes a two-character hexadecimal field size.
+    fn take_hex_size(&mut self) -> Result<usize, MRZParseError> {
+        let position = self.position(2);
+        let raw = self.take(2)?;
+        let text =
+            core::str::from_utf8(raw).map_err(|_| MRZParseError::Utf8Error(Some(position)))?;
+        usize::from_str_radix(text, 16).map_err(|_| MRZParseError::InvalidLength(Some(position)))
+    }
+
+    /// Splits off a sub-cursor over the next `n` bytes.
+    fn sub(&mut self, n: usize) -> Result<Cursor<'a>, MRZParseError> {
+        let base = self.base + self.pos;
+        self.take(n).map(|data| Cursor { data, pos: 0, base })
+    }
+
+    /// Takes exactly `n` bytes as text.
+    fn take_str<const N: usize>(&mut self, n: usize) -> Result<String<N>, MRZParseError> {
+        let position = self.position(n);
+        decode(self.take(n)?, position)
+    }
+
+    /// Takes up to `n` bytes as text.
+    fn take_str_up_to<const N: usize>(&mut self, n: usize) -> Result<String<N>, MRZParseError> {
+        let position = self.position(n.min(self.remaining()));
+        decode(self.take_up_to(n), position)
+    }
+
+    /// Takes the remaining bytes as text.
+    fn rest_str<const N: usize>(&mut self) -> Result<String<N>, MRZParseError> {
+        self.take_str_up_to(self.remaining())
+    }
+}
+
+// Decodes the bytes found at `position`, pointing at the first invalid byte on failure.
+fn decode<const N: usize>(
+    slice: &[u8],
+    position: ErrorPosition,
+) -> Result<String<N>, MRZParseError> {
+    let text = core::str::from_utf8(slice).map_err(|e| {
+        let column = position.start + e.valid_up_to();
+        MRZParseError::Utf8Error(Some(ErrorPosition::at(position.line, column)))
+    })?;
+    let mut out = String::new();
+    out.push_str(text)
+        .map_err(|_| MRZParseError::InvalidLength(Some(position)))?;
+    Ok(out)
+}
+
+fn fixed<const N: usize>(slice: &[u8]) -> [u8; N] {
+    slice.try_into().unwrap_or([b' '; N])
+}
+
+fn parse_decimal(slice: &[u8]) -> Option<u16> {
+    core::str::from_utf8(slice).ok()?.trim().parse().ok()
+}
+
+fn parse_unique_conditional(
+    cursor: &mut Cursor<'_>,
+    version: u8,
+) -> Result<BcbpConditional, MRZParseError> {
+    let size = cursor.take_hex_size()?;
+    let mut unique = cursor.sub(size)?;
+
+    Ok(BcbpConditional {
+        version,
+        passenger_description: unique.take_optional_byte(),
+        source_of_check_in: unique.take_optional_byte(),
+        source_of_issuance: unique.take_optional_byte(),
+        issue_date: unique.take_str_up_to(BCBP_ISSUE_DATE_LEN)?,
+        document_type: unique.take_optional_byte(),
+        issuer_designator: unique.take_str_up_to(BCBP_CARRIER_LEN)?,
+        baggage_tag: unique.take_str_up_to(BCBP_BAG_TAG_LEN)?,
+        first_non_consecutive_baggage_tag: unique.take_str_up_to(BCBP_BAG_TAG_LEN)?,
+        second_non_consecutive_baggage_tag: unique.take_str_up_to(BCBP_BAG_TAG_LEN)?,
+    })
+}
+
+fn parse_leg_conditional(
+    cursor: &mut Cursor<'_>,
+) -> Result<Option<BcbpLegConditional>, MRZParseError> {
+    if cursor.remaining() < 2 {
+        return Ok(None);
+    }
+    let size = cursor.take_hex_size()?;
+    if size == 0 {
+        return Ok(None);
+    }
+    let mut repeated = cursor.sub(size)?;
+
+    Ok(Some(BcbpLegConditional {
+        airline_numeric_code: repeated.take_str_up_to(BCBP_AIRLINE_NUMERIC_CODE_LEN)?,
+        document_serial_number: repeated.take_str_up_to(BCBP_DOCUMENT_SERIAL_LEN)?,
+        selectee_indicator: repeated.take_optional_byte(),
+        international_doc_verification: repeated.take_optional_byte(),
+        marketing_carrier: repeated.take_str_up_to(BCBP_CARRIER_LEN)?,
+        frequent_flyer_airline: repeated.take_str_up_to(BCBP_CARRIER_LEN)?,
+        frequent_flyer_number: repeated.take_str_up_to(BCBP_FREQUENT_FLYER_NUMBER_LEN)?,
+        id_ad_indicator: repeated.take_optional_byte(),
+        free_baggage_allowance: repeated.take_str_up_to(BCBP_FREE_BAGGAGE_LEN)?,
+        fast_track: repeated.take_optional_byte(),
+    }))
+}
+
+fn parse_leg(
+    cursor: &mut Cursor<'_>,
+    first: bool,
+    conditional: &mut Option<BcbpConditional>,
+) -> Result<BcbpLeg, MRZParseError> {
+    let pnr = cursor.take_str(BCBP_PNR_LEN)?;
+    let from_airport = fixed(cursor.take(BCBP_AIRPORT_CODE_LEN)?);
+    let to_airport = fixed(cursor.take(BCBP_AIRPORT_CODE_LEN)?);
+    let operating_carrier = cursor.take_str(BCBP_CARRIER_LEN)?;
+    let flight_number = cursor.take_str(BCBP_FLIGHT_NUMBER_LEN)?;
+    let flight_date = fixed(cursor.take(BCBP_FLIGHT_DATE_LEN)?);
+    let compartment = cursor.take_byte()?;
+    let seat = cursor.take_str(BCBP_SEAT_LEN)?;
+    let sequence_number = cursor.take_str(BCBP_SEQUENCE_NUMBER_LEN)?;
+    let passenger_status = cursor.take_byte()?;
+
+    let variable_size = cursor.take_hex_size()?;
+    let mut variable = cursor.sub(variable_size)?;
+
+    // The unique conditional items are only carried by the first leg and are
+    // introduced by the version number marker.
+    let mut has_conditional = !first;
+    if first && variable.peek() == Some(VERSION_MARKER) {
+        variable.take_byte()?;
+        let version = variable.take_byte()?;
+        *conditional = Some(parse_unique_conditional(&mut variable, version)?);
+        has_conditional = true;
+    }
+
+    let leg_conditional = if has_conditional {
+        parse_leg_conditional(&mut variable)?
+    } else {
+        None
+    };
+
+    Ok(BcbpLeg {
+        pnr,
+        from_airport,
+        to_airport,
+        operating_carrier,
+        flight_number,
+        flight_date,
+        compartment,
+        seat,
+        sequence_number,
+        passenger_status,
+        conditional: leg_conditional,
+        airline_data: variable.rest_str()?,
+    })
+}
+
+fn parse_security_data(cursor: &mut Cursor<'_>) -> Result<Option<BcbpSecurityData>, MRZParseError> {
+    if cursor.peek() != Some(SECURITY_MARKER) {
+        return Ok(None);
+    }
+    cursor.take_byte()?;
+    let kind = cursor.take_byte()?;
+    let size = cursor.take_hex_size()?;
+    let data = cursor.take_str(size)?;
+    Ok(Some(BcbpSecurityData { kind, data }))
+}
+
+/// Parses a single-line IATA Bar Coded Boarding Pass.
+///
+/// # Arguments
+///
+/// * `line` - The raw boarding pass data, starting with the format code `M`.
+///
+/// # Returns
+///
+/// A `Result` containing the parsed `Bcbp` on success, or an `MRZParseError` if the
+/// data is truncated, a hex field size is malformed, or the leg count is out of range.
+pub fn parse_bcbp(line: &[u8]) -> Result<Bcbp, MRZParseError> {
+    let mut cursor = Cursor::new(line);
+
+    if cursor.take_byte()? != FORMAT_CODE {
+        return Err(MRZParseError::UnknownFormat);
+    }
+    let leg_count = match cursor.take_byte()? {
+        c @ b'1'..=b'4' => (c - b'0') as usize,
+        _ => return Err(MRZParseError::UnknownFormat),
+    };
+    let passenger_name = cursor.take_str(BCBP_PASSENGER_NAME_LEN)?;
+    let electronic_ticket_indicator = cursor.take_byte()?;
+
+    let mut conditional = None;
+    let mut legs = Vec::new();
+    for i in 0..leg_count {
+        let start = cursor.position(0).start;
+        let leg = parse_leg(&mut cursor, i == 0, &mut conditional)?;
+        let end = cursor.position(0).start;
+        legs.push(leg)
+            .map_err(|_| MRZParseError::InvalidLength(Some(ErrorPosition::new(0, start, end))))?;
+    }
+
+    let security_data = parse_security_data(&mut cursor)?;
+
+    Ok(Bcbp {
+        passenger_name,
+        electronic_ticket_indicator,
+        conditional,
+        legs,
+        security_data,
+    })
+}
@@ -1,4 +1,114 @@
-use crate::MRZChecksumError;
+use crate::{MRZChecksumError, ICAO_EXTENDED_DOC_NUM_MAX_LEN};
+use heapless::{String, Vec};
+
+/// Maximum number of checksum-valid corrections kept for a field.
+pub const MAX_CORRECTION_CANDIDATES: usize = 4;
+
+/// A corrected field value that satisfies its check digit.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CorrectionCandidate {
+    /// The corrected value, without trailing fillers.
+    pub value: String<ICAO_EXTENDED_DOC_NUM_MAX_LEN>,
+    /// The check digit the value satisfies.
+    pub check_digit: u8,
+    /// Total cost of the substitutions, see [`crate::confusion::ConfusionModel`].
+    pub cost: u32,
+}
+
+/// Checksum-valid corrections of a field, cheapest first; see [`CheckedField::candidates`].
+pub type CorrectionCandidates = Vec<CorrectionCandidate, MAX_CORRECTION_CANDIDATES>;
+
+// Candidates only exist when OCR correction changed a value, so with `alloc` they are
+// boxed rather than reserving room for them in every checked field.
+#[cfg(feature = "alloc")]
+type CandidateStorage = Option<alloc::boxed::Box<CorrectionCandidates>>;
+#[cfg(not(feature = "alloc"))]
+type CandidateStorage = CorrectionCandidates;
+
+/// Maximum number of substitutions recorded in a [`Correction`].
+pub const MAX_SUBSTITUTIONS: usize = ICAO_EXTENDED_DOC_NUM_MAX_LEN;
+
+/// A character replaced by OCR correction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Substitution {
+    /// Index of the character in the field value.
+    pub position: u8,
+    /// The character as read.
+    pub from: u8,
+    /// The character it was corrected to.
+    pub to: u8,
+}
+
+/// How OCR correction changed the value of a [`CheckedField`].
+///
+/// Only the substitutions are stored; the value as read is rebuilt from the value the
+/// field holds with [`Correction::raw`].
+///
+/// # Examples
+///
+/// ```
+/// use mrz_core::checked_field::{Correction, Substitution};
+///
+/// let correction = Correction::new("74O8I2", "740812");
+/// assert_eq!(
+///     correction.substitutions,
+///     [
+///         Substitution { position: 2, from: b'O', to: b'0' },
+///         Substitution { position: 4, from: b'I', to: b'1' },
+///     ]
+/// );
+/// assert_eq!(correction.raw("740812"), "74O8I2");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Correction {
+    /// The characters that differ between the value as read and the corrected value,
+    /// by position.
+    pub substitutions: Vec<Substitution, MAX_SUBSTITUTIONS>,
+}
+
+impl Correction {
+    /// Records the correction of `raw` into `corrected`, which substitutes characters
+    /// in place and so has the same length.
+    pub fn new(raw: &str, corrected: &str) -> Self {
+        let mut substitutions = Vec::new();
+        let pairs = raw.bytes().zip(corrected.bytes()).enumerate();
+        for (position, (from, to)) in pairs.filter(|(_, (from, to))| from != to) {
+            let Ok(position) = u8::try_from(position) else {
+                break;
+            };
+            let _ = substitutions.push(Substitution { position, from, to });
+        }
+        Correction { substitutions }
+    }
+
+    /// Rebuilds the value as read from `corrected`, the value the field holds, by
+    /// undoing the substitutions.
+    pub fn raw(&self, corrected: &str) -> String<ICAO_EXTENDED_DOC_NUM_MAX_LEN> {
+        let mut raw = String::new();
+        for (position, c) in corrected.chars().enumerate() {
+            let substitution = self
+                .substitutions
+                .iter()
+                .find(|s| usize::from(s.position) == position);
+            let _ = raw.push(substitution.map_or(c, |s| char::from(s.from)));
+        }
+        raw
+    }
+}
+
+/// How the value of a [`CheckedField`] relates to its check digit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CorrectionOutcome {
+    /// The value agreed with its check digit as read.
+    Verified,
+    /// OCR correction found a single cheapest value that satisfies the check digit.
+    Corrected,
+    /// Several values of equal cost satisfy the check digit; the field holds the first
+    /// one found, see [`CheckedField::candidates`].
+    Ambiguous,
+    /// The value does not agree with its check digit.
+    Invalid,
+}
 
 /// A wrapper for MRZ fields that may be accompanied by a checksum validation error.
 ///
@@ -13,6 +123,12 @@ pub struct CheckedField<T> {
     value: T,
     error: Option<MRZChecksumError>,
     check_digit: Option<u8>,
+    // The check digit as read, when OCR correction repaired it.
+    raw_check_digit: Option<u8>,
+    // Checksum-valid corrections, cheapest first, when OCR correction changed the value.
+    candidates: CandidateStorage,
+    // What OCR read and how correction changed it.
+    correction: Option<Correction>,
 }
 
 impl<T> CheckedField<T> {
@@ -28,9 +144,40 @@ impl<T> CheckedField<T> {
             value,
             error,
             check_digit,
+            raw_check_digit: None,
+            candidates: CandidateStorage::default(),
+            correction: None,
         }
     }
 
+    /// Records that the check digit was read as `raw` and repaired by OCR correction.
+    pub fn with_raw_check_digit(mut self, raw: u8) -> Self {
+        self.raw_check_digit = Some(raw);
+        self
+    }
+
+    /// Records the checksum-valid corrections found for the field, cheapest first.
+    ///
+    /// With the `alloc` feature the candidates are boxed, so fields without candidates
+    /// stay small.
+    pub fn with_candidates(mut self, candidates: CorrectionCandidates) -> Self {
+        #[cfg(feature = "alloc")]
+        {
+            self.candidates = (!candidates.is_empty()).then(|| candidates.into());
+        }
+        #[cfg(not(feature = "alloc"))]
+        {
+            self.candidates = candidates;
+        }
+        self
+    }
+
+    /// Records the value as read and the substitutions OCR correction applied to it.
+    pub fn with_correction(mut self, correction: Correction) -> Self {
+        self.correction = Some(correction);
+        self
+    }
+
     /// Returns `true` if the field is valid (i.e. has no checksum error).
     pub fn is_valid(&self) -> bool {
         self.error.is_none()
@@ -58,6 +205,9 @@ impl<T> CheckedField<T> {
             value: f(self.value),
             error: self.error,
             check_digit: self.check_digit,
+            raw_check_digit: self.raw_check_digit,
+            candidates: self.candidates,
+            correction: self.correction,
         }
     }
 
@@ -72,6 +222,9 @@ impl<T> CheckedField<T> {
             value: &self.value,
             error: self.error.clone(),
             check_digit: self.check_digit,
+            raw_check_digit: self.raw_check_digit,
+            candidates: self.candidates.clone(),
+            correction: self.correction.clone(),
         }
     }
 
@@ -85,6 +238,9 @@ impl<T> CheckedField<T> {
             value: self.value,
             error: self.error.map(|e| f(e).into()),
             check_digit: self.check_digit,
+            raw_check_digit: self.raw_check_digit,
+            candidates: self.candidates,
+            correction: self.correction,
         }
     }
 
@@ -93,6 +249,62 @@ impl<T> CheckedField<T> {
         self.check_digit
     }
 
+    /// Returns the check digit as read from the MRZ, which differs from
+    /// [`CheckedField::check_digit`] if OCR correction repaired it.
+    pub fn raw_check_digit(&self) -> Option<u8> {
+        self.raw_check_digit.or(self.check_digit)
+    }
+
+    /// Returns `true` if OCR correction repaired the check digit rather than the value.
+    pub fn is_check_digit_corrected(&self) -> bool {
+        self.raw_check_digit.is_some()
+    }
+
+    /// Returns the checksum-valid values found by OCR correction, cheapest first.
+    ///
+    /// Empty unless correction changed the value. The first candidate is the value of
+    /// the field; at most [`MAX_CORRECTION_CANDIDATES`] are kept. When the composite
+    /// check digit of the document singled out one of them, only that one is kept.
+    pub fn candidates(&self) -> &[CorrectionCandidate] {
+        #[cfg(feature = "alloc")]
+        {
+            self.candidates
+                .as_deref()
+                .map_or(&[], |candidates| candidates)
+        }
+        #[cfg(not(feature = "alloc"))]
+        {
+            &self.candidates
+        }
+    }
+
+    /// Returns the substitutions that corrected the value, if OCR correction satisfied
+    /// the check digit; [`Correction::raw`] rebuilds the value as read.
+    ///
+    /// The substitutions are empty when only the check digit was repaired, see
+    /// [`CheckedField::raw_check_digit`].
+    pub fn correction(&self) -> Option<&Correction> {
+        self.correction.as_ref()
+    }
+
+    /// Returns `true` if the two cheapest corrections tie, so the value may be wrong.
+    pub fn is_ambiguous(&self) -> bool {
+        matches!(self.candidates(), [first, second, ..] if first.cost == second.cost)
+    }
+
+    /// Returns how the value was validated or corrected.
+    pub fn correction_outcome(&self) -> CorrectionOutcome {
+        if self.error.is_some() {
+            CorrectionOutcome::Invalid
+        } else if self.is_ambiguous() {
+            CorrectionOutcome::Ambiguous
+        } else if !self.candidates().is_empty() || self.is_check_digit_corrected() {
+            CorrectionOutcome::Corrected
+        } else {
+            CorrectionOutcome::Verified
+        }
+    }
+
     /// Sets the check digit.
     pub fn set_check_digit(&mut self, digit: u8) {
         self.check_digit = Some(digit);
@@ -106,25 +318,23 @@ impl<const N: usize> CheckedField<[u8; N]> {
     }
 
     /// Returns the value bytes combined with the check digit as a heapless::Vec<u8>.
-    pub fn as_slice_with_check(&self) -> heapless::Vec<u8, 32> {
-        let mut result = heapless::Vec::<u8, 32>::new();
-        dbg!(&self.check_digit);
+    pub fn as_slice_with_check(&self) -> Vec<u8, 32> {
+        let mut result = Vec::<u8, 32>::new();
         result.extend_from_slice(&self.value).ok();
         result.push(self.check_digit.unwrap_or(b'<')).ok();
         result
     }
 }
 
-impl<const N: usize> CheckedField<heapless::String<N>> {
+impl<const N: usize> CheckedField<String<N>> {
     /// Returns the length of value plus one for the check digit.
     pub const fn len_with_check() -> usize {
         N + 1
     }
 
     /// Returns the value bytes combined with the check digit as a heapless::Vec<u8>.
-    pub fn as_slice_with_check(&self) -> heapless::Vec<u8, 32> {
-        let mut result = heapless::Vec::<u8, 32>::new();
-        dbg!(&self.check_digit);
+    pub fn as_slice_with_check(&self) -> Vec<u8, 32> {
+        let mut result = Vec::<u8, 32>::new();
         result.extend_from_slice(self.value.as_bytes()).ok();
         result.push(self.check_digit.unwrap_or(b'<')).ok();
         result
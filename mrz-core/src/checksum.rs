@@ -16,23 +16,23 @@
 /// assert_eq!(checksum, Some(6));
 /// ```
 pub fn compute_checksum(data: &[u8]) -> Option<u8> {
-    fn char_value(c: u8) -> Option<u8> {
-        match c {
-            b'0'..=b'9' => Some(c - b'0'),
-            b'A'..=b'Z' => Some(c - b'A' + 10),
-            b'<' => Some(0),
-            _ => None,
-        }
-    }
-
-    let weights = [7, 3, 1];
     let mut sum: u32 = 0;
 
     for (i, &b) in data.iter().enumerate() {
-        let val = char_value(b)?;
-        let weight = weights[i % 3];
-        sum += val as u32 * weight as u32;
+        sum += weighted_value(i, b)?;
     }
 
     Some((sum % 10) as u8)
 }
+
+// The contribution of character `c` at `position` to the weighted checksum sum.
+pub(crate) fn weighted_value(position: usize, c: u8) -> Option<u32> {
+    let val = match c {
+        b'0'..=b'9' => c - b'0',
+        b'A'..=b'Z' => c - b'A' + 10,
+        b'<' => 0,
+        _ => return None,
+    };
+    let weights = [7, 3, 1];
+    Some(u32::from(val) * weights[position % 3])
+}
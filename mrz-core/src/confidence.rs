@@ -0,0 +1,113 @@
+use crate::confusion::{
+    class_allows, confusions_for, sort_by_cost, Confusion, ConfusionModel, MAX_CONFUSIONS,
+};
+use crate::CharClass;
+use heapless::Vec;
+
+/// Highest confidence of a character, meaning the OCR engine is certain of it.
+pub const MAX_CONFIDENCE: u8 = 100;
+
+/// Confidence below which OCR correction may substitute a character of a
+/// [`ScoredLine`], see [`crate::ParseOptions::confidence_threshold`].
+pub const DEFAULT_CONFIDENCE_THRESHOLD: u8 = 90;
+
+// Cost of an alternative glyph proposed by the OCR engine, before the confidence
+// penalty; as likely as the most common confusions of a model.
+const ALTERNATIVE_COST: u16 = 1;
+
+/// A line of OCR output with the engine's confidence in each character and, optionally,
+/// the other glyphs it considered.
+///
+/// Confidences range from 0 to [`MAX_CONFIDENCE`]. Characters without a confidence are
+/// treated as uncertain, so a line without confidences is corrected like plain input.
+///
+/// # Examples
+///
+/// ```
+/// use mrz_core::confidence::ScoredLine;
+///
+/// // The engine hesitated between `O` and `0` in the third character.
+/// let line = ScoredLine::new(b"L8O", &[99, 98, 40]).with_alternatives(&[b"", b"", b"0"]);
+/// assert_eq!(line.confidence(2), 40);
+/// assert_eq!(line.alternatives(2), b"0");
+/// assert!(line.alternatives(5).is_empty());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ScoredLine<'a> {
+    /// The characters as read.
+    pub text: &'a [u8],
+    /// Confidence of each character of `text`.
+    pub confidences: &'a [u8],
+    /// Other glyphs considered for each character of `text`, most likely first.
+    pub alternatives: &'a [&'a [u8]],
+}
+
+impl<'a> ScoredLine<'a> {
+    /// Creates a line with per-character confidences and no alternative glyphs.
+    pub const fn new(text: &'a [u8], confidences: &'a [u8]) -> Self {
+        ScoredLine {
+            text,
+            confidences,
+            alternatives: &[],
+        }
+    }
+
+    /// Sets the alternative glyphs of each character.
+    pub const fn with_alternatives(mut self, alternatives: &'a [&'a [u8]]) -> Self {
+        self.alternatives = alternatives;
+        self
+    }
+
+    /// Returns the confidence of the character at `column`, 0 if unknown.
+    pub fn confidence(&self, column: usize) -> u8 {
+        self.confidences
+            .get(column)
+            .map_or(0, |&confidence| confidence.min(MAX_CONFIDENCE))
+    }
+
+    /// Returns the alternative glyphs of the character at `column`.
+    pub fn alternatives(&self, column: usize) -> &'a [u8] {
+        self.alternatives.get(column).copied().unwrap_or_default()
+    }
+}
+
+/// Returns the substitutions worth trying for a character read as `c` with the given
+/// `confidence` and engine `alternatives`, cheapest first.
+///
+/// Characters read with at least `threshold` confidence are kept as read. Otherwise
+/// the alternatives legal in `class` come first, followed by the confusions of `model`
+/// (see [`confusions_for`]); every cost grows by one per 10 points of confidence, so a
+/// search spends its budget on the least certain characters first.
+pub fn scored_confusions(
+    model: &dyn ConfusionModel,
+    c: char,
+    class: CharClass,
+    confidence: u8,
+    alternatives: &[u8],
+    threshold: u8,
+) -> Vec<Confusion, MAX_CONFUSIONS> {
+    let mut confusions: Vec<Confusion, MAX_CONFUSIONS> = Vec::new();
+    if confidence >= threshold {
+        return confusions;
+    }
+    let penalty = u16::from(confidence.min(MAX_CONFIDENCE)) / 10;
+    let alternatives = alternatives
+        .iter()
+        .map(|&b| Confusion::new(b as char, ALTERNATIVE_COST))
+        .filter(|alternative| alternative.replacement != c)
+        .filter(|alternative| class_allows(class, alternative.replacement));
+    for confusion in alternatives.chain(confusions_for(model, c, class)) {
+        if confusions
+            .iter()
+            .any(|known| known.replacement == confusion.replacement)
+        {
+            continue;
+        }
+        let confusion = Confusion::new(confusion.replacement, confusion.cost + penalty);
+        if confusions.push(confusion).is_err() {
+            break;
+        }
+    }
+    sort_by_cost(&mut confusions);
+    confusions
+}
@@ -0,0 +1,201 @@
+use crate::CharClass;
+use heapless::Vec;
+
+/// Maximum number of confusions of a single character considered by the OCR search.
+pub const MAX_CONFUSIONS: usize = 8;
+
+/// A character that OCR may have produced in place of another, with the cost of
+/// assuming that substitution.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Confusion {
+    /// The character that was probably printed.
+    pub replacement: char,
+    /// Cost of the substitution; lower is more likely. The costs of several
+    /// substitutions add up.
+    pub cost: u16,
+}
+
+impl Confusion {
+    /// Creates a confusion with the given replacement and cost.
+    pub const fn new(replacement: char, cost: u16) -> Self {
+        Confusion { replacement, cost }
+    }
+}
+
+/// Which glyphs an OCR engine confuses, and how likely each confusion is.
+///
+/// Models are stored as `&'static dyn ConfusionModel` in [`crate::ParseOptions`], so
+/// they need no allocation and work in `no_std`. A table-driven model is a unit struct
+/// whose [`ConfusionModel::confusions`] returns static slices:
+///
+/// ```
+/// use mrz_core::confusion::{Confusion, ConfusionModel};
+/// use mrz_core::ParseOptions;
+///
+/// struct OnlyZeros;
+///
+/// impl ConfusionModel for OnlyZeros {
+///     fn confusions(&self, c: char) -> &[Confusion] {
+///         match c {
+///             'O' => &[Confusion { replacement: '0', cost: 1 }],
+///             _ => &[],
+///         }
+///     }
+/// }
+///
+/// let options = ParseOptions {
+///     confusion_model: &OnlyZeros,
+///     ..ParseOptions::default()
+/// };
+/// # let _ = options;
+/// ```
+pub trait ConfusionModel: Sync {
+    /// Returns the characters `c` may have been misread for, with their costs.
+    fn confusions(&self, c: char) -> &[Confusion];
+}
+
+impl core::fmt::Debug for dyn ConfusionModel {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("ConfusionModel")
+    }
+}
+
+// Models are compared by identity, so that `ParseOptions` stays comparable.
+impl PartialEq for dyn ConfusionModel {
+    fn eq(&self, other: &Self) -> bool {
+        core::ptr::addr_eq(self, other)
+    }
+}
+
+impl Eq for dyn ConfusionModel {}
+
+/// Confusions of the OCR-B font used in ICAO MRZs, as commonly reported by OCR engines.
+///
+/// Each character read maps to the characters it may stand for, at these costs:
+///
+/// | Read | Replacements |
+/// |------|--------------|
+/// | `0` | `O` 1, `D` 3, `Q` 4 |
+/// | `O` | `0` 1 |
+/// | `D` | `0` 2 |
+/// | `Q` | `0` 2, `O` 3 |
+/// | `1` | `I` 1, `L` 3 |
+/// | `I` | `1` 1 |
+/// | `L` | `1` 3 |
+/// | `2` / `Z` | `Z` / `2` 2 |
+/// | `5` / `S` | `S` / `5` 1 |
+/// | `6` / `G` | `G` / `6` 2 |
+/// | `7` / `T` | `T` / `7` 3 |
+/// | `8` / `B` | `B` / `8` 1 |
+/// | `U` / `V` | `V` / `U` 3 |
+/// | `<` | `K` 3 |
+/// | `K` | `<` 3, `X` 5 |
+/// | `H`, `N` | `M` 5 |
+/// | `E`, `P` | `F` 5 |
+///
+/// Pairs that OCR-B draws almost identically cost 1. Misreads of the sex markers `M`
+/// and `F`, and `K` for `X`, cost 5, the most of any confusion.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct IcaoOcrB;
+
+impl ConfusionModel for IcaoOcrB {
+    fn confusions(&self, c: char) -> &[Confusion] {
+        const fn to(replacement: char, cost: u16) -> Confusion {
+            Confusion::new(replacement, cost)
+        }
+        match c {
+            '0' => const { &[to('O', 1), to('D', 3), to('Q', 4)] },
+            'O' => const { &[to('0', 1)] },
+            'D' => const { &[to('0', 2)] },
+            'Q' => const { &[to('0', 2), to('O', 3)] },
+            '1' => const { &[to('I', 1), to('L', 3)] },
+            'I' => const { &[to('1', 1)] },
+            'L' => const { &[to('1', 3)] },
+            '2' => const { &[to('Z', 2)] },
+            'Z' => const { &[to('2', 2)] },
+            '5' => const { &[to('S', 1)] },
+            'S' => const { &[to('5', 1)] },
+            '6' => const { &[to('G', 2)] },
+            'G' => const { &[to('6', 2)] },
+            '7' => const { &[to('T', 3)] },
+            'T' => const { &[to('7', 3)] },
+            '8' => const { &[to('B', 1)] },
+            'B' => const { &[to('8', 1)] },
+            'U' => const { &[to('V', 3)] },
+            'V' => const { &[to('U', 3)] },
+            '<' => const { &[to('K', 3)] },
+            'K' => const { &[to('<', 3), to('X', 5)] },
+            'H' | 'N' => const { &[to('M', 5)] },
+            'E' | 'P' => const { &[to('F', 5)] },
+            _ => &[],
+        }
+    }
+}
+
+/// The four symmetric pairs `O`/`0`, `I`/`1`, `S`/`5` and `B`/`8` at unit cost, as
+/// returned by [`crate::ocr::ocr_substitutions`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct BasicPairs;
+
+impl ConfusionModel for BasicPairs {
+    fn confusions(&self, c: char) -> &[Confusion] {
+        match c {
+            'O' => const { &[Confusion::new('0', 1)] },
+            '0' => const { &[Confusion::new('O', 1)] },
+            'I' => const { &[Confusion::new('1', 1)] },
+            '1' => const { &[Confusion::new('I', 1)] },
+            'S' => const { &[Confusion::new('5', 1)] },
+            '5' => const { &[Confusion::new('S', 1)] },
+            'B' => const { &[Confusion::new('8', 1)] },
+            '8' => const { &[Confusion::new('B', 1)] },
+            _ => &[],
+        }
+    }
+}
+
+/// Returns the confusions of `c` under `model` that are legal in a field of `class`,
+/// cheapest first.
+///
+/// Characters already legal in a digit, letter, sex or filler field are never
+/// substituted, so a date keeps its digits and a country code its letters. Letter
+/// fields also accept the `<` filler. Alphanumeric fields accept every confusion.
+pub fn confusions_for(
+    model: &dyn ConfusionModel,
+    c: char,
+    class: CharClass,
+) -> Vec<Confusion, MAX_CONFUSIONS> {
+    let mut confusions: Vec<Confusion, MAX_CONFUSIONS> = match class {
+        CharClass::Alphanumeric | CharClass::Other => model
+            .confusions(c)
+            .iter()
+            .copied()
+            .take(MAX_CONFUSIONS)
+            .collect(),
+        _ if class_allows(class, c) => Vec::new(),
+        _ => model
+            .confusions(c)
+            .iter()
+            .copied()
+            .filter(|confusion| class_allows(class, confusion.replacement))
+            .take(MAX_CONFUSIONS)
+            .collect(),
+    };
+    sort_by_cost(&mut confusions);
+    confusions
+}
+
+// Whether `c` may appear in a field of `class`; letter fields also hold `<` fillers.
+pub(crate) fn class_allows(class: CharClass, c: char) -> bool {
+    c.is_ascii() && (class.contains(c as u8) || (class == CharClass::Letter && c == '<'))
+}
+
+// Insertion sort, which keeps the model's order among equal costs.
+pub(crate) fn sort_by_cost(confusions: &mut [Confusion]) {
+    for i in 1..confusions.len() {
+        let mut j = i;
+        while j > 0 && confusions[j - 1].cost > confusions[j].cost {
+            confusions.swap(j - 1, j);
+            j -= 1;
+        }
+    }
+}
@@ -0,0 +1,109 @@
+use core::fmt;
+
+/// Category of a machine-readable document, decoded from its two-character document code.
+///
+/// ICAO Doc 9303 reserves the first character for the document type (`P` passport,
+/// `I`/`A`/`C` official travel documents, `V` visa), while the second character is
+/// largely left to the issuing State. Well-known national usages of the second
+/// character are recognized; anything else falls back to the broad category of the
+/// first character, or to [`DocumentKind::Other`] when that is ambiguous.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DocumentKind {
+    /// Ordinary national passport (`P<`, `PP`, `PN` and other `P` codes).
+    Passport,
+    /// Diplomatic passport (`PD`).
+    DiplomaticPassport,
+    /// Official or service passport (`PO`, `PS`).
+    OfficialPassport,
+    /// Emergency or temporary passport (`PE`, `PU`).
+    EmergencyPassport,
+    /// National identity card (`I<`, `ID`, `IC`, `A<`, `C<` and other `I` codes).
+    IdentityCard,
+    /// Residence permit (`IR`, `AR`, `CR`, `RP`).
+    ResidencePermit,
+    /// Crew member certificate (`AC`).
+    CrewMemberCertificate,
+    /// Machine-readable visa (`V<`, `VA`, `VB`, `VC`, ...).
+    Visa,
+    /// Travel permit or refugee/stateless travel document (`IT`, `CT`, `PT`).
+    TravelPermit,
+    /// Passport card, a TD1 card issued alongside a passport (`IP`, e.g. the Irish
+    /// passport card). Not a passport booklet, so [`DocumentKind::is_passport`] is `false`.
+    PassportCard,
+    /// Driving licence (`D1`, `D<` as used by ISO/IEC 18013-1 licences, Swiss `FA`).
+    DrivingLicence,
+    /// IATA bar coded boarding pass, which carries no document code.
+    BoardingPass,
+    /// Unrecognized document code, kept verbatim.
+    Other([u8; 2]),
+}
+
+impl DocumentKind {
+    /// Decodes a document code as found in the MRZ.
+    ///
+    /// Codes shorter than two characters are padded with the `<` filler, so both
+    /// `b"P<"` and the filler-stripped `b"P"` decode to [`DocumentKind::Passport`].
+    pub fn from_code(code: &[u8]) -> Self {
+        let first = code.first().copied().unwrap_or(b'<').to_ascii_uppercase();
+        let second = code.get(1).copied().unwrap_or(b'<').to_ascii_uppercase();
+
+        match (first, second) {
+            (b'P', b'D') => DocumentKind::DiplomaticPassport,
+            (b'P', b'O' | b'S') => DocumentKind::OfficialPassport,
+            (b'P', b'E' | b'U') => DocumentKind::EmergencyPassport,
+            (b'P', b'T') => DocumentKind::TravelPermit,
+            (b'P', _) => DocumentKind::Passport,
+            (b'V', _) => DocumentKind::Visa,
+            (b'A', b'C') => DocumentKind::CrewMemberCertificate,
+            (b'I' | b'A' | b'C', b'R') | (b'R', b'P') => DocumentKind::ResidencePermit,
+            (b'I' | b'C', b'T') => DocumentKind::TravelPermit,
+            (b'I', b'P') => DocumentKind::PassportCard,
+            (b'A' | b'C', b'<') => DocumentKind::IdentityCard,
+            (b'I', _) => DocumentKind::IdentityCard,
+            (b'D', b'1' | b'<') | (b'F', b'A') => DocumentKind::DrivingLicence,
+            _ => DocumentKind::Other([first, second]),
+        }
+    }
+
+    /// Returns `true` for all passport kinds.
+    pub fn is_passport(&self) -> bool {
+        matches!(
+            self,
+            DocumentKind::Passport
+                | DocumentKind::DiplomaticPassport
+                | DocumentKind::OfficialPassport
+                | DocumentKind::EmergencyPassport
+        )
+    }
+
+    /// Returns a stable, lowercase identifier for the kind (e.g. `"passport"`),
+    /// suitable for FFI consumers. Unrecognized codes map to `"other"`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DocumentKind::Passport => "passport",
+            DocumentKind::DiplomaticPassport => "diplomatic_passport",
+            DocumentKind::OfficialPassport => "official_passport",
+            DocumentKind::EmergencyPassport => "emergency_passport",
+            DocumentKind::IdentityCard => "identity_card",
+            DocumentKind::ResidencePermit => "residence_permit",
+            DocumentKind::CrewMemberCertificate => "crew_member_certificate",
+            DocumentKind::Visa => "visa",
+            DocumentKind::TravelPermit => "travel_permit",
+            DocumentKind::PassportCard => "passport_card",
+            DocumentKind::DrivingLicence => "driving_licence",
+            DocumentKind::BoardingPass => "boarding_pass",
+            DocumentKind::Other(_) => "other",
+        }
+    }
+}
+
+impl fmt::Display for DocumentKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DocumentKind::Other(code) => {
+                write!(f, "other({}{})", code[0] as char, code[1] as char)
+            }
+            kind => f.write_str(kind.as_str()),
+        }
+    }
+}
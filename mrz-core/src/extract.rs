@@ -0,0 +1,252 @@
+use crate::normalize::canonical;
+use crate::parser::{detect_format, parse_any_with};
+use crate::{ChecksumPolicy, MRZFormat, MrzIcaoCommonFields, ParseOptions, ParsedMRZ};
+use heapless::{String, Vec};
+
+/// Length of the longest MRZ line (TD3 and MRV-A).
+pub const MAX_MRZ_LINE_LEN: usize = 44;
+/// Maximum number of MRZs returned by [`extract_mrz`].
+pub const MAX_EXTRACTED_MRZ: usize = 4;
+
+// Lines that do not carry check digits may lose any number of trailing fillers to
+// OCR, but must keep at least the document code and issuing state.
+const MIN_LINE_LEN: usize = 5;
+// Data lines may lose at most this many trailing characters.
+const MAX_DATA_LINE_SHORTFALL: usize = 2;
+// Minimum share of MRZ alphabet characters, in percent, for a line to qualify.
+const MIN_DENSITY_PERCENT: usize = 90;
+
+const CHECK_DIGIT_POINTS: u32 = 100;
+const EXACT_LENGTH_POINTS: u32 = 10;
+
+/// A window of lines in a block of text that looks like an MRZ.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MrzCandidate {
+    /// Format detected for the window.
+    pub format: MRZFormat,
+    /// Index of the first line of the window in the text, counting blank lines.
+    pub first_line: usize,
+    /// Index of the last line of the window in the text.
+    pub last_line: usize,
+    /// Canonical lines padded to the layout length, ready for [`crate::parser::parse_any`].
+    pub lines: Vec<String<MAX_MRZ_LINE_LEN>, 3>,
+    /// Confidence score; higher is better. See [`scan`].
+    pub score: u32,
+    /// Number of check digits that agree with their data.
+    pub checks_passed: u8,
+    /// Number of check digits in the window.
+    pub checks_total: u8,
+}
+
+impl MrzCandidate {
+    /// Returns the lines as byte slices, as expected by [`crate::parser::parse_any`].
+    pub fn line_bytes(&self) -> Vec<&[u8], 3> {
+        self.lines.iter().map(|line| line.as_bytes()).collect()
+    }
+
+    /// Returns `true` if both candidates share a line of the text.
+    pub fn overlaps(&self, other: &MrzCandidate) -> bool {
+        self.first_line <= other.last_line && other.first_line <= self.last_line
+    }
+}
+
+/// Returns the best non-overlapping MRZs found in `text`, in text order.
+///
+/// Overlapping candidates, such as a TD2 reading of two lines of a TD1, are resolved in
+/// favour of the higher score. At most [`MAX_EXTRACTED_MRZ`] MRZs are returned.
+pub fn extract_mrz(text: &str) -> Vec<MrzCandidate, MAX_EXTRACTED_MRZ> {
+    let mut chosen: Vec<MrzCandidate, MAX_EXTRACTED_MRZ> = Vec::new();
+    for candidate in scan(text) {
+        let beaten = chosen
+            .iter()
+            .filter(|c| c.overlaps(&candidate))
+            .all(|c| c.score < candidate.score);
+        if !beaten {
+            continue;
+        }
+        chosen.retain(|c| !c.overlaps(&candidate));
+        let _ = chosen.push(candidate);
+    }
+    chosen.sort_unstable_by_key(|c| c.first_line);
+    chosen
+}
+
+/// Scans arbitrary text for windows of TD1 (3x30), TD2 (2x36) and TD3 (2x44) lines.
+///
+/// Each line is trimmed and canonicalized as by [`crate::normalize::normalize`]. A window
+/// qualifies when every line has at least 90% MRZ alphabet characters, its data lines
+/// are at most two characters short, it starts with a document code letter, and at
+/// least half of its check digits agree. Short lines are padded with `<`.
+///
+/// The score is 100 points per agreeing check digit, plus the average alphabet density
+/// in percent, plus 10 points per line at its exact layout length. Candidates are
+/// yielded in text order and may overlap; see [`extract_mrz`].
+pub fn scan(text: &str) -> Scan<'_> {
+    Scan {
+        lines: text.split('\n'),
+        index: 0,
+        window: [None, None, None],
+        pending: Vec::new(),
+    }
+}
+
+/// Iterator returned by [`scan`].
+#[derive(Debug)]
+pub struct Scan<'a> {
+    lines: core::str::Split<'a, char>,
+    index: usize,
+    // The last three non-blank lines; `None` for lines that cannot be part of an MRZ.
+    window: [Option<WindowLine>; 3],
+    pending: Vec<MrzCandidate, 2>,
+}
+
+#[derive(Debug, Clone)]
+struct WindowLine {
+    index: usize,
+    text: String<MAX_MRZ_LINE_LEN>,
+    density_percent: usize,
+}
+
+impl Iterator for Scan<'_> {
+    type Item = MrzCandidate;
+
+    fn next(&mut self) -> Option<MrzCandidate> {
+        loop {
+            if let Some(candidate) = self.pending.pop() {
+                return Some(candidate);
+            }
+            let line = self.lines.next()?;
+            let index = self.index;
+            self.index += 1;
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            self.window.rotate_left(1);
+            self.window[2] = window_line(line, index);
+
+            // At most one two-line reading applies, as the data line length decides it.
+            for (layout_len, line_count) in [(30, 3), (36, 2), (44, 2)] {
+                let lines = &self.window[3 - line_count..];
+                if let Some(candidate) = evaluate(lines, layout_len) {
+                    let _ = self.pending.push(candidate);
+                }
+            }
+        }
+    }
+}
+
+fn window_line(line: &str, index: usize) -> Option<WindowLine> {
+    let mut text = String::new();
+    let mut mrz_chars = 0;
+    for c in line.trim().chars() {
+        let c = canonical(c).map_or(c, |(replacement, _)| replacement as char);
+        if c.is_ascii_uppercase() || c.is_ascii_digit() || c == '<' {
+            mrz_chars += 1;
+        }
+        text.push(c).ok()?;
+    }
+    let len = text.chars().count();
+    let density_percent = mrz_chars * 100 / len;
+    (len >= MIN_LINE_LEN && density_percent >= MIN_DENSITY_PERCENT).then_some(WindowLine {
+        index,
+        text,
+        density_percent,
+    })
+}
+
+fn evaluate(window: &[Option<WindowLine>], layout_len: usize) -> Option<MrzCandidate> {
+    let mut lines: Vec<String<MAX_MRZ_LINE_LEN>, 3> = Vec::new();
+    let mut score = 0;
+    let mut density = 0;
+    for (position, line) in window.iter().enumerate() {
+        let line = line.as_ref()?;
+        let len = line.text.len();
+        // The second line of every layout carries the check digits.
+        let min_len = if position == 1 {
+            layout_len - MAX_DATA_LINE_SHORTFALL
+        } else {
+            MIN_LINE_LEN
+        };
+        if len < min_len || len > layout_len {
+            return None;
+        }
+        if len == layout_len {
+            score += EXACT_LENGTH_POINTS;
+        }
+        density += line.density_percent;
+
+        let mut padded = line.text.clone();
+        while padded.len() < layout_len {
+            padded.push('<').ok()?;
+        }
+        lines.push(padded).ok()?;
+    }
+    if !lines[0].as_bytes()[0].is_ascii_uppercase() {
+        return None;
+    }
+
+    let (format, document) = {
+        let bytes: Vec<&[u8], 3> = lines.iter().map(|line| line.as_bytes()).collect();
+        let format = detect_format(&bytes);
+        if matches!(
+            format,
+            MRZFormat::Unknown | MRZFormat::MrzSwissDrivingLicence
+        ) {
+            return None;
+        }
+        let options = ParseOptions {
+            ocr_correction: false,
+            checksum_policy: ChecksumPolicy::Record,
+            ..ParseOptions::default()
+        };
+        (format, parse_any_with(&bytes, &options).ok()?)
+    };
+    let (checks_passed, checks_total) = check_digits(&document);
+    if checks_total == 0 || checks_passed * 2 < checks_total {
+        return None;
+    }
+    score += CHECK_DIGIT_POINTS * u32::from(checks_passed);
+    score += (density / window.len()) as u32;
+
+    Some(MrzCandidate {
+        format,
+        first_line: window[0].as_ref()?.index,
+        last_line: window[window.len() - 1].as_ref()?.index,
+        lines,
+        score,
+        checks_passed,
+        checks_total,
+    })
+}
+
+// Counts the agreeing check digits of a document and its check digits in total.
+pub(crate) fn check_digits(document: &ParsedMRZ) -> (u8, u8) {
+    fn count(checks: &[Option<bool>]) -> (u8, u8) {
+        let passed = checks.iter().filter(|c| **c == Some(true)).count() as u8;
+        let total = checks.iter().filter(|c| c.is_some()).count() as u8;
+        (passed, total)
+    }
+    fn common(mrz: &impl MrzIcaoCommonFields) -> (u8, u8) {
+        count(&[
+            Some(mrz.is_document_number_valid()),
+            Some(mrz.is_birth_date_valid()),
+            Some(mrz.is_expiry_date_valid()),
+            mrz.is_final_check_valid(),
+        ])
+    }
+
+    match document {
+        ParsedMRZ::MrzIcaoTd3(mrz) => common(mrz),
+        ParsedMRZ::MrzIcaoTd1(mrz) => common(mrz),
+        ParsedMRZ::MrzIcaoTd2(mrz) => common(mrz),
+        ParsedMRZ::MrzIcaoMrvA(mrz) => common(mrz),
+        ParsedMRZ::MrzIcaoMrvB(mrz) => common(mrz),
+        ParsedMRZ::MrzFraId(fra) => count(&[
+            Some(fra.is_card_number_valid()),
+            Some(fra.is_birth_date_valid()),
+            Some(fra.is_final_check_valid()),
+        ]),
+        _ => (0, 0),
+    }
+}
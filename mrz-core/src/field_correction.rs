@@ -1,9 +1,17 @@
-use crate::checked_field::CheckedField;
-use crate::checksum::compute_checksum;
-use crate::ocr::ocr_permutations;
-use crate::{MRZChecksumError, MRZParseError};
+use crate::checked_field::{
+    CheckedField, Correction, CorrectionCandidate, CorrectionCandidates, MAX_CORRECTION_CANDIDATES,
+};
+use crate::confidence::{scored_confusions, ScoredLine};
+use crate::confusion::{confusions_for, Confusion, ConfusionModel, IcaoOcrB, MAX_CONFUSIONS};
+use crate::ocr::OcrPermutations;
+use crate::options::ParseOptions;
+use crate::{CharClass, MRZChecksumError, MRZParseError};
 
 use core::str::FromStr;
+use heapless::{String, Vec};
+
+/// Maximum number of parts combined by [`cheapest_combination`].
+pub const MAX_COMBINED_PARTS: usize = 8;
 
 /// Tries to correct a field with OCR permutations until the checksum is valid.
 ///
@@ -14,35 +22,273 @@ use core::str::FromStr;
 /// - `field_kind`: The specific `MRZChecksumError` to return on failure.
 ///
 /// # Returns
-/// `CheckedField<T>` if a corrected permutation passes the checksum and parses successfully,
-/// or an appropriate `MRZParseError` if none match.
+/// `CheckedField<T>` holding the cheapest permutation that passes the checksum and parses
+/// successfully, or an appropriate `MRZParseError` if none match. Every matching
+/// permutation is ranked in [`CheckedField::candidates`]; when the two cheapest tie,
+/// the field reports [`crate::checked_field::CorrectionOutcome::Ambiguous`].
 pub fn correct_checked_field<const N: usize, const M: usize, T>(
     raw: &str,
     expected_checksum: char,
     max_depth: usize,
     field_kind: MRZChecksumError,
 ) -> Result<CheckedField<T>, MRZParseError>
+where
+    T: FromStr,
+    <T as FromStr>::Err: core::fmt::Debug,
+{
+    correct_checked_field_within::<N, M, T>(raw, expected_checksum, max_depth, M, field_kind)
+}
+
+/// Like [`correct_checked_field`], but examines at most `budget` candidates,
+/// cheapest first under the [`IcaoOcrB`] confusion model.
+///
+/// Candidates are enumerated lazily by [`OcrPermutations`], so the work and stack use
+/// follow `budget`; `M` only caps it.
+pub fn correct_checked_field_within<const N: usize, const M: usize, T>(
+    raw: &str,
+    expected_checksum: char,
+    max_depth: usize,
+    budget: usize,
+    field_kind: MRZChecksumError,
+) -> Result<CheckedField<T>, MRZParseError>
+where
+    T: FromStr,
+    <T as FromStr>::Err: core::fmt::Debug,
+{
+    correct_checked_field_as::<N, M, T>(
+        raw,
+        expected_checksum,
+        CharClass::Alphanumeric,
+        &IcaoOcrB,
+        max_depth,
+        budget,
+        field_kind,
+    )
+}
+
+/// Like [`correct_checked_field_within`], but tries the confusions of `model` in order
+/// of increasing cost, and only those that move characters into `class`, the character
+/// class of the field (e.g. digits for dates); see [`crate::confusion::confusions_for`].
+///
+/// A check digit misread as a letter (`O` for `0`, `S` for `5`) is repaired as well;
+/// the returned field then reports [`CheckedField::is_check_digit_corrected`].
+pub fn correct_checked_field_as<const N: usize, const M: usize, T>(
+    raw: &str,
+    expected_checksum: char,
+    class: CharClass,
+    model: &dyn ConfusionModel,
+    max_depth: usize,
+    budget: usize,
+    field_kind: MRZChecksumError,
+) -> Result<CheckedField<T>, MRZParseError>
+where
+    T: FromStr,
+    <T as FromStr>::Err: core::fmt::Debug,
+{
+    correct_with::<N, M, T>(
+        raw,
+        expected_checksum,
+        model,
+        max_depth,
+        budget,
+        field_kind,
+        |_, c| confusions_for(model, c, class),
+    )
+}
+
+/// Like [`correct_checked_field_as`], for a field read with per-character confidences,
+/// using the confusion model, depth and budget of `options`.
+///
+/// Only characters read with less than [`ParseOptions::confidence_threshold`] are
+/// substituted, so every candidate differs from the input in uncertain positions only.
+/// The least certain characters and the engine's alternative glyphs are tried first; see
+/// [`scored_confusions`].
+pub fn correct_scored_field<const N: usize, const M: usize, T>(
+    field: &ScoredLine<'_>,
+    expected_checksum: char,
+    class: CharClass,
+    options: &ParseOptions,
+    field_kind: MRZChecksumError,
+) -> Result<CheckedField<T>, MRZParseError>
+where
+    T: FromStr,
+    <T as FromStr>::Err: core::fmt::Debug,
+{
+    let raw = core::str::from_utf8(field.text)
+        .map_err(|_| MRZParseError::from_checksum(field_kind.clone()))?;
+    correct_with::<N, M, T>(
+        raw,
+        expected_checksum,
+        options.confusion_model,
+        options.correction_depth,
+        options.correction_budget,
+        field_kind,
+        |i, c| {
+            scored_confusions(
+                options.confusion_model,
+                c,
+                class,
+                field.confidence(i),
+                field.alternatives(i),
+                options.confidence_threshold,
+            )
+        },
+    )
+}
+
+// Searches the permutations of `raw` built from `substitutions` for values that satisfy
+// the check digit, which is repaired with the numeric confusions of `model`.
+fn correct_with<const N: usize, const M: usize, T>(
+    raw: &str,
+    expected_checksum: char,
+    model: &dyn ConfusionModel,
+    max_depth: usize,
+    budget: usize,
+    field_kind: MRZChecksumError,
+    substitutions: impl Fn(usize, char) -> Vec<Confusion, MAX_CONFUSIONS>,
+) -> Result<CheckedField<T>, MRZParseError>
 where
     T: FromStr,
     <T as FromStr>::Err: core::fmt::Debug,
 {
     let cleaned = raw.trim_end_matches('<');
 
-    let permutations = ocr_permutations::<N, M>(cleaned, max_depth);
-    #[cfg(test)]
-    dbg!(&permutations);
+    // A misread check digit is repaired with the numeric confusion set; a check digit
+    // read as a digit is trusted.
+    let check_digits: Vec<Confusion, MAX_CONFUSIONS> = if expected_checksum.is_ascii_digit() {
+        Vec::from_slice(&[Confusion::new(expected_checksum, 0)]).unwrap_or_default()
+    } else {
+        confusions_for(model, expected_checksum, CharClass::Digit)
+    };
+    if check_digits.is_empty() {
+        return Err(MRZParseError::from_checksum(field_kind));
+    }
 
-    let expected = expected_checksum
-        .to_digit(10)
-        .ok_or(MRZParseError::from_checksum(field_kind.clone()))? as u8;
+    let mut candidates = CorrectionCandidates::new();
+    let permutations = OcrPermutations::<N>::with_substitutions(cleaned, max_depth, substitutions);
+    for permutation in permutations.take(budget.min(M)) {
+        let p = permutation.text.as_str();
+        let matching = check_digits
+            .iter()
+            .find(|digit| permutation.checksum == digit.replacement.to_digit(10).map(|d| d as u8));
+        let Some(digit) = matching else {
+            continue;
+        };
+        let mut value = String::new();
+        if value.push_str(p).is_err() || T::from_str(p).is_err() {
+            continue;
+        }
+        insert_ranked(
+            &mut candidates,
+            CorrectionCandidate {
+                value,
+                check_digit: digit.replacement as u8,
+                cost: permutation.cost + u32::from(digit.cost),
+            },
+        );
+    }
+
+    let Some(best) = candidates.first() else {
+        return Err(MRZParseError::from_checksum(field_kind));
+    };
+    let value = T::from_str(&best.value).map_err(|_| MRZParseError::from_checksum(field_kind))?;
+    let field = CheckedField::new(value, None, best.check_digit)
+        .with_correction(Correction::new(cleaned, &best.value));
+    let field = if best.check_digit as char == expected_checksum {
+        field
+    } else {
+        field.with_raw_check_digit(expected_checksum as u8)
+    };
+    Ok(field.with_candidates(candidates))
+}
+
+// Inserts `candidate` after the candidates that cost no more, dropping the most
+// expensive one when the list is full.
+fn insert_ranked(candidates: &mut CorrectionCandidates, candidate: CorrectionCandidate) {
+    let index = candidates
+        .iter()
+        .position(|c| c.cost > candidate.cost)
+        .unwrap_or(candidates.len());
+    if index == MAX_CORRECTION_CANDIDATES {
+        return;
+    }
+    if candidates.is_full() {
+        candidates.pop();
+    }
+    let _ = candidates.insert(index, candidate);
+}
 
-    for p in permutations {
-        if compute_checksum(p.as_bytes()) == Some(expected) {
-            if let Ok(value) = T::from_str(&p) {
-                return Ok(CheckedField::new(value, None, b'0' + expected));
+/// Picks one option of each part so that their checksum deltas add up to `target`
+/// modulo 10, at the lowest total cost.
+///
+/// Each option is a `(cost, delta)` pair, where `delta` is what the option adds to a
+/// checksum modulo 10, e.g. the candidates of the fields covered by a composite check
+/// digit. Returns the index of the option picked in each part, or `None` if no
+/// combination reaches `target` or several reach it at the lowest cost.
+///
+/// # Examples
+///
+/// ```
+/// use mrz_core::field_correction::cheapest_combination;
+/// let field = [(0, 0), (1, 4)];
+/// let optional = [(0, 0), (1, 3), (2, 7)];
+/// assert_eq!(cheapest_combination(&[&field, &optional], 4).unwrap(), [1, 0]);
+/// // `(2, 7)` alone and `(1, 4)` with `(1, 3)` both reach 7 at cost 2.
+/// assert_eq!(cheapest_combination(&[&field, &optional], 7), None);
+/// assert_eq!(cheapest_combination(&[&field, &optional], 2), None);
+/// ```
+pub fn cheapest_combination(
+    parts: &[&[(u32, u8)]],
+    target: u8,
+) -> Option<Vec<usize, MAX_COMBINED_PARTS>> {
+    // The cheapest way to reach each residue with the parts seen so far.
+    #[derive(Clone, Copy)]
+    struct Step {
+        cost: u32,
+        option: usize,
+        from: usize,
+        tied: bool,
+    }
+
+    let mut stages: Vec<[Option<Step>; 10], MAX_COMBINED_PARTS> = Vec::new();
+    let mut reached: [Option<(u32, bool)>; 10] = [None; 10];
+    reached[0] = Some((0, false));
+    for part in parts {
+        let mut stage: [Option<Step>; 10] = [None; 10];
+        for (from, entry) in reached.iter().enumerate() {
+            let Some((base, tied)) = *entry else {
+                continue;
+            };
+            for (option, &(cost, delta)) in part.iter().enumerate() {
+                let cost = base + cost;
+                match &mut stage[(from + usize::from(delta)) % 10] {
+                    Some(step) if step.cost < cost => {}
+                    Some(step) if step.cost == cost => step.tied = true,
+                    slot => {
+                        *slot = Some(Step {
+                            cost,
+                            option,
+                            from,
+                            tied,
+                        })
+                    }
+                }
             }
         }
+        reached = stage.map(|step| step.map(|step| (step.cost, step.tied)));
+        stages.push(stage).ok()?;
     }
 
-    Err(MRZParseError::from_checksum(field_kind))
+    let mut residue = usize::from(target % 10);
+    if !matches!(reached[residue], Some((_, false))) {
+        return None;
+    }
+    let mut picks: Vec<usize, MAX_COMBINED_PARTS> = Vec::new();
+    picks.resize(stages.len(), 0).ok()?;
+    for (pick, stage) in picks.iter_mut().zip(&stages).rev() {
+        let step = stage[residue]?;
+        *pick = step.option;
+        residue = step.from;
+    }
+    Some(picks)
 }
@@ -1,25 +1,58 @@
 //! MRZ (Machine Readable Zone) parsing core library.
 //!
 //! This crate provides core data structures and utilities for parsing ICAO-compliant MRZ formats,
-//! including TD1 and TD3. It is designed to be `no_std` compatible and safe for embedded and MCU use.
+//! including TD1, TD2, TD3 and MRV-A/MRV-B visas, as well as IATA boarding passes (BCBP).
+//! It is designed to be `no_std` compatible and safe for embedded and MCU use.
 #![cfg_attr(not(feature = "std"), no_std)]
 #![forbid(unsafe_code)]
 #![deny(missing_docs)]
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+use bcbp::Bcbp;
 use checked_field::CheckedField;
+pub use checked_field::{Correction, CorrectionCandidate, CorrectionOutcome, Substitution};
+pub use confidence::ScoredLine;
+pub use confusion::{Confusion, ConfusionModel, IcaoOcrB};
+pub use document_kind::DocumentKind;
 use heapless::String;
 use heapless::Vec;
+pub use line_order::LineOrder;
+pub use normalize::{Normalization, NormalizationKind, NormalizedInput};
+pub use options::{ChecksumPolicy, ParseOptions};
+pub use report::{IssueKind, MrzField, ParseIssue, ParseReport, Severity};
 
+/// IATA Bar Coded Boarding Pass (BCBP) parsing.
+pub mod bcbp;
 /// Checked field types for MRZ data.
 pub mod checked_field;
 /// MRZ checksum validation utilities.
 pub mod checksum;
+/// Lines of OCR output with per-character confidences.
+pub mod confidence;
+/// Weighted OCR confusion models used by the correction search.
+pub mod confusion;
+/// Decoding of the two-character document code into a document category.
+pub mod document_kind;
+/// Location of MRZs inside a block of OCR text.
+pub mod extract;
 /// MRZ field correction utilities for handling OCR errors and substitutions.
 pub mod field_correction;
+/// Recovery of MRZ lines captured in the wrong order.
+pub mod line_order;
+/// Canonicalization of raw OCR text into the MRZ alphabet.
+pub mod normalize;
 /// OCR (Optical Character Recognition) utilities for MRZ data.
 pub mod ocr;
+/// Options controlling parsing tolerances, OCR correction and checksum handling.
+pub mod options;
 /// MRZ format parsing utilities and functions.
 pub mod parser;
+/// Realignment of MRZ lines with dropped, duplicated or shifted characters.
+pub mod realign;
+/// Non-short-circuiting parse reports listing every problem found.
+pub mod report;
 
 /// Splits the MRZ name field into surname and given names.
 ///
@@ -54,7 +87,26 @@ fn split_name_field<const N: usize>(name_field: &str) -> (String<N>, String<N>)
     (surname, given_names)
 }
 
-/// Common field interface shared across ICAO MRZ formats (TD1, TD3), implemented for `MrzIcao<...>`.
+/// Returns whether a YYMMDD date is a calendar date. February 29 is accepted in years
+/// divisible by 4, as the century is not known.
+fn is_calendar_date(date: &[u8; ICAO_COMMON_DATE_LEN]) -> bool {
+    if !date.iter().all(u8::is_ascii_digit) {
+        return false;
+    }
+    let year = (date[0] - b'0') * 10 + (date[1] - b'0');
+    let month = (date[2] - b'0') * 10 + (date[3] - b'0');
+    let day = (date[4] - b'0') * 10 + (date[5] - b'0');
+    let days_in_month = match month {
+        1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+        4 | 6 | 9 | 11 => 30,
+        2 if year & 3 == 0 => 29,
+        2 => 28,
+        _ => return false,
+    };
+    (1..=days_in_month).contains(&day)
+}
+
+/// Common field interface shared across ICAO MRZ formats (TD1, TD2, TD3, MRV-A, MRV-B), implemented for `MrzIcao<...>`.
 pub trait MrzIcaoCommonFields {
     /// Returns the sex field value as a single ASCII byte (e.g., 'M', 'F', or '<').
     fn sex(&self) -> u8;
@@ -72,6 +124,26 @@ pub trait MrzIcaoCommonFields {
     fn is_expiry_date_valid(&self) -> bool;
     /// Returns whether the final checksum passed validation, if applicable.
     fn is_final_check_valid(&self) -> Option<bool>;
+    /// Returns the check digits that were misread and repaired by OCR correction,
+    /// as opposed to fields whose data was repaired.
+    fn corrected_check_digits(&self) -> Vec<MRZChecksumError, 4> {
+        Vec::new()
+    }
+    /// Returns how the check digit of `field` was satisfied, or `None` if the document
+    /// has no such check digit.
+    fn correction_outcome(&self, _field: MRZChecksumError) -> Option<CorrectionOutcome> {
+        None
+    }
+    /// Returns the checksum-valid corrections found for `field`, cheapest first; see
+    /// [`CheckedField::candidates`].
+    fn correction_candidates(&self, _field: MRZChecksumError) -> &[CorrectionCandidate] {
+        &[]
+    }
+    /// Returns the substitutions OCR correction applied to `field`; see
+    /// [`CheckedField::correction`].
+    fn correction(&self, _field: MRZChecksumError) -> Option<&Correction> {
+        None
+    }
     /// Returns the surname parsed from the name field.
     fn surname(&self) -> String<ICAO_TD3_NAME_MAX_LEN>;
     /// Returns the given names parsed from the name field.
@@ -84,6 +156,11 @@ pub trait MrzIcaoCommonFields {
     /// Returns the document code (e.g., "P<" for passport).
     fn document_code(&self) -> &[u8; ICAO_COMMON_DOC_CODE_LEN];
 
+    /// Returns the document category decoded from the document code.
+    fn document_kind(&self) -> DocumentKind {
+        DocumentKind::from_code(self.document_code())
+    }
+
     /// Returns the raw name field (surname and given names joined by '<<').
     fn raw_name(&self) -> &str;
 
@@ -95,24 +172,172 @@ pub trait MrzIcaoCommonFields {
 
     /// Returns optional data field 2.
     fn optional_data2(&self) -> &str;
+
+    /// Returns the order the lines were parsed in, or `None` if it is the input order.
+    fn line_order(&self) -> Option<LineOrder> {
+        None
+    }
+
+    /// Returns how the composite check digit rewrote optional data field 1 or 2
+    /// (`index` 0 or 1), or `None` if it was kept as read.
+    fn optional_data_correction(&self, _index: usize) -> Option<&Correction> {
+        None
+    }
 }
 
+/// Storage of the boarding pass held by [`ParsedMRZ::Bcbp`].
+///
+/// A [`Bcbp`] holds up to four legs with 255-byte airline data fields, far more than
+/// any MRZ document. With the `alloc` feature (enabled by `std`) it is boxed, so a
+/// `ParsedMRZ` stays as small as its MRZ variants; without it, it is stored inline.
+#[cfg(feature = "alloc")]
+pub type BcbpStorage = alloc::boxed::Box<Bcbp>;
+/// Storage of the boarding pass held by [`ParsedMRZ::Bcbp`].
+///
+/// Without the `alloc` feature the [`Bcbp`] is stored inline, which makes a
+/// `ParsedMRZ` as large as a boarding pass.
+#[cfg(not(feature = "alloc"))]
+pub type BcbpStorage = Bcbp;
+
 /// Parsed MRZ format variants.
+#[cfg_attr(not(feature = "alloc"), allow(clippy::large_enum_variant))]
 pub enum ParsedMRZ {
     /// TD3 format.
     MrzIcaoTd3(MrzIcaoTd3),
     /// TD1 format.
     MrzIcaoTd1(MrzIcaoTd1),
+    /// TD2 format.
+    MrzIcaoTd2(MrzIcaoTd2),
+    /// MRV-A format (visa).
+    MrzIcaoMrvA(MrzIcaoMrvA),
+    /// MRV-B format (visa).
+    MrzIcaoMrvB(MrzIcaoMrvB),
+    /// Legacy French national ID card.
+    MrzFraId(MrzFraId),
+    /// Single-line driving licence.
+    MrzDrivingLicence(MrzDrivingLicence),
+    /// Swiss driving licence.
+    MrzSwissDrivingLicence(MrzSwissDrivingLicence),
+    /// IATA Bar Coded Boarding Pass, see [`BcbpStorage`].
+    Bcbp(BcbpStorage),
     /// Unknown or unsupported format.
     Unknown,
 }
 
-#[cfg_attr(not(feature = "std"), derive(Debug))]
+impl ParsedMRZ {
+    /// Returns the document category of the parsed document, or `None` for [`ParsedMRZ::Unknown`].
+    pub fn document_kind(&self) -> Option<DocumentKind> {
+        match self {
+            ParsedMRZ::MrzIcaoTd3(mrz) => Some(mrz.document_kind()),
+            ParsedMRZ::MrzIcaoTd1(mrz) => Some(mrz.document_kind()),
+            ParsedMRZ::MrzIcaoTd2(mrz) => Some(mrz.document_kind()),
+            ParsedMRZ::MrzIcaoMrvA(mrz) => Some(mrz.document_kind()),
+            ParsedMRZ::MrzIcaoMrvB(mrz) => Some(mrz.document_kind()),
+            ParsedMRZ::MrzFraId(fra) => Some(fra.document_kind()),
+            ParsedMRZ::MrzDrivingLicence(dl) => Some(dl.document_kind()),
+            ParsedMRZ::MrzSwissDrivingLicence(dl) => Some(dl.document_kind()),
+            ParsedMRZ::Bcbp(bcbp) => Some(bcbp.document_kind()),
+            ParsedMRZ::Unknown => None,
+        }
+    }
+}
+
+impl ParsedMRZ {
+    /// Returns the order the lines were parsed in, or `None` if it is the input order or
+    /// the format has a single line.
+    pub fn line_order(&self) -> Option<LineOrder> {
+        match self {
+            ParsedMRZ::MrzIcaoTd3(mrz) => mrz.line_order,
+            ParsedMRZ::MrzIcaoTd1(mrz) => mrz.line_order,
+            ParsedMRZ::MrzIcaoTd2(mrz) => mrz.line_order,
+            ParsedMRZ::MrzIcaoMrvA(mrz) => mrz.line_order,
+            ParsedMRZ::MrzIcaoMrvB(mrz) => mrz.line_order,
+            ParsedMRZ::MrzFraId(fra) => fra.line_order,
+            _ => None,
+        }
+    }
+
+    // Records the order the lines were parsed in, see [`ParseOptions::reorder_lines`].
+    pub(crate) fn set_line_order(&mut self, order: LineOrder) {
+        let order = (!order.is_identity()).then_some(order);
+        match self {
+            ParsedMRZ::MrzIcaoTd3(mrz) => mrz.line_order = order,
+            ParsedMRZ::MrzIcaoTd1(mrz) => mrz.line_order = order,
+            ParsedMRZ::MrzIcaoTd2(mrz) => mrz.line_order = order,
+            ParsedMRZ::MrzIcaoMrvA(mrz) => mrz.line_order = order,
+            ParsedMRZ::MrzIcaoMrvB(mrz) => mrz.line_order = order,
+            ParsedMRZ::MrzFraId(fra) => fra.line_order = order,
+            _ => {}
+        }
+    }
+}
+
+impl ParsedMRZ {
+    /// Returns where the field checked by `kind` and its check digit sit in the input,
+    /// as [`MRZParseError::position`] reports a failed check digit. `None` if the
+    /// document has no such check digit.
+    pub fn checksum_position(&self, kind: &MRZChecksumError) -> Option<ErrorPosition> {
+        use MRZChecksumError::{BirthDate, DocumentNumber, ExpiryDate, Final};
+
+        // A field runs from `start` to its check digit, which an extended document
+        // number moves into the optional data starting at `continuation`.
+        let span = |line, start, check| Some(ErrorPosition::new(line, start, check + 1));
+        let number_check = |number: &str, check: usize, continuation: usize| match number
+            .len()
+            .checked_sub(ICAO_COMMON_DOC_NUM_MAX_LEN)
+        {
+            Some(extension) if extension > 0 => continuation + extension,
+            _ => check,
+        };
+        match (self, kind) {
+            (ParsedMRZ::MrzIcaoTd1(mrz), DocumentNumber) => {
+                span(0, 5, number_check(mrz.document_number.value(), 14, 15))
+            }
+            (ParsedMRZ::MrzIcaoTd1(_), BirthDate) => span(1, 0, 6),
+            (ParsedMRZ::MrzIcaoTd1(_), ExpiryDate) => span(1, 8, 14),
+            (ParsedMRZ::MrzIcaoTd1(_), Final) => span(1, 29, 29),
+            (ParsedMRZ::MrzIcaoTd2(mrz), DocumentNumber) => {
+                span(1, 0, number_check(mrz.document_number.value(), 9, 28))
+            }
+            (ParsedMRZ::MrzIcaoTd2(_), Final) => span(1, 35, 35),
+            (ParsedMRZ::MrzIcaoTd3(_), Final) => span(1, 43, 43),
+            (
+                ParsedMRZ::MrzIcaoTd3(_)
+                | ParsedMRZ::MrzIcaoTd2(_)
+                | ParsedMRZ::MrzIcaoMrvA(_)
+                | ParsedMRZ::MrzIcaoMrvB(_),
+                _,
+            ) => match kind {
+                DocumentNumber => span(1, 0, 9),
+                BirthDate => span(1, 13, 19),
+                ExpiryDate => span(1, 21, 27),
+                Final => None,
+            },
+            (ParsedMRZ::MrzFraId(_), DocumentNumber) => span(1, 0, 12),
+            (ParsedMRZ::MrzFraId(_), BirthDate) => span(1, 27, 33),
+            (ParsedMRZ::MrzFraId(_), Final) => span(1, 35, 35),
+            (ParsedMRZ::MrzDrivingLicence(_), DocumentNumber) => span(0, 5, 29),
+            _ => None,
+        }
+    }
+}
+
 impl core::fmt::Debug for ParsedMRZ {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         match self {
             ParsedMRZ::MrzIcaoTd3(td3) => f.debug_tuple("MrzIcaoTd3").field(td3).finish(),
             ParsedMRZ::MrzIcaoTd1(td1) => f.debug_tuple("MrzIcaoTd1").field(td1).finish(),
+            ParsedMRZ::MrzIcaoTd2(td2) => f.debug_tuple("MrzIcaoTd2").field(td2).finish(),
+            ParsedMRZ::MrzIcaoMrvA(mrva) => f.debug_tuple("MrzIcaoMrvA").field(mrva).finish(),
+            ParsedMRZ::MrzIcaoMrvB(mrvb) => f.debug_tuple("MrzIcaoMrvB").field(mrvb).finish(),
+            ParsedMRZ::MrzFraId(fra) => f.debug_tuple("MrzFraId").field(fra).finish(),
+            ParsedMRZ::MrzDrivingLicence(dl) => {
+                f.debug_tuple("MrzDrivingLicence").field(dl).finish()
+            }
+            ParsedMRZ::MrzSwissDrivingLicence(dl) => {
+                f.debug_tuple("MrzSwissDrivingLicence").field(dl).finish()
+            }
+            ParsedMRZ::Bcbp(bcbp) => f.debug_tuple("Bcbp").field(bcbp).finish(),
             ParsedMRZ::Unknown => write!(f, "Unknown"),
         }
     }
@@ -126,6 +351,9 @@ pub const ICAO_COMMON_DOC_CODE_LEN: usize = 2;
 pub const ICAO_COMMON_COUNTRY_CODE_LEN: usize = 3;
 /// Maximum length of document number field in ICAO MRZ formats.
 pub const ICAO_COMMON_DOC_NUM_MAX_LEN: usize = 9;
+/// Maximum length of an extended document number, whose characters beyond the
+/// 9-character document number field continue in the optional data field (TD1 layout).
+pub const ICAO_EXTENDED_DOC_NUM_MAX_LEN: usize = 23;
 
 /// Maximum length of name field in ICAO TD3 format.
 pub const ICAO_TD3_NAME_MAX_LEN: usize = 39;
@@ -146,8 +374,9 @@ pub struct MrzIcao<const NAME_LEN: usize, const OPT1_LEN: usize, const OPT2_LEN:
     pub nationality: [u8; ICAO_COMMON_COUNTRY_CODE_LEN],
     /// Sex character ('M', 'F', or '<').
     pub sex: u8,
-    /// Document number.
-    pub document_number: CheckedField<String<ICAO_COMMON_DOC_NUM_MAX_LEN>>,
+    /// Document number. On TD1 and TD2 documents, this includes any extended
+    /// characters carried in the optional data.
+    pub document_number: CheckedField<String<ICAO_EXTENDED_DOC_NUM_MAX_LEN>>,
     /// Date of birth (YYMMDD).
     pub birth_date: CheckedField<[u8; ICAO_COMMON_DATE_LEN]>,
     /// Expiry date (YYMMDD).
@@ -158,13 +387,18 @@ pub struct MrzIcao<const NAME_LEN: usize, const OPT1_LEN: usize, const OPT2_LEN:
     pub optional_data1: String<OPT1_LEN>,
     /// Second optional data field.
     pub optional_data2: String<OPT2_LEN>,
+    /// The order the lines were parsed in under [`ParseOptions::reorder_lines`], or
+    /// `None` if it is the input order.
+    pub line_order: Option<LineOrder>,
+    /// How the composite check digit rewrote each optional data field under
+    /// [`ParseOptions::correct_optional_data`], or `None` if it was kept as read.
+    pub optional_data_corrections: [Option<Correction>; 2],
 }
 
 /// ICAO MRZ TD3 document type (e.g., passport), with fixed field lengths.
 pub type MrzIcaoTd3 =
     MrzIcao<ICAO_TD3_NAME_MAX_LEN, ICAO_TD3_OPTIONAL1_MAX_LEN, ICAO_TD3_OPTIONAL2_MAX_LEN>;
 
-#[cfg_attr(not(feature = "std"), derive(Debug))]
 impl<const NAME_LEN: usize, const OPT1_LEN: usize, const OPT2_LEN: usize> core::fmt::Debug
     for MrzIcao<NAME_LEN, OPT1_LEN, OPT2_LEN>
 {
@@ -206,13 +440,13 @@ impl<const NAME_LEN: usize, const OPT1_LEN: usize, const OPT2_LEN: usize> MrzIca
         self.sex
     }
     fn document_number(&self) -> &str {
-        &self.document_number.value()
+        self.document_number.value()
     }
     fn birth_date(&self) -> &[u8; ICAO_COMMON_DATE_LEN] {
-        &self.birth_date.value()
+        self.birth_date.value()
     }
     fn expiry_date(&self) -> &[u8; ICAO_COMMON_DATE_LEN] {
-        &self.expiry_date.value()
+        self.expiry_date.value()
     }
     fn is_document_number_valid(&self) -> bool {
         self.document_number.is_valid()
@@ -226,6 +460,59 @@ impl<const NAME_LEN: usize, const OPT1_LEN: usize, const OPT2_LEN: usize> MrzIca
     fn is_final_check_valid(&self) -> Option<bool> {
         self.final_check.as_ref().map(|c| c.is_valid())
     }
+    fn corrected_check_digits(&self) -> Vec<MRZChecksumError, 4> {
+        let mut corrected = Vec::new();
+        let fields = [
+            (
+                self.document_number.is_check_digit_corrected(),
+                MRZChecksumError::DocumentNumber,
+            ),
+            (
+                self.birth_date.is_check_digit_corrected(),
+                MRZChecksumError::BirthDate,
+            ),
+            (
+                self.expiry_date.is_check_digit_corrected(),
+                MRZChecksumError::ExpiryDate,
+            ),
+            (
+                self.final_check
+                    .as_ref()
+                    .is_some_and(|c| c.is_check_digit_corrected()),
+                MRZChecksumError::Final,
+            ),
+        ];
+        for (is_corrected, field) in fields {
+            if is_corrected {
+                let _ = corrected.push(field);
+            }
+        }
+        corrected
+    }
+    fn correction_outcome(&self, field: MRZChecksumError) -> Option<CorrectionOutcome> {
+        match field {
+            MRZChecksumError::DocumentNumber => Some(self.document_number.correction_outcome()),
+            MRZChecksumError::BirthDate => Some(self.birth_date.correction_outcome()),
+            MRZChecksumError::ExpiryDate => Some(self.expiry_date.correction_outcome()),
+            MRZChecksumError::Final => self.final_check.as_ref().map(|c| c.correction_outcome()),
+        }
+    }
+    fn correction_candidates(&self, field: MRZChecksumError) -> &[CorrectionCandidate] {
+        match field {
+            MRZChecksumError::DocumentNumber => self.document_number.candidates(),
+            MRZChecksumError::BirthDate => self.birth_date.candidates(),
+            MRZChecksumError::ExpiryDate => self.expiry_date.candidates(),
+            MRZChecksumError::Final => &[],
+        }
+    }
+    fn correction(&self, field: MRZChecksumError) -> Option<&Correction> {
+        match field {
+            MRZChecksumError::DocumentNumber => self.document_number.correction(),
+            MRZChecksumError::BirthDate => self.birth_date.correction(),
+            MRZChecksumError::ExpiryDate => self.expiry_date.correction(),
+            MRZChecksumError::Final => self.final_check.as_ref().and_then(|c| c.correction()),
+        }
+    }
 
     fn surname(&self) -> String<ICAO_TD3_NAME_MAX_LEN> {
         split_name_field(&self.name).0
@@ -259,6 +546,13 @@ impl<const NAME_LEN: usize, const OPT1_LEN: usize, const OPT2_LEN: usize> MrzIca
     fn optional_data2(&self) -> &str {
         &self.optional_data2
     }
+    fn line_order(&self) -> Option<LineOrder> {
+        self.line_order
+    }
+
+    fn optional_data_correction(&self, index: usize) -> Option<&Correction> {
+        self.optional_data_corrections.get(index)?.as_ref()
+    }
 }
 
 /// Maximum length of name field in ICAO TD1 format.
@@ -269,35 +563,474 @@ pub const ICAO_TD1_OPTIONAL1_MAX_LEN: usize = 15;
 pub const ICAO_TD1_OPTIONAL2_MAX_LEN: usize = 11;
 
 /// ICAO MRZ TD1 document type (e.g., ID card), with fixed field lengths.
+///
+/// The composite check digit covers line 1 from the document number on, so
+/// `optional_data1` is part of it, as well as the dates and `optional_data2`.
 pub type MrzIcaoTd1 =
     MrzIcao<ICAO_TD1_NAME_MAX_LEN, ICAO_TD1_OPTIONAL1_MAX_LEN, ICAO_TD1_OPTIONAL2_MAX_LEN>;
 
+/// Maximum length of name field in ICAO TD2 format.
+pub const ICAO_TD2_NAME_MAX_LEN: usize = 31;
+/// Maximum length of optional data field 1 in ICAO TD2 format.
+pub const ICAO_TD2_OPTIONAL1_MAX_LEN: usize = 7;
+/// Maximum length of optional data field 2 in ICAO TD2 format (TD2 has a single optional data field).
+pub const ICAO_TD2_OPTIONAL2_MAX_LEN: usize = 0;
+
+/// ICAO MRZ TD2 document type (e.g., ID card, residence permit), with fixed field lengths.
+pub type MrzIcaoTd2 =
+    MrzIcao<ICAO_TD2_NAME_MAX_LEN, ICAO_TD2_OPTIONAL1_MAX_LEN, ICAO_TD2_OPTIONAL2_MAX_LEN>;
+
+/// Maximum length of name field in ICAO MRV-A format.
+pub const ICAO_MRVA_NAME_MAX_LEN: usize = 39;
+/// Maximum length of optional data field 1 in ICAO MRV-A format.
+pub const ICAO_MRVA_OPTIONAL1_MAX_LEN: usize = 16;
+/// Maximum length of optional data field 2 in ICAO MRV-A format (MRV-A has a single optional data field).
+pub const ICAO_MRVA_OPTIONAL2_MAX_LEN: usize = 0;
+
+/// ICAO MRZ MRV-A document type (full-page visa), with fixed field lengths.
+///
+/// MRV-A has no composite check digit, so `final_check` is always `None`,
+/// and the 16 characters following the expiry date check digit are exposed
+/// through `optional_data1`.
+pub type MrzIcaoMrvA =
+    MrzIcao<ICAO_MRVA_NAME_MAX_LEN, ICAO_MRVA_OPTIONAL1_MAX_LEN, ICAO_MRVA_OPTIONAL2_MAX_LEN>;
+
+/// Maximum length of name field in ICAO MRV-B format.
+pub const ICAO_MRVB_NAME_MAX_LEN: usize = 31;
+/// Maximum length of optional data field 1 in ICAO MRV-B format.
+pub const ICAO_MRVB_OPTIONAL1_MAX_LEN: usize = 8;
+/// Maximum length of optional data field 2 in ICAO MRV-B format (MRV-B has a single optional data field).
+pub const ICAO_MRVB_OPTIONAL2_MAX_LEN: usize = 0;
+
+/// ICAO MRZ MRV-B document type (visa sticker sized to leave a clear area), with fixed field lengths.
+///
+/// Like MRV-A, MRV-B has no composite check digit, so `final_check` is always `None`.
+pub type MrzIcaoMrvB =
+    MrzIcao<ICAO_MRVB_NAME_MAX_LEN, ICAO_MRVB_OPTIONAL1_MAX_LEN, ICAO_MRVB_OPTIONAL2_MAX_LEN>;
+
+/// Maximum length of the surname field of the legacy French national ID card.
+pub const FRA_ID_SURNAME_MAX_LEN: usize = 25;
+/// Maximum length of the given names field of the legacy French national ID card.
+pub const FRA_ID_GIVEN_NAMES_MAX_LEN: usize = 14;
+/// Length of the card number of the legacy French national ID card.
+pub const FRA_ID_CARD_NUMBER_LEN: usize = 12;
+/// Length of the department and office codes of the legacy French national ID card.
+pub const FRA_ID_ADMIN_CODE_LEN: usize = 3;
+
+/// Legacy (pre-2021) French national identity card ("IDFRA", 2x36).
+///
+/// This national layout predates the ICAO TD1 cards and is not TD2 compatible: the
+/// first line carries the surname and the issuing office, the second line carries the
+/// 12-character card number, the given names, the birth date and the sex, and the
+/// composite check digit covers both lines. There is no expiry date or nationality field.
+pub struct MrzFraId {
+    /// Document code (always "ID").
+    pub document_code: [u8; ICAO_COMMON_DOC_CODE_LEN],
+    /// Issuing country code (always "FRA").
+    pub issuing_state: [u8; ICAO_COMMON_COUNTRY_CODE_LEN],
+    /// Raw surname field, padded with `<`.
+    pub surname: String<FRA_ID_SURNAME_MAX_LEN>,
+    /// Department code of the issuing office.
+    pub department: [u8; FRA_ID_ADMIN_CODE_LEN],
+    /// Code of the issuing office within the department.
+    pub office: [u8; FRA_ID_ADMIN_CODE_LEN],
+    /// Card number (issue year and month, department, sequence number).
+    pub card_number: CheckedField<String<FRA_ID_CARD_NUMBER_LEN>>,
+    /// Raw given names field (names separated by `<<`).
+    pub given_names: String<FRA_ID_GIVEN_NAMES_MAX_LEN>,
+    /// Date of birth (YYMMDD).
+    pub birth_date: CheckedField<[u8; ICAO_COMMON_DATE_LEN]>,
+    /// Sex character ('M', 'F', or '<').
+    pub sex: u8,
+    /// Composite check digit over both lines.
+    pub final_check: CheckedField<()>,
+    /// The order the lines were parsed in under [`ParseOptions::reorder_lines`], or
+    /// `None` if it is the input order.
+    pub line_order: Option<LineOrder>,
+}
+
+impl MrzFraId {
+    /// Returns the surname without filler characters.
+    pub fn surname(&self) -> &str {
+        self.surname.trim_end_matches('<')
+    }
+
+    /// Returns the given names separated by spaces.
+    pub fn given_names(&self) -> String<FRA_ID_GIVEN_NAMES_MAX_LEN> {
+        let mut out = String::new();
+        for name in self.given_names.split('<').filter(|n| !n.is_empty()) {
+            if !out.is_empty() {
+                let _ = out.push(' ');
+            }
+            let _ = out.push_str(name);
+        }
+        out
+    }
+
+    /// Returns the document category decoded from the document code.
+    pub fn document_kind(&self) -> DocumentKind {
+        DocumentKind::from_code(&self.document_code)
+    }
+
+    /// Returns the card number as a string slice.
+    pub fn card_number(&self) -> &str {
+        self.card_number.value()
+    }
+
+    /// Returns the year and month of issue (YYMM) encoded at the start of the card number.
+    pub fn issue_year_month(&self) -> &[u8] {
+        &self.card_number.value().as_bytes()[..4.min(self.card_number.value().len())]
+    }
+
+    /// Returns the birth date as a byte array (YYMMDD).
+    pub fn birth_date(&self) -> &[u8; ICAO_COMMON_DATE_LEN] {
+        self.birth_date.value()
+    }
+
+    /// Returns whether the card number passed checksum validation.
+    pub fn is_card_number_valid(&self) -> bool {
+        self.card_number.is_valid()
+    }
+
+    /// Returns whether the birth date passed checksum validation.
+    pub fn is_birth_date_valid(&self) -> bool {
+        self.birth_date.is_valid()
+    }
+
+    /// Returns whether the composite check digit passed validation.
+    pub fn is_final_check_valid(&self) -> bool {
+        self.final_check.is_valid()
+    }
+}
+
+/// Length of the licence number field of the single-line driving licence MRZ.
+pub const DL_LICENCE_NUMBER_LEN: usize = 23;
+
+/// Single-line driving licence MRZ (ISO/IEC 18013-1 style, 1x30), as found on EU licences.
+///
+/// The line starts with the document code (`D1` or `D<`), followed by the issuing state,
+/// a one-character configuration code, the `<`-padded licence number and a check digit
+/// computed over the configuration code and the licence number.
+pub struct MrzDrivingLicence {
+    /// Document code ("D1" or "D<").
+    pub document_code: [u8; ICAO_COMMON_DOC_CODE_LEN],
+    /// Issuing country code (3-letter).
+    pub issuing_state: [u8; ICAO_COMMON_COUNTRY_CODE_LEN],
+    /// Configuration code of the licence.
+    pub configuration: u8,
+    /// Licence number, padded with `<`.
+    pub licence_number: CheckedField<String<DL_LICENCE_NUMBER_LEN>>,
+}
+
+impl MrzDrivingLicence {
+    /// Returns the document category decoded from the document code.
+    pub fn document_kind(&self) -> DocumentKind {
+        DocumentKind::from_code(&self.document_code)
+    }
+
+    /// Returns the licence number without filler characters.
+    pub fn licence_number(&self) -> &str {
+        self.licence_number.value().trim_end_matches('<')
+    }
+
+    /// Returns whether the check digit passed validation.
+    pub fn is_licence_number_valid(&self) -> bool {
+        self.licence_number.is_valid()
+    }
+}
+
+impl core::fmt::Debug for MrzDrivingLicence {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("MrzDrivingLicence")
+            .field("document_code", &self.document_code)
+            .field("issuing_state", &self.issuing_state)
+            .field("configuration", &self.configuration)
+            .field("licence_number", &self.licence_number())
+            .field(
+                "licence_number_check_valid",
+                &self.licence_number.is_valid(),
+            )
+            .finish()
+    }
+}
+
+/// Document code starting the second line of the Swiss driving licence MRZ.
+pub const CH_DL_DOC_CODE: &[u8] = b"FA";
+/// Length of the first line of the Swiss driving licence MRZ.
+pub const CH_DL_LINE1_LEN: usize = 9;
+/// Length of the second and third lines of the Swiss driving licence MRZ.
+pub const CH_DL_LINE_LEN: usize = 30;
+/// Length of the licence number (issuing authority code and serial number).
+pub const CH_DL_LICENCE_NUMBER_LEN: usize = 6;
+/// Length of the personal identification number (PIN) field.
+pub const CH_DL_PIN_LEN: usize = 9;
+/// Length of the version number field.
+pub const CH_DL_VERSION_LEN: usize = 3;
+
+/// Swiss credit-card driving licence MRZ (3 lines: 9, 30 and 30 characters).
+///
+/// The first line holds the licence number and a language code, the second the document
+/// code (`FA`), issuing state, PIN, version number and date of birth, and the third the name.
+/// The layout carries no check digits; [`MrzSwissDrivingLicence::is_birth_date_valid`]
+/// checks that the birth date is a calendar date instead, down to the days of the month.
+pub struct MrzSwissDrivingLicence {
+    /// Licence number (3-letter issuing authority code and 3-digit serial number).
+    pub licence_number: String<CH_DL_LICENCE_NUMBER_LEN>,
+    /// Language code of the licence (`D`, `F`, `I` or `R`).
+    pub language_code: u8,
+    /// Document code ("FA").
+    pub document_code: [u8; ICAO_COMMON_DOC_CODE_LEN],
+    /// Issuing country code (3-letter).
+    pub issuing_state: [u8; ICAO_COMMON_COUNTRY_CODE_LEN],
+    /// Personal identification number of the holder, padded with `<`.
+    pub pin_code: String<CH_DL_PIN_LEN>,
+    /// Version number of the licence.
+    pub version: [u8; CH_DL_VERSION_LEN],
+    /// Date of birth (YYMMDD).
+    pub birth_date: [u8; ICAO_COMMON_DATE_LEN],
+    /// Raw name field (surname and given names joined by '<<').
+    pub name: String<CH_DL_LINE_LEN>,
+}
+
+impl MrzSwissDrivingLicence {
+    /// Returns the document category decoded from the document code.
+    pub fn document_kind(&self) -> DocumentKind {
+        DocumentKind::from_code(&self.document_code)
+    }
+
+    /// Returns the licence number as a string slice.
+    pub fn licence_number(&self) -> &str {
+        self.licence_number.trim_end_matches('<')
+    }
+
+    /// Returns the PIN without filler characters.
+    pub fn pin_code(&self) -> &str {
+        self.pin_code.trim_end_matches('<')
+    }
+
+    /// Returns the version number as a string slice.
+    pub fn version(&self) -> &str {
+        core::str::from_utf8(&self.version)
+            .unwrap_or("")
+            .trim_end_matches('<')
+    }
+
+    /// Returns the birth date as a byte array (YYMMDD).
+    pub fn birth_date(&self) -> &[u8; ICAO_COMMON_DATE_LEN] {
+        &self.birth_date
+    }
+
+    /// Returns whether the birth date is a calendar date, the only check the layout allows.
+    pub fn is_birth_date_valid(&self) -> bool {
+        is_calendar_date(&self.birth_date)
+    }
+
+    /// Returns the surname parsed from the name field.
+    pub fn surname(&self) -> String<CH_DL_LINE_LEN> {
+        split_name_field::<CH_DL_LINE_LEN>(&self.name).0
+    }
+
+    /// Returns the given names parsed from the name field.
+    pub fn given_names(&self) -> String<CH_DL_LINE_LEN> {
+        let (_, given) = split_name_field::<CH_DL_LINE_LEN>(&self.name);
+        let mut out = String::new();
+        let _ = out.push_str(given.trim_end());
+        out
+    }
+}
+
+impl core::fmt::Debug for MrzSwissDrivingLicence {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let birth = core::str::from_utf8(&self.birth_date).unwrap_or("??");
+
+        f.debug_struct("MrzSwissDrivingLicence")
+            .field("licence_number", &self.licence_number())
+            .field("language_code", &self.language_code)
+            .field("document_code", &self.document_code)
+            .field("issuing_state", &self.issuing_state)
+            .field("pin_code", &self.pin_code())
+            .field("version", &self.version())
+            .field("birth_date", &birth)
+            .field("birth_date_valid", &self.is_birth_date_valid())
+            .field("surname", &self.surname())
+            .field("given_names", &self.given_names())
+            .finish()
+    }
+}
+
+impl core::fmt::Debug for MrzFraId {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let birth = core::str::from_utf8(self.birth_date.value()).unwrap_or("??");
+
+        f.debug_struct("MrzFraId")
+            .field("document_code", &self.document_code)
+            .field("issuing_state", &self.issuing_state)
+            .field("surname", &self.surname())
+            .field("given_names", &self.given_names())
+            .field("department", &self.department)
+            .field("office", &self.office)
+            .field("card_number", &self.card_number)
+            .field("birth_date", &birth)
+            .field("birth_date_check_valid", &self.birth_date.is_valid())
+            .field("sex", &self.sex)
+            .field("final_check_valid", &self.final_check.is_valid())
+            .finish()
+    }
+}
+
 /// MRZ document format types.
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum MRZFormat {
     /// TD3 format (passport).
     MrzIcaoTd3,
     /// TD1 format (ID card).
     MrzIcaoTd1,
+    /// TD2 format (ID card, residence permit).
+    MrzIcaoTd2,
+    /// MRV-A format (visa, 2x44).
+    MrzIcaoMrvA,
+    /// MRV-B format (visa, 2x36).
+    MrzIcaoMrvB,
+    /// Legacy French national ID card (2x36, "IDFRA").
+    MrzFraId,
+    /// Single-line driving licence (1x30, ISO/IEC 18013-1 style).
+    MrzDrivingLicence,
+    /// Swiss driving licence (3 lines of 9, 30 and 30 characters).
+    MrzSwissDrivingLicence,
     /// BCBP format (boarding pass).
     BCBP,
     /// Unknown or unsupported format.
     Unknown,
 }
 
+/// Location of a parse error in the input.
+///
+/// Lines and columns are zero-based; `start..end` is the half-open column span of
+/// the offending field or character.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ErrorPosition {
+    /// Index of the line.
+    pub line: usize,
+    /// First column of the span.
+    pub start: usize,
+    /// Column after the last column of the span.
+    pub end: usize,
+}
+
+impl ErrorPosition {
+    /// Creates a position spanning columns `start..end` of `line`.
+    pub const fn new(line: usize, start: usize, end: usize) -> Self {
+        ErrorPosition { line, start, end }
+    }
+
+    /// Creates a position pointing at a single column of `line`.
+    pub const fn at(line: usize, column: usize) -> Self {
+        ErrorPosition::new(line, column, column + 1)
+    }
+}
+
+impl core::fmt::Display for ErrorPosition {
+    // Displayed for humans, with one-based line and column numbers.
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        if self.end <= self.start + 1 {
+            write!(f, "line {}, column {}", self.line + 1, self.start + 1)
+        } else {
+            write!(
+                f,
+                "line {}, columns {}-{}",
+                self.line + 1,
+                self.start + 1,
+                self.end
+            )
+        }
+    }
+}
+
+/// Class of characters allowed at an MRZ position.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CharClass {
+    /// `0`-`9`.
+    Digit,
+    /// `A`-`Z`.
+    Letter,
+    /// The `<` filler.
+    Filler,
+    /// Any MRZ character: `A`-`Z`, `0`-`9` or the `<` filler.
+    Alphanumeric,
+    /// A sex marker: `M`, `F`, `X` or the `<` filler.
+    Sex,
+    /// Anything outside the MRZ character set.
+    Other,
+}
+
+impl CharClass {
+    /// Returns the narrowest class of `c`: [`CharClass::Digit`], [`CharClass::Letter`],
+    /// [`CharClass::Filler`] or [`CharClass::Other`].
+    pub fn of(c: u8) -> Self {
+        match c {
+            b'0'..=b'9' => CharClass::Digit,
+            b'A'..=b'Z' => CharClass::Letter,
+            b'<' => CharClass::Filler,
+            _ => CharClass::Other,
+        }
+    }
+
+    /// Returns `true` if `c` belongs to this class.
+    pub fn contains(&self, c: u8) -> bool {
+        match self {
+            CharClass::Alphanumeric => CharClass::of(c) != CharClass::Other,
+            CharClass::Sex => matches!(c, b'M' | b'F' | b'X' | b'<'),
+            class => CharClass::of(c) == *class,
+        }
+    }
+
+    /// Returns a short human-readable description of the class.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CharClass::Digit => "digit",
+            CharClass::Letter => "letter",
+            CharClass::Filler => "filler '<'",
+            CharClass::Alphanumeric => "MRZ character",
+            CharClass::Sex => "sex marker",
+            CharClass::Other => "non-MRZ character",
+        }
+    }
+}
+
+impl core::fmt::Display for CharClass {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
 /// MRZ parsing error types.
+///
+/// Where the parser can tell, errors carry the [`ErrorPosition`] of the offending
+/// line and columns; see [`MRZParseError::position`].
 #[derive(Debug, PartialEq, Eq)]
 pub enum MRZParseError {
-    /// Input length is invalid for any known MRZ format.
-    InvalidLength,
-    /// Checksum validation failed for a specific field.
-    InvalidChecksumField(MRZChecksumError),
+    /// Input length is invalid for any known MRZ format. The position, if any,
+    /// points at the missing or surplus columns of the first line with a wrong length.
+    InvalidLength(Option<ErrorPosition>),
+    /// Checksum validation failed for a specific field. The position, if any,
+    /// spans the field and its check digit.
+    InvalidChecksumField(MRZChecksumError, Option<ErrorPosition>),
+    /// A character of a checked field is not allowed at its position.
+    InvalidCharacter {
+        /// Position of the character.
+        position: ErrorPosition,
+        /// The offending character.
+        found: u8,
+        /// Class of characters allowed at the position.
+        expected: CharClass,
+    },
     /// MRZ format could not be determined.
     UnknownFormat,
     /// MRZ format is recognized but not supported.
     UnsupportedFormat,
-    /// UTF-8 decoding error occurred.
-    Utf8Error,
+    /// UTF-8 decoding error occurred. The position, if any, points at the first
+    /// invalid byte.
+    Utf8Error(Option<ErrorPosition>),
 }
 
 impl MRZParseError {
@@ -312,12 +1045,77 @@ impl MRZParseError {
     ///
     /// # Returns
     ///
-    /// An `MRZParseError::InvalidChecksumField` variant containing the provided error.
+    /// An `MRZParseError::InvalidChecksumField` variant containing the provided error
+    /// and no position.
     pub fn from_checksum(e: MRZChecksumError) -> Self {
-        MRZParseError::InvalidChecksumField(e)
+        MRZParseError::InvalidChecksumField(e, None)
+    }
+
+    /// Returns the position of the error in the input, if known.
+    pub fn position(&self) -> Option<ErrorPosition> {
+        match self {
+            MRZParseError::InvalidLength(position)
+            | MRZParseError::InvalidChecksumField(_, position)
+            | MRZParseError::Utf8Error(position) => *position,
+            MRZParseError::InvalidCharacter { position, .. } => Some(*position),
+            MRZParseError::UnknownFormat | MRZParseError::UnsupportedFormat => None,
+        }
+    }
+
+    /// Returns the failed check digit, for [`MRZParseError::InvalidChecksumField`].
+    pub fn checksum_error(&self) -> Option<&MRZChecksumError> {
+        match self {
+            MRZParseError::InvalidChecksumField(kind, _) => Some(kind),
+            _ => None,
+        }
+    }
+
+    /// Attaches `position` to the error unless it already carries one.
+    pub fn at(self, position: ErrorPosition) -> Self {
+        match self {
+            MRZParseError::InvalidLength(None) => MRZParseError::InvalidLength(Some(position)),
+            MRZParseError::InvalidChecksumField(kind, None) => {
+                MRZParseError::InvalidChecksumField(kind, Some(position))
+            }
+            MRZParseError::Utf8Error(None) => MRZParseError::Utf8Error(Some(position)),
+            e => e,
+        }
     }
 }
 
+impl core::fmt::Display for MRZParseError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            MRZParseError::InvalidLength(_) => f.write_str("invalid MRZ length")?,
+            MRZParseError::InvalidChecksumField(kind, _) => {
+                write!(f, "check digit mismatch in {}", kind)?
+            }
+            MRZParseError::InvalidCharacter {
+                found, expected, ..
+            } => {
+                if found.is_ascii_graphic() {
+                    write!(
+                        f,
+                        "invalid character '{}', expected {}",
+                        *found as char, expected
+                    )?
+                } else {
+                    write!(f, "invalid byte 0x{:02X}, expected {}", found, expected)?
+                }
+            }
+            MRZParseError::UnknownFormat => f.write_str("unknown MRZ format")?,
+            MRZParseError::UnsupportedFormat => f.write_str("unsupported MRZ format")?,
+            MRZParseError::Utf8Error(_) => f.write_str("invalid UTF-8")?,
+        }
+        match self.position() {
+            Some(position) => write!(f, " at {}", position),
+            None => Ok(()),
+        }
+    }
+}
+
+impl core::error::Error for MRZParseError {}
+
 /// MRZ checksum validation error types.
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub enum MRZChecksumError {
@@ -331,7 +1129,6 @@ pub enum MRZChecksumError {
     Final,
 }
 
-#[cfg(feature = "std")]
 impl core::fmt::Display for MRZChecksumError {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         match self {
@@ -343,17 +1140,7 @@ impl core::fmt::Display for MRZChecksumError {
     }
 }
 
-#[cfg(not(feature = "std"))]
-impl core::fmt::Display for MRZChecksumError {
-    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-        match self {
-            MRZChecksumError::DocumentNumber => f.write_str("DocumentNumber"),
-            MRZChecksumError::BirthDate => f.write_str("BirthDate"),
-            MRZChecksumError::ExpiryDate => f.write_str("ExpiryDate"),
-            MRZChecksumError::Final => f.write_str("Final"),
-        }
-    }
-}
+impl core::error::Error for MRZChecksumError {}
 
 /// Detects the MRZ format of the given input string.
 ///
@@ -389,3 +1176,34 @@ pub fn parse_any(input: &str) -> Result<ParsedMRZ, MRZParseError> {
     }
     parser::parse_any(&lines)
 }
+
+/// Parses the given MRZ input string using the given options.
+///
+/// With [`ParseOptions::normalize_input`], the whole input is first canonicalized by
+/// [`normalize::normalize`], which also drops blank lines and `\r` line endings.
+///
+/// # Arguments
+///
+/// * `input` - A string slice representing the raw MRZ input.
+/// * `options` - Parsing tolerances.
+///
+/// # Returns
+///
+/// A `Result` containing a `ParsedMRZ` enum variant on success,
+/// or an `MRZParseError` on failure.
+pub fn parse_any_with(input: &str, options: &ParseOptions) -> Result<ParsedMRZ, MRZParseError> {
+    if options.normalize_input {
+        let normalized = normalize::normalize(input)?;
+        let options = ParseOptions {
+            normalize_input: false,
+            ..*options
+        };
+        let lines = normalized.line_bytes();
+        return parser::parse_any_with(&lines, &options);
+    }
+    let mut lines: Vec<&[u8], 3> = Vec::new();
+    for line in input.lines() {
+        let _ = lines.push(line.as_bytes());
+    }
+    parser::parse_any_with(&lines, options)
+}
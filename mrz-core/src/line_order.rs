@@ -0,0 +1,101 @@
+use crate::extract::check_digits;
+use crate::parser::{detect_format, parse_any_with};
+use crate::{ChecksumPolicy, MRZFormat, ParseOptions};
+use heapless::Vec;
+
+const PERMUTATIONS_2: [[u8; 3]; 2] = [[0, 1, 2], [1, 0, 2]];
+const PERMUTATIONS_3: [[u8; 3]; 6] = [
+    [0, 1, 2],
+    [0, 2, 1],
+    [1, 0, 2],
+    [1, 2, 0],
+    [2, 0, 1],
+    [2, 1, 0],
+];
+
+/// The order in which input lines were arranged before parsing.
+///
+/// `as_slice()[i]` is the index in the input of the line parsed as line `i`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LineOrder {
+    indices: [u8; 3],
+    len: u8,
+}
+
+impl LineOrder {
+    /// The input order of `len` lines.
+    ///
+    /// Only two or three lines can be reordered; other counts keep an empty order.
+    pub const fn identity(len: usize) -> Self {
+        LineOrder {
+            indices: [0, 1, 2],
+            len: if len <= 3 { len as u8 } else { 0 },
+        }
+    }
+
+    /// Input line indices in parsing order.
+    pub fn as_slice(&self) -> &[u8] {
+        &self.indices[..usize::from(self.len)]
+    }
+
+    /// Returns `true` if the lines are parsed in input order.
+    pub fn is_identity(&self) -> bool {
+        self.as_slice()
+            .iter()
+            .enumerate()
+            .all(|(i, &index)| i == usize::from(index))
+    }
+
+    /// Arranges `lines` in this order.
+    pub fn apply<'a>(&self, lines: &[&'a [u8]]) -> Vec<&'a [u8], 3> {
+        self.as_slice()
+            .iter()
+            .map(|&index| lines[usize::from(index)])
+            .collect()
+    }
+}
+
+/// Finds the order of two or three MRZ lines whose structure and check digits validate,
+/// such as a TD3 captured with line 2 first or a TD1 with its lines shuffled.
+///
+/// Every permutation is parsed without OCR correction; orders whose format cannot be
+/// detected or parsed are discarded, and the one with the most agreeing check digits
+/// wins. The input order wins ties, so well-formed input is never reordered: when it
+/// parses with every check digit agreeing, no other order is tried. Other line counts
+/// are returned in input order.
+pub fn detect_line_order(lines: &[&[u8]]) -> LineOrder {
+    let identity = LineOrder::identity(lines.len());
+    let permutations: &[[u8; 3]] = match lines.len() {
+        2 => &PERMUTATIONS_2,
+        3 => &PERMUTATIONS_3,
+        _ => return identity,
+    };
+    let options = ParseOptions {
+        ocr_correction: false,
+        checksum_policy: ChecksumPolicy::Record,
+        ..ParseOptions::default()
+    };
+
+    let mut best: Option<(u8, LineOrder)> = None;
+    for &indices in permutations {
+        let order = LineOrder {
+            indices,
+            len: lines.len() as u8,
+        };
+        let arranged = order.apply(lines);
+        if matches!(detect_format(&arranged), MRZFormat::Unknown) {
+            continue;
+        }
+        let Ok(document) = parse_any_with(&arranged, &options) else {
+            continue;
+        };
+        let (passed, total) = check_digits(&document);
+        if order.is_identity() && passed == total {
+            return order;
+        }
+        if best.is_none_or(|(best_passed, _)| passed > best_passed) {
+            best = Some((passed, order));
+        }
+    }
+    best.map_or(identity, |(_, order)| order)
+}
@@ -0,0 +1,210 @@
+use crate::{ErrorPosition, MRZParseError};
+use heapless::{String, Vec};
+
+/// Maximum number of non-blank lines accepted by [`normalize`].
+pub const MAX_NORMALIZED_LINES: usize = 3;
+/// Maximum length in bytes of a normalized line; boarding passes are the longest input.
+pub const MAX_NORMALIZED_LINE_LEN: usize = 512;
+/// Maximum number of changes recorded in a [`NormalizedInput`]; further changes are
+/// still applied but not recorded, see [`NormalizedInput::truncated`].
+pub const MAX_NORMALIZATION_CHANGES: usize = 128;
+
+/// Why a character was changed by [`normalize`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NormalizationKind {
+    /// A lowercase letter was uppercased.
+    Uppercased,
+    /// A space or a filler-like glyph (`«`, `‹`, fullwidth `＜`) was replaced by `<`,
+    /// including trailing spaces that pad a line to the length of the longest line.
+    Filler,
+    /// A Cyrillic or Greek letter was replaced by the Latin letter it looks like.
+    Lookalike,
+    /// Leading or trailing whitespace, including a `\r` line ending, was removed.
+    Trimmed,
+}
+
+/// A single change made by [`normalize`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Normalization {
+    /// Index of the line in [`NormalizedInput::lines`].
+    pub line: usize,
+    /// Byte offset of the character in the original line, before trimming, like the
+    /// columns of [`crate::ErrorPosition`].
+    pub column: usize,
+    /// The original character.
+    pub original: char,
+    /// The replacement, or `None` if the character was removed.
+    pub replacement: Option<u8>,
+    /// Why the character was changed.
+    pub kind: NormalizationKind,
+}
+
+/// Input canonicalized into the MRZ alphabet, with every change made to it.
+#[derive(Debug, Clone, Default)]
+pub struct NormalizedInput {
+    /// The normalized lines; blank lines are dropped.
+    pub lines: Vec<String<MAX_NORMALIZED_LINE_LEN>, MAX_NORMALIZED_LINES>,
+    /// Changes in input order, up to [`MAX_NORMALIZATION_CHANGES`].
+    pub changes: Vec<Normalization, MAX_NORMALIZATION_CHANGES>,
+    /// `true` if more than [`MAX_NORMALIZATION_CHANGES`] changes were made, so that
+    /// `changes` only holds the first ones.
+    pub truncated: bool,
+}
+
+impl NormalizedInput {
+    /// Returns the normalized lines as byte slices, as expected by [`crate::parser::parse_any`].
+    pub fn line_bytes(&self) -> Vec<&[u8], MAX_NORMALIZED_LINES> {
+        self.lines.iter().map(|line| line.as_bytes()).collect()
+    }
+
+    /// Returns `true` if the input was already canonical.
+    pub fn is_unchanged(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    fn record(&mut self, change: Normalization) {
+        if self.changes.push(change).is_err() {
+            self.truncated = true;
+        }
+    }
+}
+
+/// Canonicalizes raw OCR text into the MRZ alphabet.
+///
+/// The input is split on `\n` and blank lines are dropped. Each remaining line is
+/// trimmed, lowercase letters are uppercased, spaces and filler-like glyphs become
+/// `<`, and Cyrillic or Greek lookalikes become their Latin counterparts. Other
+/// characters are kept as they are, so the parser can report them.
+///
+/// Trailing spaces often stand in for the fillers that end a line, so those that
+/// follow a line shorter than the longest line become `<` up to the longest line's
+/// length; only the rest are trimmed.
+///
+/// A single line starting with `M1`-`M4` is taken as a boarding pass, where spaces
+/// and lowercase letters are data: it is only trimmed.
+///
+/// Fails with [`MRZParseError::InvalidLength`] if there are more than
+/// [`MAX_NORMALIZED_LINES`] lines or a line exceeds [`MAX_NORMALIZED_LINE_LEN`].
+pub fn normalize(input: &str) -> Result<NormalizedInput, MRZParseError> {
+    normalize_lines(input.split('\n'))
+}
+
+/// Canonicalizes already split lines; see [`normalize`].
+pub fn normalize_lines<'a, I>(lines: I) -> Result<NormalizedInput, MRZParseError>
+where
+    I: IntoIterator<Item = &'a str>,
+{
+    let mut raw_lines: Vec<&str, MAX_NORMALIZED_LINES> = Vec::new();
+    for line in lines.into_iter().filter(|line| !line.trim().is_empty()) {
+        let index = raw_lines.len();
+        raw_lines.push(line).map_err(|_| {
+            MRZParseError::InvalidLength(Some(ErrorPosition::new(index, 0, line.len())))
+        })?;
+    }
+    let boarding_pass = raw_lines.len() == 1 && is_boarding_pass(raw_lines[0].trim());
+    let width = raw_lines
+        .iter()
+        .map(|line| line.trim().chars().count())
+        .max()
+        .unwrap_or(0);
+
+    let mut normalized = NormalizedInput::default();
+    for (index, raw) in raw_lines.iter().enumerate() {
+        let line = normalize_line(raw, index, boarding_pass, width, &mut normalized)?;
+        let _ = normalized.lines.push(line);
+    }
+    Ok(normalized)
+}
+
+// Normalizes a line, restoring trailing spaces as fillers up to `width` characters.
+fn normalize_line(
+    raw: &str,
+    index: usize,
+    boarding_pass: bool,
+    width: usize,
+    normalized: &mut NormalizedInput,
+) -> Result<String<MAX_NORMALIZED_LINE_LEN>, MRZParseError> {
+    let trimmed = raw.trim();
+    let leading = raw.len() - raw.trim_start().len();
+    let mut end = leading + trimmed.len();
+    if !boarding_pass {
+        let missing = width.saturating_sub(trimmed.chars().count());
+        end += raw[end..]
+            .bytes()
+            .take_while(|&b| b == b' ')
+            .take(missing)
+            .count();
+    }
+
+    let mut line = String::new();
+    for (column, c) in raw.char_indices() {
+        if column < leading || column >= end {
+            normalized.record(Normalization {
+                line: index,
+                column,
+                original: c,
+                replacement: None,
+                kind: NormalizationKind::Trimmed,
+            });
+            continue;
+        }
+        let out = match canonical(c) {
+            Some((replacement, kind)) if !boarding_pass => {
+                normalized.record(Normalization {
+                    line: index,
+                    column,
+                    original: c,
+                    replacement: Some(replacement),
+                    kind,
+                });
+                replacement as char
+            }
+            _ => c,
+        };
+        line.push(out).map_err(|_| {
+            MRZParseError::InvalidLength(Some(ErrorPosition::new(index, 0, raw.len())))
+        })?;
+    }
+    Ok(line)
+}
+
+fn is_boarding_pass(line: &str) -> bool {
+    let bytes = line.as_bytes();
+    bytes.first() == Some(&b'M') && matches!(bytes.get(1), Some(b'1'..=b'4'))
+}
+
+// Maps a character to its MRZ form, or `None` if it needs no change.
+pub(crate) fn canonical(c: char) -> Option<(u8, NormalizationKind)> {
+    if c.is_ascii_lowercase() {
+        return Some((c.to_ascii_uppercase() as u8, NormalizationKind::Uppercased));
+    }
+    if c.is_whitespace() || matches!(c, '«' | '‹' | '＜' | '﹤') {
+        return Some((b'<', NormalizationKind::Filler));
+    }
+    lookalike(c).map(|latin| (latin, NormalizationKind::Lookalike))
+}
+
+// Cyrillic and Greek letters that OCR engines commonly return for Latin MRZ letters.
+fn lookalike(c: char) -> Option<u8> {
+    let latin = match c {
+        'А' | 'а' | 'Α' | 'α' => b'A',
+        'В' | 'в' | 'Β' | 'β' => b'B',
+        'С' | 'с' => b'C',
+        'Е' | 'е' | 'Ε' | 'ε' => b'E',
+        'Н' | 'н' | 'Η' => b'H',
+        'І' | 'і' | 'Ι' | 'ι' => b'I',
+        'Ј' | 'ј' => b'J',
+        'К' | 'к' | 'Κ' | 'κ' => b'K',
+        'М' | 'м' | 'Μ' => b'M',
+        'Ν' => b'N',
+        'О' | 'о' | 'Ο' | 'ο' => b'O',
+        'Р' | 'р' | 'Ρ' | 'ρ' => b'P',
+        'Ѕ' | 'ѕ' => b'S',
+        'Т' | 'т' | 'Τ' | 'τ' => b'T',
+        'Х' | 'х' | 'Χ' | 'χ' => b'X',
+        'У' | 'у' | 'Υ' | 'υ' => b'Y',
+        'Ζ' => b'Z',
+        _ => return None,
+    };
+    Some(latin)
+}
@@ -7,8 +7,11 @@
 //! The primary use case is recovering plausible original strings from OCR output by exploring
 //! a defined set of character-level substitutions.
 
-use heapless::Deque;
-use heapless::FnvIndexSet;
+use crate::checksum::weighted_value;
+use crate::confusion::{
+    confusions_for, BasicPairs, Confusion, ConfusionModel, IcaoOcrB, MAX_CONFUSIONS,
+};
+use crate::CharClass;
 use heapless::String;
 use heapless::Vec;
 
@@ -55,11 +58,35 @@ pub fn ocr_substitutions(c: char) -> Vec<char, 4> {
     substitutes
 }
 
+/// Returns the plausible OCR substitutions of `c` that are legal in a field of `class`,
+/// cheapest first under the [`IcaoOcrB`] model.
+///
+/// Characters already legal in the field are never substituted, so a date keeps its
+/// digits and a country code its letters; only characters outside the class move into
+/// it. Letter fields also accept the `<` filler. See [`confusions_for`].
+///
+/// # Examples
+///
+/// ```
+/// use mrz_core::ocr::ocr_substitutions_for;
+/// use mrz_core::CharClass;
+/// assert_eq!(ocr_substitutions_for('O', CharClass::Digit), ['0']);
+/// assert!(ocr_substitutions_for('0', CharClass::Digit).is_empty());
+/// assert_eq!(ocr_substitutions_for('0', CharClass::Letter)[0], 'O');
+/// ```
+pub fn ocr_substitutions_for(c: char, class: CharClass) -> Vec<char, MAX_CONFUSIONS> {
+    confusions_for(&IcaoOcrB, c, class)
+        .iter()
+        .map(|confusion| confusion.replacement)
+        .collect()
+}
+
 /// Generates permutations of a string by applying common OCR substitutions.
 ///
-/// This function uses a breadth-first search approach to generate all strings up to a certain
-/// substitution depth. Each substitution replaces a single character with one of its plausible
-/// OCR alternatives.
+/// This function collects the first `M` permutations of [`OcrPermutations`], cheapest
+/// first, up to a certain substitution depth. Each substitution replaces a single
+/// character with one of its plausible OCR alternatives, as listed by
+/// [`ocr_substitutions`]; see [`ocr_permutations_with`] for weighted models.
 ///
 /// # Parameters
 ///
@@ -69,7 +96,7 @@ pub fn ocr_substitutions(c: char) -> Vec<char, 4> {
 /// # Type Parameters
 ///
 /// - `N`: The maximum length of the input string.
-/// - `M`: The maximum number of permutations generated.
+/// - `M`: The maximum number of permutations collected.
 ///
 /// # Returns
 ///
@@ -88,61 +115,332 @@ pub fn ocr_substitutions(c: char) -> Vec<char, 4> {
 /// ```
 ///
 /// Note: To guarantee inclusion of deeply substituted variants (e.g., "0000" from "OOOO"),
-/// ensure that `M` is large enough to contain all permutations up to `max_depth` substitutions.
-/// The number of generated permutations can grow exponentially with both `max_depth` and the number of
-/// substitutable characters in the input.
+/// ensure that `M` is large enough to contain all permutations up to `max_depth` substitutions,
+/// or iterate [`OcrPermutations`] directly. The number of permutations can grow exponentially
+/// with both `max_depth` and the number of substitutable characters in the input.
 pub fn ocr_permutations<const N: usize, const M: usize>(
     input: &str,
     max_depth: usize,
 ) -> Vec<String<N>, M> {
-    assert!(N > 0, "N must be greater than 0");
+    ocr_permutations_with::<N, M>(input, &BasicPairs, CharClass::Alphanumeric, max_depth)
+}
+
+/// Like [`ocr_permutations`], but only applies the substitutions of the [`IcaoOcrB`]
+/// model legal in a field of `class`, see [`ocr_substitutions_for`].
+///
+/// # Examples
+///
+/// ```
+/// use mrz_core::ocr::ocr_permutations_for;
+/// use mrz_core::CharClass;
+/// let perms = ocr_permutations_for::<6, 16>("74O8I2", CharClass::Digit, 2);
+/// assert!(perms.iter().any(|s| s == "740812"));
+/// assert!(perms.iter().all(|s| !s.contains('B')));
+/// ```
+pub fn ocr_permutations_for<const N: usize, const M: usize>(
+    input: &str,
+    class: CharClass,
+    max_depth: usize,
+) -> Vec<String<N>, M> {
+    ocr_permutations_with::<N, M>(input, &IcaoOcrB, class, max_depth)
+}
+
+/// Collects the first `M` permutations of `input` with the confusions of `model` legal
+/// in a field of `class`, in order of increasing total cost; see [`OcrPermutations`].
+///
+/// # Examples
+///
+/// ```
+/// use mrz_core::confusion::IcaoOcrB;
+/// use mrz_core::ocr::ocr_permutations_with;
+/// use mrz_core::CharClass;
+/// // `O` -> `0` costs 1, `Z` -> `2` costs 2.
+/// let perms = ocr_permutations_with::<3, 8>("Z1O", &IcaoOcrB, CharClass::Digit, 2);
+/// assert_eq!(perms, ["Z1O", "Z10", "21O", "210"]);
+/// ```
+pub fn ocr_permutations_with<const N: usize, const M: usize>(
+    input: &str,
+    model: &dyn ConfusionModel,
+    class: CharClass,
+    max_depth: usize,
+) -> Vec<String<N>, M> {
     assert!(M > 0, "M must be greater than 0");
-    assert!(input.len() <= N, "Input length exceeds buffer limit");
-    let mut results = Vec::<String<N>, M>::new();
-    let mut queue = Deque::<(String<N>, usize), M>::new();
-    let mut seen = FnvIndexSet::<String<N>, M>::new();
-
-    // Initialize BFS queue with the original string and depth 0.
-    // Use a set to track seen permutations and avoid duplicates.
-    let input_string: String<N> = String::from(input);
-    queue.push_back((input_string.clone(), 0)).ok();
-    seen.insert(input_string.clone()).ok();
-
-    // BFS ensures minimal substitutions are considered first.
-    // Note: High `max_depth` values may exponentially increase permutation count.
-    // Recommend limiting `max_depth` to 4–6 for 6–9 character inputs.
-    while let Some((current, depth)) = queue.pop_front() {
-        results.push(current.clone()).ok();
-
-        if depth >= max_depth {
-            continue;
-        }
-
-        // Attempt substitutions at each character position.
-        for i in 0..current.len() {
-            match current.chars().nth(i) {
-                Some(ch) => {
-                    let subs = ocr_substitutions(ch);
-                    if subs.is_empty() {
-                        continue;
-                    }
-                    for subst in subs.iter().copied() {
-                        let mut chars: Vec<char, N> = current.chars().collect();
-                        if let Some(c) = chars.get_mut(i) {
-                            *c = subst;
-                        }
-                        let new_str: String<N> = chars.iter().collect::<String<N>>();
-
-                        // Only proceed if this permutation hasn't been seen.
-                        if seen.insert(new_str.clone()).is_ok() {
-                            queue.push_back((new_str, depth + 1)).ok();
-                        }
-                    }
+    OcrPermutations::<N>::new(input, model, class, max_depth)
+        .take(M)
+        .map(|permutation| permutation.text)
+        .collect()
+}
+
+/// A permutation of an OCR reading, see [`OcrPermutations`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Permutation<const N: usize> {
+    /// The permuted string.
+    pub text: String<N>,
+    /// Total cost of the substitutions applied to the input.
+    pub cost: u32,
+    /// The ICAO check digit of `text`, `None` if it holds characters outside the MRZ
+    /// alphabet.
+    pub checksum: Option<u8>,
+}
+
+/// Lazily enumerates the permutations of an OCR reading in order of increasing cost.
+///
+/// The input itself comes first at cost 0. Each position is substituted at most once,
+/// and at most `max_depth` positions per permutation; every combination is produced
+/// exactly once. Among permutations of equal cost, substitutions of earlier positions
+/// come first, so with unit costs the order is that of a breadth-first search.
+///
+/// The state is a choice per position, so memory stays linear in `N` however many
+/// permutations are enumerated. Each cost level is searched depth-first, and the
+/// checksum of each permutation is updated per substitution rather than recomputed.
+///
+/// # Panics
+///
+/// [`OcrPermutations::new`] panics if `N == 0` or the input is longer than `N`.
+///
+/// # Examples
+///
+/// ```
+/// use mrz_core::confusion::IcaoOcrB;
+/// use mrz_core::ocr::OcrPermutations;
+/// use mrz_core::CharClass;
+/// let mut perms = OcrPermutations::<9>::new("L8989O2C3", &IcaoOcrB, CharClass::Alphanumeric, 2);
+/// let first = perms.next().unwrap();
+/// assert_eq!((first.text.as_str(), first.cost), ("L8989O2C3", 0));
+/// // `L898902C3` is the only permutation with check digit 6 this cheap.
+/// let fixed = perms.find(|p| p.checksum == Some(6)).unwrap();
+/// assert_eq!((fixed.text.as_str(), fixed.cost), ("L898902C3", 1));
+/// ```
+#[derive(Debug, Clone)]
+pub struct OcrPermutations<const N: usize> {
+    chars: Vec<char, N>,
+    confusions: Vec<Vec<Confusion, MAX_CONFUSIONS>, N>,
+    // Cost of substituting every position from this one on with its dearest confusion.
+    reach: Vec<u32, N>,
+    // Per position, the index of the confusion applied; `confusions[i].len()` keeps the
+    // character as read, and anything beyond means every choice has been tried.
+    choices: Vec<u8, N>,
+    max_depth: usize,
+    // Column of the first character in the data covered by the checksum.
+    offset: usize,
+    level: u32,
+    max_level: u32,
+    level_done: bool,
+    // The positions before this one hold their current choice.
+    position: usize,
+    cost: u32,
+    depth: usize,
+    // The weighted checksum sum of the current permutation, and the number of its
+    // characters outside the MRZ alphabet.
+    sum: u32,
+    invalid: usize,
+}
+
+impl<const N: usize> OcrPermutations<N> {
+    /// Enumerates the permutations of `input` with the confusions of `model` legal in a
+    /// field of `class`, see [`confusions_for`].
+    pub fn new(
+        input: &str,
+        model: &dyn ConfusionModel,
+        class: CharClass,
+        max_depth: usize,
+    ) -> Self {
+        Self::with_substitutions(input, max_depth, |_, c| confusions_for(model, c, class))
+    }
+
+    // Takes the substitutions of the character at each position from `substitutions`,
+    // which must list them cheapest first.
+    pub(crate) fn with_substitutions(
+        input: &str,
+        max_depth: usize,
+        substitutions: impl Fn(usize, char) -> Vec<Confusion, MAX_CONFUSIONS>,
+    ) -> Self {
+        assert!(N > 0, "N must be greater than 0");
+        assert!(input.len() <= N, "Input length exceeds buffer limit");
+
+        let mut permutations = OcrPermutations {
+            chars: input.chars().collect(),
+            confusions: Vec::new(),
+            reach: Vec::new(),
+            choices: Vec::new(),
+            max_depth,
+            offset: 0,
+            level: 0,
+            max_level: 0,
+            level_done: false,
+            position: 0,
+            cost: 0,
+            depth: 0,
+            sum: 0,
+            invalid: 0,
+        };
+        let mut dearest: Vec<u32, N> = Vec::new();
+        for (i, c) in input.chars().enumerate() {
+            let confusions = substitutions(i, c);
+            let _ = dearest.push(confusions.last().map_or(0, |c| u32::from(c.cost)));
+            let _ = permutations.choices.push(0);
+            let _ = permutations.confusions.push(confusions);
+            permutations.add(i, c);
+        }
+        let mut reach = 0;
+        for &cost in dearest.iter().rev() {
+            reach += cost;
+            let _ = permutations.reach.insert(0, reach);
+        }
+        dearest.sort_unstable_by(|a, b| b.cmp(a));
+        permutations.max_level = dearest.iter().take(max_depth).sum();
+        permutations
+    }
+
+    /// Weighs the [`Permutation::checksum`] as if the input started at column `offset`
+    /// of a longer field, such as a segment of the data covered by a composite check
+    /// digit. The checksum then is that segment's share of the composite checksum.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mrz_core::checksum::compute_checksum;
+    /// use mrz_core::confusion::IcaoOcrB;
+    /// use mrz_core::ocr::OcrPermutations;
+    /// use mrz_core::CharClass;
+    /// let mut perms = OcrPermutations::<3>::new("Z1O", &IcaoOcrB, CharClass::Digit, 1).at_offset(2);
+    /// assert_eq!(perms.next().unwrap().checksum, compute_checksum(b"<<Z1O"));
+    /// ```
+    pub fn at_offset(mut self, offset: usize) -> Self {
+        self.offset = offset;
+        self.sum = 0;
+        self.invalid = 0;
+        for i in 0..self.chars.len() {
+            let c = match self.confusions[i].get(usize::from(self.choices[i])) {
+                Some(confusion) if i < self.position => confusion.replacement,
+                _ => self.chars[i],
+            };
+            self.add(i, c);
+        }
+        self
+    }
+
+    // Adds the weighted value of `c` at position `i` to the checksum sum.
+    fn add(&mut self, i: usize, c: char) {
+        let column = self.offset + i;
+        match u8::try_from(c).ok().and_then(|b| weighted_value(column, b)) {
+            Some(value) => self.sum += value,
+            None => self.invalid += 1,
+        }
+    }
+
+    // Removes the weighted value of `c` at position `i` from the checksum sum.
+    fn remove(&mut self, i: usize, c: char) {
+        let column = self.offset + i;
+        match u8::try_from(c).ok().and_then(|b| weighted_value(column, b)) {
+            Some(value) => self.sum -= value,
+            None => self.invalid -= 1,
+        }
+    }
+
+    fn reach_from(&self, i: usize) -> u32 {
+        self.reach.get(i).copied().unwrap_or(0)
+    }
+
+    // Continues the depth-first search of the current cost level to its next
+    // permutation.
+    fn search(&mut self) -> Option<Permutation<N>> {
+        let len = self.chars.len();
+        while !self.level_done {
+            if self.position == len {
+                let found = (self.cost == self.level).then(|| self.current());
+                self.level_done = !self.backtrack();
+                if found.is_some() {
+                    return found;
                 }
-                None => continue,
+                continue;
+            }
+
+            let i = self.position;
+            let choice = usize::from(self.choices[i]);
+            let count = self.confusions[i].len();
+            if choice > count {
+                self.choices[i] = 0;
+                self.level_done = !self.backtrack();
+                continue;
             }
+            let confusion = self.confusions[i].get(choice).copied();
+            let cost = confusion.map_or(0, |c| u32::from(c.cost));
+            if confusion.is_some()
+                && (self.cost + cost > self.level || self.depth == self.max_depth)
+            {
+                // Confusions are sorted by cost, so the remaining ones cost too much
+                // as well; keep the character as read.
+                self.choices[i] = count as u8;
+                continue;
+            }
+            if self.cost + cost + self.reach_from(i + 1) < self.level {
+                self.choices[i] += 1;
+                continue;
+            }
+            if let Some(confusion) = confusion {
+                self.remove(i, self.chars[i]);
+                self.add(i, confusion.replacement);
+                self.cost += cost;
+                self.depth += 1;
+            }
+            self.position += 1;
         }
+        None
     }
 
-    results
+    // Undoes the choice of the last decided position and moves on to its next one.
+    // Returns `false` when no position is decided.
+    fn backtrack(&mut self) -> bool {
+        let Some(i) = self.position.checked_sub(1) else {
+            return false;
+        };
+        self.position = i;
+        if let Some(confusion) = self.confusions[i]
+            .get(usize::from(self.choices[i]))
+            .copied()
+        {
+            self.remove(i, confusion.replacement);
+            self.add(i, self.chars[i]);
+            self.cost -= u32::from(confusion.cost);
+            self.depth -= 1;
+        }
+        self.choices[i] += 1;
+        true
+    }
+
+    fn current(&self) -> Permutation<N> {
+        let text = self
+            .chars
+            .iter()
+            .zip(self.confusions.iter().zip(&self.choices))
+            .map(|(&c, (confusions, &choice))| {
+                confusions
+                    .get(usize::from(choice))
+                    .map_or(c, |confusion| confusion.replacement)
+            })
+            .collect();
+        Permutation {
+            text,
+            cost: self.cost,
+            checksum: (self.invalid == 0).then_some((self.sum % 10) as u8),
+        }
+    }
+}
+
+impl<const N: usize> Iterator for OcrPermutations<N> {
+    type Item = Permutation<N>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.level <= self.max_level {
+            if let Some(permutation) = self.search() {
+                return Some(permutation);
+            }
+            // The search has backtracked to the first position; start the next level.
+            self.level += 1;
+            self.level_done = false;
+            self.choices.iter_mut().for_each(|choice| *choice = 0);
+        }
+        None
+    }
 }
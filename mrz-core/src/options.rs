@@ -0,0 +1,136 @@
+use crate::confidence::DEFAULT_CONFIDENCE_THRESHOLD;
+use crate::confusion::{ConfusionModel, IcaoOcrB};
+
+/// Upper bound on the number of OCR correction candidates examined per field.
+///
+/// [`ParseOptions::correction_budget`] values above this limit are clamped to it.
+pub const MAX_CORRECTION_BUDGET: usize = 64;
+
+/// Cost of assuming that the composite check digit itself was misread, against which
+/// corrections of the optional data compete; see [`ParseOptions::correct_optional_data`].
+pub const COMPOSITE_MISREAD_COST: u32 = 3;
+
+/// How a failed check digit is handled while parsing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ChecksumPolicy {
+    /// Abort parsing with [`crate::MRZParseError::InvalidChecksumField`].
+    #[default]
+    Fatal,
+    /// Keep the raw field value and record the failure in its [`crate::CheckedField`].
+    Record,
+}
+
+/// Tolerances applied by [`crate::parser::parse_any_with`].
+///
+/// The default matches [`crate::parser::parse_any`]: lenient line lengths, no input
+/// normalization, OCR correction enabled with a budget of 8 candidates per field,
+/// and fatal checksum failures.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParseOptions {
+    /// Require every line to have the exact length of the detected format.
+    ///
+    /// When disabled, TD3 and MRV-A lines longer than 44 characters are accepted and
+    /// callers such as `mrz_host::parse_lines` pad short lines with `<`.
+    pub strict_lengths: bool,
+    /// Canonicalize the input with [`crate::normalize::normalize_lines`] before parsing.
+    ///
+    /// Error positions then refer to the normalized lines.
+    pub normalize_input: bool,
+    /// Try every order of the lines and parse the one whose check digits validate, see
+    /// [`crate::line_order::detect_line_order`].
+    pub reorder_lines: bool,
+    /// Restore dropped, duplicated or shifted characters with [`crate::realign::realign`]
+    /// before parsing.
+    pub realign_lines: bool,
+    /// Try OCR substitutions on fields whose check digit does not match, and move
+    /// characters of unchecked fields such as the nationality into the field's class
+    /// (e.g. `UT0` to `UTO`). Substitutions never move a character out of its class.
+    /// On TD1 and TD3 documents, a failed composite check digit or tied corrections
+    /// are resolved jointly across the checked fields.
+    pub ocr_correction: bool,
+    /// Also let OCR correction satisfy a failed composite check digit of a TD1 or TD3
+    /// document by substituting characters of the optional data, which no other check
+    /// digit covers.
+    ///
+    /// A rewrite is only adopted when its substitutions cost less than
+    /// [`COMPOSITE_MISREAD_COST`], the cost of assuming the composite check digit was
+    /// misread instead. Since the check digit then chose the data rather than verified
+    /// it, the rewrite is recorded, see
+    /// [`crate::MrzIcaoCommonFields::optional_data_correction`]. Off in every preset.
+    pub correct_optional_data: bool,
+    /// Maximum number of substitutions applied to a single field.
+    pub correction_depth: usize,
+    /// Maximum number of correction candidates examined per field,
+    /// clamped to [`MAX_CORRECTION_BUDGET`].
+    pub correction_budget: usize,
+    /// Glyph confusions tried by OCR correction, cheapest first. Defaults to
+    /// [`IcaoOcrB`].
+    pub confusion_model: &'static dyn ConfusionModel,
+    /// Characters of a [`crate::confidence::ScoredLine`] read with at least this
+    /// confidence are never substituted by OCR correction, see
+    /// [`crate::parser::parse_scored`].
+    pub confidence_threshold: u8,
+    /// Whether a check digit mismatch aborts parsing or is only recorded.
+    pub checksum_policy: ChecksumPolicy,
+}
+
+impl ParseOptions {
+    /// Exact line lengths, no normalization or OCR correction and fatal checksum failures.
+    pub const fn strict() -> Self {
+        ParseOptions {
+            strict_lengths: true,
+            normalize_input: false,
+            reorder_lines: false,
+            realign_lines: false,
+            ocr_correction: false,
+            correct_optional_data: false,
+            correction_depth: 0,
+            correction_budget: 0,
+            confusion_model: &IcaoOcrB,
+            confidence_threshold: DEFAULT_CONFIDENCE_THRESHOLD,
+            checksum_policy: ChecksumPolicy::Fatal,
+        }
+    }
+
+    /// Lenient line lengths, input normalization, reordering and realignment, OCR
+    /// correction and checksum failures recorded instead of aborting, so that a
+    /// best-effort document is always returned.
+    ///
+    /// Unlike the default, this enables `normalize_input`: lowercase letters, look-alike
+    /// characters and whitespace are rewritten before parsing and error positions refer
+    /// to the normalized lines. [`crate::report::ParseReport::normalization`] lists the
+    /// rewrites.
+    pub const fn lenient() -> Self {
+        ParseOptions {
+            strict_lengths: false,
+            normalize_input: true,
+            reorder_lines: true,
+            realign_lines: true,
+            ocr_correction: true,
+            correct_optional_data: false,
+            correction_depth: 8,
+            correction_budget: MAX_CORRECTION_BUDGET,
+            confusion_model: &IcaoOcrB,
+            confidence_threshold: DEFAULT_CONFIDENCE_THRESHOLD,
+            checksum_policy: ChecksumPolicy::Record,
+        }
+    }
+}
+
+impl Default for ParseOptions {
+    fn default() -> Self {
+        ParseOptions {
+            strict_lengths: false,
+            normalize_input: false,
+            reorder_lines: false,
+            realign_lines: false,
+            ocr_correction: true,
+            correct_optional_data: false,
+            correction_depth: 8,
+            correction_budget: 8,
+            confusion_model: &IcaoOcrB,
+            confidence_threshold: DEFAULT_CONFIDENCE_THRESHOLD,
+            checksum_policy: ChecksumPolicy::Fatal,
+        }
+    }
+}
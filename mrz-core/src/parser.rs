@@ -1,8 +1,21 @@
+use crate::bcbp::parse_bcbp;
+use crate::checked_field::{Correction, CorrectionCandidate, MAX_CORRECTION_CANDIDATES};
 use crate::checksum::compute_checksum;
-use crate::field_correction::correct_checked_field;
+use crate::confidence::ScoredLine;
+use crate::confusion::confusions_for;
+use crate::field_correction::{
+    cheapest_combination, correct_checked_field_as, correct_scored_field,
+};
+use crate::line_order::detect_line_order;
+use crate::normalize::{normalize_lines, NormalizedInput, MAX_NORMALIZED_LINES};
+use crate::ocr::{OcrPermutations, Permutation};
+use crate::options::{ChecksumPolicy, ParseOptions, COMPOSITE_MISREAD_COST, MAX_CORRECTION_BUDGET};
+use crate::realign::realign_with;
+use crate::report::{IssueKind, MrzField, ParseIssue, ParseReport};
 use crate::{
-    CheckedField, MRZChecksumError, MRZFormat, MRZParseError, MrzIcaoTd3, ParsedMRZ,
-    ICAO_COMMON_COUNTRY_CODE_LEN, ICAO_COMMON_DATE_LEN, ICAO_COMMON_DOC_NUM_MAX_LEN,
+    CharClass, CheckedField, ErrorPosition, MRZChecksumError, MRZFormat, MRZParseError, MrzIcao,
+    MrzIcaoTd3, ParsedMRZ, CH_DL_DOC_CODE, CH_DL_LINE1_LEN, CH_DL_LINE_LEN,
+    ICAO_COMMON_COUNTRY_CODE_LEN, ICAO_COMMON_DATE_LEN, ICAO_EXTENDED_DOC_NUM_MAX_LEN,
     ICAO_TD1_OPTIONAL1_MAX_LEN, ICAO_TD1_OPTIONAL2_MAX_LEN, ICAO_TD3_NAME_MAX_LEN,
     ICAO_TD3_OPTIONAL1_MAX_LEN, ICAO_TD3_OPTIONAL2_MAX_LEN,
 };
@@ -11,35 +24,113 @@ use core::fmt::Write;
 use core::str::FromStr;
 use heapless::String;
 
-// Maximum number of permutations to try for field correction.
-const MAX_FIELD_PERMUTATIONS: usize = 8;
+// Maximum length of the data covered by a composite check digit
+// (71 characters for the legacy French ID card, which covers both lines).
+const COMPOSITE_MAX_LEN: usize = 72;
+
+// Per-character confidences of the input lines, when parsing scored lines.
+type Readings<'a> = Option<&'a [ScoredLine<'a>]>;
+
+// Where a checked field sits in the input, so that errors can point at it.
+#[derive(Clone, Copy)]
+struct FieldLocation {
+    line: usize,
+    start: usize,
+    end: usize,
+    check: usize,
+    // Column where characters beyond `start..end` continue (extended document numbers).
+    continuation: usize,
+    class: CharClass,
+}
+
+impl FieldLocation {
+    const fn new(line: usize, start: usize, end: usize, check: usize, class: CharClass) -> Self {
+        FieldLocation {
+            line,
+            start,
+            end,
+            check,
+            continuation: end,
+            class,
+        }
+    }
+
+    const fn continued_at(self, continuation: usize, check: usize) -> Self {
+        FieldLocation {
+            continuation,
+            check,
+            ..self
+        }
+    }
+
+    fn column_of(&self, index: usize) -> usize {
+        let width = self.end - self.start;
+        if index < width {
+            self.start + index
+        } else {
+            self.continuation + index - width
+        }
+    }
+
+    fn span(&self) -> ErrorPosition {
+        ErrorPosition::new(self.line, self.start, self.check.max(self.end) + 1)
+    }
+
+    // Explains why a field could not be validated: the first character outside the
+    // field's class or a non-digit check digit, else a plain checksum mismatch.
+    fn diagnose(&self, field: &[u8], check_char: u8, kind: MRZChecksumError) -> MRZParseError {
+        if let Some(index) = field.iter().position(|&c| !self.class.contains(c)) {
+            return MRZParseError::InvalidCharacter {
+                position: ErrorPosition::at(self.line, self.column_of(index)),
+                found: field[index],
+                expected: self.class,
+            };
+        }
+        if !check_char.is_ascii_digit() {
+            return MRZParseError::InvalidCharacter {
+                position: ErrorPosition::at(self.line, self.check),
+                found: check_char,
+                expected: CharClass::Digit,
+            };
+        }
+        MRZParseError::InvalidChecksumField(kind, Some(self.span()))
+    }
+}
+
+// Decodes a field as UTF-8, pointing at the first invalid byte on failure.
+fn decode_field<'a>(field: &'a [u8], location: &FieldLocation) -> Result<&'a str, MRZParseError> {
+    core::str::from_utf8(field).map_err(|e| {
+        let column = location.column_of(e.valid_up_to());
+        MRZParseError::Utf8Error(Some(ErrorPosition::at(location.line, column)))
+    })
+}
 
 // Generic helper to parse a checked field and return CheckedField<[u8; N]>.
 fn parse_checked_field_with_correction<const N: usize>(
     line: &[u8],
-    start: usize,
-    end: usize,
-    _check: usize,
+    location: FieldLocation,
     kind: MRZChecksumError,
+    options: &ParseOptions,
+    readings: Readings<'_>,
 ) -> Result<CheckedField<[u8; N]>, MRZParseError> {
-    let field = &line[start..end];
-    let check_char = line[_check] as char;
+    let field = &line[location.start..location.end];
+    let check_char = line[location.check] as char;
 
-    let field_str = core::str::from_utf8(field).map_err(|_| MRZParseError::Utf8Error)?;
-    let corrected = parse_correctable_checked_field::<N, String<N>>(field_str, check_char, kind)?;
+    let field_str = decode_field(field, &location)?;
+    let corrected = parse_correctable_checked_field::<N, String<N>>(
+        field_str, check_char, kind, location, options, readings,
+    )?;
 
-    let array = corrected.value().as_bytes().try_into().unwrap_or([b'0'; N]);
-    Ok(CheckedField::new(
-        array,
-        corrected.error().cloned(),
-        check_char as u8,
-    ))
+    Ok(corrected.map(|value| value.as_bytes().try_into().unwrap_or([b'0'; N])))
 }
 
 fn parse_correctable_checked_field<const N: usize, T>(
     field_str: &str,
     check_char: char,
     kind: MRZChecksumError,
+    location: FieldLocation,
+    options: &ParseOptions,
+    readings: Readings<'_>,
 ) -> Result<CheckedField<T>, MRZParseError>
 where
     T: FromStr + Clone + for<'a> TryFrom<&'a str> + core::fmt::Debug,
@@ -47,51 +138,479 @@ where
 {
     let raw_bytes = field_str.as_bytes();
     if verify_checksum(raw_bytes, check_char as u8) {
-        let parsed: T = field_str
-            .parse()
-            .map_err(|_| MRZParseError::from_checksum(kind))?;
+        let parsed: T = field_str.parse().map_err(|_| {
+            MRZParseError::InvalidChecksumField(kind.clone(), Some(location.span()))
+        })?;
         return Ok(CheckedField::new(parsed, None, check_char as u8));
     }
 
-    correct_checked_field::<N, MAX_FIELD_PERMUTATIONS, T>(
-        field_str,
-        check_char,
-        MAX_FIELD_PERMUTATIONS,
-        kind,
+    let scored = readings.and_then(|lines| lines.get(location.line));
+    let corrected = if let (true, Some(line)) = (options.ocr_correction, scored) {
+        let mut confidences: heapless::Vec<u8, N> = heapless::Vec::new();
+        let mut alternatives: heapless::Vec<&[u8], N> = heapless::Vec::new();
+        for index in 0..raw_bytes.len() {
+            let column = location.column_of(index);
+            let _ = confidences.push(line.confidence(column));
+            let _ = alternatives.push(line.alternatives(column));
+        }
+        let field = ScoredLine::new(raw_bytes, &confidences).with_alternatives(&alternatives);
+        correct_scored_field::<N, MAX_CORRECTION_BUDGET, T>(
+            &field,
+            check_char,
+            location.class,
+            options,
+            kind.clone(),
+        )
+    } else if options.ocr_correction {
+        correct_checked_field_as::<N, MAX_CORRECTION_BUDGET, T>(
+            field_str,
+            check_char,
+            location.class,
+            options.confusion_model,
+            options.correction_depth,
+            options.correction_budget,
+            kind.clone(),
+        )
+    } else {
+        Err(MRZParseError::from_checksum(kind.clone()))
+    };
+
+    match (corrected, options.checksum_policy) {
+        (Ok(field), _) => Ok(field),
+        (Err(_), ChecksumPolicy::Fatal) => {
+            Err(location.diagnose(raw_bytes, check_char as u8, kind))
+        }
+        (Err(_), ChecksumPolicy::Record) => {
+            let raw: T = field_str.parse().map_err(|_| {
+                MRZParseError::InvalidChecksumField(kind.clone(), Some(location.span()))
+            })?;
+            Ok(CheckedField::new(raw, Some(kind), check_char as u8))
+        }
+    }
+}
+
+// Parses the 9-character document number field and its check digit.
+fn parse_document_number(
+    line: &[u8],
+    location: FieldLocation,
+    options: &ParseOptions,
+    readings: Readings<'_>,
+) -> Result<CheckedField<String<ICAO_EXTENDED_DOC_NUM_MAX_LEN>>, MRZParseError> {
+    parse_correctable_checked_field::<
+        ICAO_EXTENDED_DOC_NUM_MAX_LEN,
+        String<ICAO_EXTENDED_DOC_NUM_MAX_LEN>,
+    >(
+        decode_field(&line[location.start..location.end], &location)?,
+        line[location.check] as char,
+        MRZChecksumError::DocumentNumber,
+        location,
+        options,
+        readings,
     )
 }
 
+// Parses the document number of a TD1 or TD2 document. When the number is longer
+// than the 9-character field, the check digit position holds `<` and the remaining
+// characters, followed by the real check digit, continue in the optional data field
+// (ICAO Doc 9303 Parts 5 and 6; TD3 has no such extension). Returns the document
+// number and the unconsumed optional data.
+fn parse_extended_document_number<'a>(
+    line: &'a [u8],
+    location: FieldLocation,
+    optional_start: usize,
+    optional_end: usize,
+    options: &ParseOptions,
+    readings: Readings<'_>,
+) -> Result<
+    (
+        CheckedField<String<ICAO_EXTENDED_DOC_NUM_MAX_LEN>>,
+        &'a [u8],
+    ),
+    MRZParseError,
+> {
+    let number_field = &line[location.start..location.end];
+    let check_char = line[location.check];
+    let optional_data = &line[optional_start..optional_end];
+    let extension_len = optional_data
+        .iter()
+        .position(|&b| b == b'<')
+        .unwrap_or(optional_data.len());
+
+    if check_char != b'<' || extension_len == 0 {
+        let document_number = parse_document_number(line, location, options, readings)?;
+        return Ok((document_number, optional_data));
+    }
+
+    let (extension, real_check) = optional_data[..extension_len].split_at(extension_len - 1);
+    let location = location.continued_at(optional_start, optional_start + extension_len - 1);
+    let mut full_number: heapless::Vec<u8, ICAO_EXTENDED_DOC_NUM_MAX_LEN> = heapless::Vec::new();
+    full_number
+        .extend_from_slice(number_field)
+        .and_then(|_| full_number.extend_from_slice(extension))
+        .map_err(|_| MRZParseError::InvalidLength(Some(location.span())))?;
+
+    let document_number = parse_correctable_checked_field::<
+        ICAO_EXTENDED_DOC_NUM_MAX_LEN,
+        String<ICAO_EXTENDED_DOC_NUM_MAX_LEN>,
+    >(
+        decode_field(&full_number, &location)?,
+        real_check[0] as char,
+        MRZChecksumError::DocumentNumber,
+        location,
+        options,
+        readings,
+    )?;
+    Ok((document_number, &optional_data[extension_len..]))
+}
+
+// Re-encodes a (possibly extended) document number into its MRZ layout for the
+// composite check: the 9-character number field with its check digit, and the
+// optional data field, which starts with the extended part and its check digit.
+fn encode_document_number(
+    document_number: &CheckedField<String<ICAO_EXTENDED_DOC_NUM_MAX_LEN>>,
+    optional_data: &str,
+) -> (heapless::Vec<u8, 10>, heapless::Vec<u8, 32>) {
+    const NUMBER_FIELD_LEN: usize = 9;
+
+    let number = document_number.value().as_bytes();
+    let check = document_number.check_digit().unwrap_or(b'<');
+    let mut number_field: heapless::Vec<u8, 10> = heapless::Vec::new();
+    let mut optional_field: heapless::Vec<u8, 32> = heapless::Vec::new();
+
+    if number.len() > NUMBER_FIELD_LEN {
+        let _ = number_field.extend_from_slice(&number[..NUMBER_FIELD_LEN]);
+        let _ = number_field.push(b'<');
+        let _ = optional_field.extend_from_slice(&number[NUMBER_FIELD_LEN..]);
+        let _ = optional_field.push(check);
+    } else {
+        let _ = number_field.extend_from_slice(number);
+        while number_field.len() < NUMBER_FIELD_LEN {
+            let _ = number_field.push(b'<');
+        }
+        let _ = number_field.push(check);
+    }
+    let _ = optional_field.extend_from_slice(optional_data.as_bytes());
+
+    (number_field, optional_field)
+}
+
+// The data covered by the composite check digit of a TD1 or TD3 document, whose
+// fields `Composite::correct` corrects jointly.
+#[derive(Clone)]
+struct Composite {
+    format: MRZFormat,
+    document_number: CheckedField<String<ICAO_EXTENDED_DOC_NUM_MAX_LEN>>,
+    birth_date: CheckedField<[u8; ICAO_COMMON_DATE_LEN]>,
+    expiry_date: CheckedField<[u8; ICAO_COMMON_DATE_LEN]>,
+    // Optional data of the first line and, for TD1, of the second line.
+    optional_data: [String<ICAO_TD1_OPTIONAL1_MAX_LEN>; 2],
+    // Substitutions made in the optional data under `correct_optional_data`.
+    optional_corrections: [Option<Correction>; 2],
+}
+
+impl Composite {
+    // Encodes the data in MRZ order, with the offsets of the optional data fields.
+    fn encode(&self) -> (heapless::Vec<u8, COMPOSITE_MAX_LEN>, [usize; 2]) {
+        let (number_field, optional_area) =
+            encode_document_number(&self.document_number, &self.optional_data[0]);
+        let birth_date = self.birth_date.as_slice_with_check();
+        let expiry_date = self.expiry_date.as_slice_with_check();
+        let optional_data2 = self.optional_data[1].as_bytes();
+
+        let mut data: heapless::Vec<u8, COMPOSITE_MAX_LEN> = heapless::Vec::new();
+        let mut offsets = [0; 2];
+        let _ = data.extend_from_slice(&number_field);
+        if self.format == MRZFormat::MrzIcaoTd1 {
+            // The TD1 composite covers all of line 1 from the document number on,
+            // optional data 1 included (ICAO Doc 9303 Part 5), not only the
+            // document number and its check digit.
+            let _ = data.extend_from_slice(&optional_area);
+            offsets[0] = data.len() - self.optional_data[0].len();
+            let _ = data.extend_from_slice(&birth_date);
+            let _ = data.extend_from_slice(&expiry_date);
+        } else {
+            let _ = data.extend_from_slice(&birth_date);
+            let _ = data.extend_from_slice(&expiry_date);
+            let _ = data.extend_from_slice(&optional_area);
+            offsets[0] = data.len() - self.optional_data[0].len();
+        }
+        offsets[1] = data.len();
+        let _ = data.extend_from_slice(optional_data2);
+        (data, offsets)
+    }
+
+    // When the composite check digit fails, or a field has tied corrections, adopts
+    // the cheapest combination of field candidates and, under `correct_optional_data`,
+    // optional data permutations that satisfies it. A misread composite check digit
+    // competes at `COMPOSITE_MISREAD_COST`; nothing changes if it wins or if several
+    // combinations tie.
+    fn correct(&mut self, check_char: u8, options: &ParseOptions) {
+        let check_digit = class_normalized_char(check_char, CharClass::Digit, options);
+        if !options.ocr_correction || !check_digit.is_ascii_digit() {
+            return;
+        }
+        let (data, offsets) = self.encode();
+        let Some(base) = compute_checksum(&data) else {
+            return;
+        };
+        let ambiguous = self.document_number.is_ambiguous()
+            || self.birth_date.is_ambiguous()
+            || self.expiry_date.is_ambiguous();
+        if base == check_digit - b'0' && !ambiguous {
+            return;
+        }
+
+        let delta = |composite: &Composite| {
+            compute_checksum(&composite.encode().0).map(|checksum| (10 + checksum - base) % 10)
+        };
+        let document_numbers = candidate_options(self.document_number.candidates(), |c| {
+            let mut composite = self.clone();
+            composite.document_number = adopt_candidate(&self.document_number, c, c.value.clone());
+            delta(&composite)
+        });
+        let birth_dates = candidate_options(self.birth_date.candidates(), |c| {
+            let mut composite = self.clone();
+            let value = c.value.as_bytes().try_into().ok()?;
+            composite.birth_date = adopt_candidate(&self.birth_date, c, value);
+            delta(&composite)
+        });
+        let expiry_dates = candidate_options(self.expiry_date.candidates(), |c| {
+            let mut composite = self.clone();
+            let value = c.value.as_bytes().try_into().ok()?;
+            composite.expiry_date = adopt_candidate(&self.expiry_date, c, value);
+            delta(&composite)
+        });
+        let (Some(document_numbers), Some(birth_dates), Some(expiry_dates)) =
+            (document_numbers, birth_dates, expiry_dates)
+        else {
+            return;
+        };
+        let optional_options = |index: usize| {
+            let mut choices: heapless::Vec<(u32, u8), MAX_CORRECTION_BUDGET> = heapless::Vec::new();
+            let _ = choices.push((0, 0));
+            if !options.correct_optional_data {
+                return choices;
+            }
+            let mut permutations = self.optional_permutations(index, offsets[index], options);
+            let as_read = permutations.next().and_then(|p| p.checksum).unwrap_or(0);
+            for permutation in permutations {
+                let checksum = permutation.checksum.unwrap_or(0);
+                let _ = choices.push((permutation.cost, (10 + checksum - as_read) % 10));
+            }
+            choices
+        };
+        let optional_data1 = optional_options(0);
+        let optional_data2 = optional_options(1);
+        // Reading the check digit as `digit` shifts the checksum the data must reach.
+        let read = check_digit - b'0';
+        let misread: heapless::Vec<(u32, u8), 10> = (0..10)
+            .map(|digit| match digit == read {
+                true => (0, 0),
+                false => (COMPOSITE_MISREAD_COST, (10 + read - digit) % 10),
+            })
+            .collect();
+
+        let target = (10 + read - base) % 10;
+        let parts: [&[(u32, u8)]; 6] = [
+            &document_numbers,
+            &birth_dates,
+            &expiry_dates,
+            &optional_data1,
+            &optional_data2,
+            &misread,
+        ];
+        let Some(picks) = cheapest_combination(&parts, target) else {
+            return;
+        };
+        if usize::from(read) != picks[5] {
+            return;
+        }
+
+        if let Some(candidate) = picked_candidate(&self.document_number, picks[0]) {
+            self.document_number =
+                adopt_candidate(&self.document_number, candidate, candidate.value.clone());
+        }
+        if let Some(candidate) = picked_candidate(&self.birth_date, picks[1]) {
+            if let Ok(value) = candidate.value.as_bytes().try_into() {
+                self.birth_date = adopt_candidate(&self.birth_date, candidate, value);
+            }
+        }
+        if let Some(candidate) = picked_candidate(&self.expiry_date, picks[2]) {
+            if let Ok(value) = candidate.value.as_bytes().try_into() {
+                self.expiry_date = adopt_candidate(&self.expiry_date, candidate, value);
+            }
+        }
+        for index in 0..2 {
+            let pick = picks[3 + index];
+            if pick == 0 {
+                continue;
+            }
+            let permutation = self
+                .optional_permutations(index, offsets[index], options)
+                .nth(pick);
+            if let Some(permutation) = permutation {
+                let correction = Correction::new(&self.optional_data[index], &permutation.text);
+                self.optional_corrections[index] = Some(correction);
+                self.optional_data[index] = permutation.text;
+            }
+        }
+    }
+
+    // Permutations of an optional data field, fillers excepted, whose checksums are
+    // their share of the composite checksum. The field as read comes first. The
+    // optional data of a TD3 document ends with the check digit of the personal
+    // number, which every other permutation must satisfy.
+    fn optional_permutations<'a>(
+        &self,
+        index: usize,
+        offset: usize,
+        options: &'a ParseOptions,
+    ) -> impl Iterator<Item = Permutation<ICAO_TD1_OPTIONAL1_MAX_LEN>> + 'a {
+        let model = options.confusion_model;
+        let personal_number = self.format == MRZFormat::MrzIcaoTd3 && index == 0;
+        let mut permutations = OcrPermutations::with_substitutions(
+            &self.optional_data[index],
+            options.correction_depth,
+            |_, c| {
+                if c == '<' {
+                    heapless::Vec::new()
+                } else {
+                    confusions_for(model, c, CharClass::Alphanumeric)
+                }
+            },
+        )
+        .at_offset(offset);
+        let as_read = permutations.next();
+        let alternatives = permutations.filter(move |permutation| {
+            permutation.checksum.is_some()
+                && (!personal_number || personal_number_valid(permutation.text.as_bytes()))
+        });
+        as_read
+            .into_iter()
+            .chain(alternatives)
+            .take(options.correction_budget.min(MAX_CORRECTION_BUDGET))
+    }
+}
+
+// Whether the TD3 optional data `field` satisfies its trailing check digit, if any.
+fn personal_number_valid(field: &[u8]) -> bool {
+    match field.split_last() {
+        Some((&check_digit, number)) if check_digit != b'<' => verify_checksum(number, check_digit),
+        _ => true,
+    }
+}
+
+// The `(cost, checksum delta)` of each candidate of a field, computed by `delta`; a field
+// without candidates keeps its value. `None` if a delta cannot be computed.
+fn candidate_options(
+    candidates: &[CorrectionCandidate],
+    delta: impl Fn(&CorrectionCandidate) -> Option<u8>,
+) -> Option<heapless::Vec<(u32, u8), MAX_CORRECTION_CANDIDATES>> {
+    if candidates.is_empty() {
+        return heapless::Vec::from_slice(&[(0, 0)]).ok();
+    }
+    candidates
+        .iter()
+        .map(|candidate| Some((candidate.cost, delta(candidate)?)))
+        .collect()
+}
+
+// The candidate picked by the composite check digit, unless the field keeps its value.
+fn picked_candidate<T>(field: &CheckedField<T>, pick: usize) -> Option<&CorrectionCandidate> {
+    if pick == 0 && !field.is_ambiguous() {
+        return None;
+    }
+    field.candidates().get(pick)
+}
+
+// Returns `field` holding `value`, the value of `candidate`, which becomes its only
+// candidate. The correction still leads back to the value as read.
+fn adopt_candidate<T, U>(
+    field: &CheckedField<T>,
+    candidate: &CorrectionCandidate,
+    value: U,
+) -> CheckedField<U> {
+    let adopted = CheckedField::new(value, None, candidate.check_digit);
+    let adopted = match field.raw_check_digit() {
+        Some(raw) if raw != candidate.check_digit => adopted.with_raw_check_digit(raw),
+        _ => adopted,
+    };
+    // The field holds its first candidate, from which the correction rebuilds the
+    // value as read.
+    let adopted = match (field.correction(), field.candidates().first()) {
+        (Some(correction), Some(current)) => {
+            let raw = correction.raw(&current.value);
+            adopted.with_correction(Correction::new(&raw, &candidate.value))
+        }
+        _ => adopted,
+    };
+    let mut candidates = heapless::Vec::new();
+    let _ = candidates.push(candidate.clone());
+    adopted.with_candidates(candidates)
+}
+
 fn validate_final_check(
     segments: &[&[u8]],
     check_char: u8,
     kind: MRZChecksumError,
+    position: ErrorPosition,
+    options: &ParseOptions,
 ) -> Result<Option<bool>, MRZParseError> {
     if check_char == b'<' {
         Ok(None)
     } else {
         match compute_composite_checksum(segments, check_char) {
             Some(true) => Ok(Some(true)),
-            Some(false) => Err(MRZParseError::InvalidChecksumField(kind)),
+            Some(false) if options.checksum_policy == ChecksumPolicy::Record => Ok(Some(false)),
+            Some(false) => Err(MRZParseError::InvalidChecksumField(kind, Some(position))),
             None => Ok(None),
         }
     }
 }
 
-fn verify_checksum(data: &[u8], check_digit: u8) -> bool {
-    match compute_checksum(data) {
-        Some(csum) => csum == (check_digit - b'0'),
-        None => false,
+// Validates the composite check digit of a document, repairing a misread check digit
+// (e.g. `O` for `0`) when OCR correction is enabled. Returns `None` if the composite
+// check digit is absent (`<`).
+fn final_check_field(
+    segments: &[&[u8]],
+    check_char: u8,
+    position: ErrorPosition,
+    options: &ParseOptions,
+) -> Result<Option<CheckedField<()>>, MRZParseError> {
+    if check_char == b'<' {
+        return Ok(None);
     }
+    let check_digit = class_normalized_char(check_char, CharClass::Digit, options);
+    let error = match validate_final_check(
+        segments,
+        check_digit,
+        MRZChecksumError::Final,
+        position,
+        options,
+    )? {
+        Some(false) => Some(MRZChecksumError::Final),
+        Some(true) | None => None,
+    };
+    let field = CheckedField::new((), error, check_digit);
+    Ok(Some(if check_digit != check_char {
+        field.with_raw_check_digit(check_char)
+    } else {
+        field
+    }))
+}
+
+fn verify_checksum(data: &[u8], check_digit: u8) -> bool {
+    check_digit.is_ascii_digit() && compute_checksum(data) == Some(check_digit - b'0')
 }
 
-fn compute_composite_checksum<'a>(segments: &[&'a [u8]], check_digit: u8) -> Option<bool> {
-    if (b'0'..=b'9').contains(&check_digit) {
-        let mut final_check_data: heapless::Vec<u8, 64> = heapless::Vec::new();
+fn compute_composite_checksum(segments: &[&[u8]], check_digit: u8) -> Option<bool> {
+    if check_digit.is_ascii_digit() {
+        let mut final_check_data: heapless::Vec<u8, COMPOSITE_MAX_LEN> = heapless::Vec::new();
         for segment in segments {
             final_check_data.extend_from_slice(segment).ok()?;
         }
         let checksum = compute_checksum(&final_check_data);
-        return checksum.map(|csum| csum == (check_digit - b'0'));
+        checksum.map(|csum| csum == (check_digit - b'0'))
     } else {
         None
     }
@@ -101,6 +620,40 @@ fn fixed_slice<const N: usize>(slice: &[u8]) -> [u8; N] {
     slice.try_into().unwrap_or([b' '; N])
 }
 
+// Copies an unchecked field, moving characters outside its class into it when OCR
+// correction is enabled and one substitution is cheapest (e.g. `UT0` -> `UTO`).
+fn class_normalized<const N: usize>(
+    slice: &[u8],
+    class: CharClass,
+    options: &ParseOptions,
+) -> [u8; N] {
+    let mut field = fixed_slice::<N>(slice);
+    for c in field.iter_mut() {
+        *c = class_normalized_char(*c, class, options);
+    }
+    field
+}
+
+fn class_normalized_char(c: u8, class: CharClass, options: &ParseOptions) -> u8 {
+    if !options.ocr_correction {
+        return c;
+    }
+    match confusions_for(options.confusion_model, c as char, class).as_slice() {
+        [cheapest] => cheapest.replacement as u8,
+        [cheapest, next, ..] if cheapest.cost < next.cost => cheapest.replacement as u8,
+        _ => c,
+    }
+}
+
+// Decodes a name field, normalizing its characters into letters like `class_normalized`.
+fn decode_name_range<const N: usize>(slice: &[u8], options: &ParseOptions) -> String<N> {
+    let mut out = String::new();
+    for &b in slice {
+        let _ = out.push(class_normalized_char(b, CharClass::Letter, options) as char);
+    }
+    out
+}
+
 fn decode_range<const N: usize>(slice: &[u8]) -> String<N> {
     let mut out = String::new();
     for &b in slice {
@@ -128,7 +681,7 @@ fn decode_mrz_td_name<const N: usize>(raw: &str) -> String<N> {
     full_name
 }
 
-/// Detects the MRZ format (e.g., TD1, TD3) based on the provided lines.
+/// Detects the MRZ format (e.g., TD1, TD2, TD3) based on the provided lines.
 /// Returns `MRZFormat::Unknown` if the format cannot be determined.
 pub fn detect_format(lines: &[&[u8]]) -> MRZFormat {
     if lines.len() == 2
@@ -137,13 +690,48 @@ pub fn detect_format(lines: &[&[u8]]) -> MRZFormat {
         && lines[1].len() >= 40
     {
         MRZFormat::MrzIcaoTd3 {}
+    } else if lines.len() == 2
+        && lines[0].starts_with(b"V")
+        && lines[0].len() >= 40
+        && lines[1].len() >= 40
+    {
+        MRZFormat::MrzIcaoMrvA
     } else if lines.len() == 3
         && lines[0].len() == 30
         && lines[1].len() == 30
         && lines[2].len() == 30
     {
         MRZFormat::MrzIcaoTd1
-    } else if lines.len() == 1 && lines[0].starts_with(b"M1") {
+    } else if lines.len() == 3
+        && lines[0].len() == CH_DL_LINE1_LEN
+        && lines[1].starts_with(CH_DL_DOC_CODE)
+        && lines[1].len() == CH_DL_LINE_LEN
+        && lines[2].len() == CH_DL_LINE_LEN
+    {
+        MRZFormat::MrzSwissDrivingLicence
+    } else if lines.len() == 2
+        && lines[0].starts_with(b"IDFRA")
+        && lines[0].len() == 36
+        && lines[1].len() == 36
+    {
+        MRZFormat::MrzFraId
+    } else if lines.len() == 2
+        && lines[0].starts_with(b"V")
+        && lines[0].len() == 36
+        && lines[1].len() == 36
+    {
+        MRZFormat::MrzIcaoMrvB
+    } else if lines.len() == 2 && lines[0].len() == 36 && lines[1].len() == 36 {
+        MRZFormat::MrzIcaoTd2
+    } else if lines.len() == 1
+        && (lines[0].starts_with(b"D1") || lines[0].starts_with(b"D<"))
+        && lines[0].len() == 30
+    {
+        MRZFormat::MrzDrivingLicence
+    } else if lines.len() == 1
+        && lines[0].starts_with(b"M")
+        && matches!(lines[0].get(1), Some(b'1'..=b'4'))
+    {
         MRZFormat::BCBP
     } else {
         MRZFormat::Unknown
@@ -153,34 +741,271 @@ pub fn detect_format(lines: &[&[u8]]) -> MRZFormat {
 /// Parses any supported MRZ format from the provided lines.
 /// Returns an error if the format is unknown or the lines are malformed.
 pub fn parse_any(lines: &[&[u8]]) -> Result<ParsedMRZ, MRZParseError> {
+    parse_any_with(lines, &ParseOptions::default())
+}
+
+/// Parses any supported MRZ format from the provided lines using the given options.
+/// Returns an error if the format is unknown or the lines are malformed.
+///
+/// With [`ParseOptions::normalize_input`], the lines are first canonicalized by
+/// [`crate::normalize::normalize_lines`]; they must then be valid UTF-8. With
+/// [`ParseOptions::reorder_lines`], they are then arranged in the order found by
+/// [`crate::line_order::detect_line_order`], and with [`ParseOptions::realign_lines`],
+/// lines of the ICAO layouts are realigned by [`crate::realign::realign_with`]. Use
+/// [`parse_any_report`] to learn which changes were made.
+pub fn parse_any_with(lines: &[&[u8]], options: &ParseOptions) -> Result<ParsedMRZ, MRZParseError> {
+    if options.normalize_input {
+        let normalized = normalize_bytes(lines)?;
+        let options = ParseOptions {
+            normalize_input: false,
+            ..*options
+        };
+        return parse_any_with(&normalized.line_bytes(), &options);
+    }
+    if options.reorder_lines {
+        let options = ParseOptions {
+            reorder_lines: false,
+            ..*options
+        };
+        let order = detect_line_order(lines);
+        if order.is_identity() {
+            return parse_any_with(lines, &options);
+        }
+        let mut document = parse_any_with(&order.apply(lines), &options)?;
+        document.set_line_order(order);
+        return Ok(document);
+    }
+    if options.realign_lines {
+        let options = ParseOptions {
+            realign_lines: false,
+            ..*options
+        };
+        return match realign_with(lines, &options) {
+            Some(realigned) => parse_any_with(&realigned.line_bytes(), &options),
+            None => parse_any_with(lines, &options),
+        };
+    }
+
+    parse_detected(lines, options, None)
+}
+
+/// Parses MRZ lines read with per-character confidences, see [`ScoredLine`].
+///
+/// OCR correction only substitutes characters read with less than
+/// [`ParseOptions::confidence_threshold`], trying the least certain characters and the
+/// engine's alternative glyphs first; see [`crate::field_correction::correct_scored_field`].
+/// Lines are reordered under [`ParseOptions::reorder_lines`], but neither normalized nor
+/// realigned, as that would separate characters from their confidences.
+pub fn parse_scored(
+    lines: &[ScoredLine<'_>],
+    options: &ParseOptions,
+) -> Result<ParsedMRZ, MRZParseError> {
+    let mut scored: heapless::Vec<ScoredLine<'_>, 3> = heapless::Vec::new();
+    scored.extend_from_slice(lines).map_err(|_| {
+        let surplus = scored.capacity();
+        MRZParseError::InvalidLength(Some(ErrorPosition::new(
+            surplus,
+            0,
+            lines[surplus].text.len(),
+        )))
+    })?;
+    if options.reorder_lines {
+        let texts: heapless::Vec<&[u8], 3> = scored.iter().map(|line| line.text).collect();
+        let order = detect_line_order(&texts);
+        if !order.is_identity() {
+            scored = order
+                .as_slice()
+                .iter()
+                .map(|&index| lines[usize::from(index)])
+                .collect();
+            let texts: heapless::Vec<&[u8], 3> = scored.iter().map(|line| line.text).collect();
+            let mut document = parse_detected(&texts, options, Some(&scored))?;
+            document.set_line_order(order);
+            return Ok(document);
+        }
+    }
+    let texts: heapless::Vec<&[u8], 3> = scored.iter().map(|line| line.text).collect();
+    parse_detected(&texts, options, Some(&scored))
+}
+
+fn parse_detected(
+    lines: &[&[u8]],
+    options: &ParseOptions,
+    readings: Readings<'_>,
+) -> Result<ParsedMRZ, MRZParseError> {
+    // TD3 and MRV-A lines may carry trailing characters unless strict lengths are requested.
+    let open_ended = !options.strict_lengths;
+
     match detect_format(lines) {
         MRZFormat::MrzIcaoTd3 => {
-            if lines.len() != 2 || lines[0].len() < 44 || lines[1].len() < 44 {
-                return Err(MRZParseError::InvalidLength);
-            }
-            parse_td3(lines[0], lines[1])
+            check_line_lengths(lines, &[44, 44], open_ended)?;
+            parse_td3(lines[0], lines[1], options, readings)
         }
         MRZFormat::MrzIcaoTd1 => {
-            if lines.len() != 3
-                || lines[0].len() != 30
-                || lines[1].len() != 30
-                || lines[2].len() != 30
-            {
-                return Err(MRZParseError::InvalidLength);
-            }
-            parse_td1(lines[0], lines[1], lines[2])
+            check_line_lengths(lines, &[30, 30, 30], false)?;
+            parse_td1(lines[0], lines[1], lines[2], options, readings)
         }
-        MRZFormat::BCBP => {
-            if lines[0].len() < 30 {
-                return Err(MRZParseError::InvalidLength);
-            }
-            return Err(MRZParseError::UnsupportedFormat);
+        MRZFormat::MrzIcaoTd2 => {
+            check_line_lengths(lines, &[36, 36], false)?;
+            parse_td2(lines[0], lines[1], options, readings)
         }
+        MRZFormat::MrzIcaoMrvA => {
+            check_line_lengths(lines, &[44, 44], open_ended)?;
+            parse_mrva(lines[0], lines[1], options, readings)
+        }
+        MRZFormat::MrzIcaoMrvB => {
+            check_line_lengths(lines, &[36, 36], false)?;
+            parse_mrvb(lines[0], lines[1], options, readings)
+        }
+        MRZFormat::MrzFraId => {
+            check_line_lengths(lines, &[36, 36], false)?;
+            parse_fra_id(lines[0], lines[1], options, readings)
+        }
+        MRZFormat::MrzDrivingLicence => {
+            check_line_lengths(lines, &[30], false)?;
+            parse_driving_licence(lines[0], options, readings)
+        }
+        MRZFormat::MrzSwissDrivingLicence => {
+            check_line_lengths(
+                lines,
+                &[CH_DL_LINE1_LEN, CH_DL_LINE_LEN, CH_DL_LINE_LEN],
+                false,
+            )?;
+            parse_swiss_driving_licence(lines[0], lines[1], lines[2])
+        }
+        MRZFormat::BCBP => parse_bcbp(lines[0]).map(|bcbp| ParsedMRZ::Bcbp(store_bcbp(bcbp))),
         MRZFormat::Unknown => Err(MRZParseError::UnknownFormat),
     }
 }
 
-fn parse_td3(line1: &[u8], line2: &[u8]) -> Result<ParsedMRZ, MRZParseError> {
+// Boxes the boarding pass under `alloc`, see [`crate::BcbpStorage`].
+#[cfg(feature = "alloc")]
+fn store_bcbp(bcbp: crate::bcbp::Bcbp) -> crate::BcbpStorage {
+    alloc::boxed::Box::new(bcbp)
+}
+
+#[cfg(not(feature = "alloc"))]
+fn store_bcbp(bcbp: crate::bcbp::Bcbp) -> crate::BcbpStorage {
+    bcbp
+}
+
+fn normalize_bytes(lines: &[&[u8]]) -> Result<NormalizedInput, MRZParseError> {
+    let mut text: heapless::Vec<&str, MAX_NORMALIZED_LINES> = heapless::Vec::new();
+    for (index, line) in lines.iter().enumerate() {
+        let line = core::str::from_utf8(line).map_err(|e| {
+            MRZParseError::Utf8Error(Some(ErrorPosition::at(index, e.valid_up_to())))
+        })?;
+        text.push(line).map_err(|_| {
+            MRZParseError::InvalidLength(Some(ErrorPosition::new(index, 0, line.len())))
+        })?;
+    }
+    normalize_lines(text)
+}
+
+// Checks the line count and line lengths of a format. Lines of `open_ended`
+// formats may be longer than expected. A wrong length is reported with the
+// missing or surplus columns of the first offending line, and a wrong line
+// count with the first missing or surplus line.
+fn check_line_lengths(
+    lines: &[&[u8]],
+    expected: &[usize],
+    open_ended: bool,
+) -> Result<(), MRZParseError> {
+    if lines.len() != expected.len() {
+        let index = lines.len().min(expected.len());
+        let len = lines.get(index).map_or(expected[index], |line| line.len());
+        return Err(MRZParseError::InvalidLength(Some(ErrorPosition::new(
+            index, 0, len,
+        ))));
+    }
+    for (index, (line, &len)) in lines.iter().zip(expected).enumerate() {
+        if line.len() != len && !(open_ended && line.len() > len) {
+            let (start, end) = (line.len().min(len), line.len().max(len));
+            return Err(MRZParseError::InvalidLength(Some(ErrorPosition::new(
+                index, start, end,
+            ))));
+        }
+    }
+    Ok(())
+}
+
+/// Parses any supported MRZ format and reports every problem found, instead of
+/// stopping at the first failed check digit.
+///
+/// Check digit failures are always recorded regardless of `options.checksum_policy`,
+/// so the report carries the best-effort document whenever the layout can be parsed.
+/// Changes made under [`ParseOptions::normalize_input`] are reported in
+/// [`ParseReport::normalization`], along with an [`IssueKind::InputNormalized`] warning.
+/// Lines reordered under [`ParseOptions::reorder_lines`] are reported in
+/// [`ParseReport::line_order`], along with an [`IssueKind::LinesReordered`] warning, and
+/// characters inserted or deleted under [`ParseOptions::realign_lines`] in
+/// [`ParseReport::realignment`], along with an [`IssueKind::LinesRealigned`] warning.
+pub fn parse_any_report(lines: &[&[u8]], options: &ParseOptions) -> ParseReport {
+    if options.normalize_input {
+        let options = ParseOptions {
+            normalize_input: false,
+            ..*options
+        };
+        return match normalize_bytes(lines) {
+            Ok(normalized) => {
+                let mut report = parse_any_report(&normalized.line_bytes(), &options);
+                report.record_normalization(&normalized);
+                report
+            }
+            Err(e) => ParseReport::from_error(&e),
+        };
+    }
+    if options.reorder_lines {
+        let options = ParseOptions {
+            reorder_lines: false,
+            ..*options
+        };
+        let order = detect_line_order(lines);
+        if order.is_identity() {
+            return parse_any_report(lines, &options);
+        }
+        let mut report = parse_any_report(&order.apply(lines), &options);
+        if let Some(document) = &mut report.document {
+            document.set_line_order(order);
+        }
+        report.line_order = Some(order);
+        report.push(ParseIssue::warning(
+            MrzField::Layout,
+            IssueKind::LinesReordered,
+        ));
+        return report;
+    }
+    if options.realign_lines {
+        let options = ParseOptions {
+            realign_lines: false,
+            ..*options
+        };
+        return match realign_with(lines, &options) {
+            Some(realigned) => {
+                let mut report = parse_any_report(&realigned.line_bytes(), &options);
+                report.record_realignment(&realigned);
+                report
+            }
+            None => parse_any_report(lines, &options),
+        };
+    }
+
+    let options = ParseOptions {
+        checksum_policy: ChecksumPolicy::Record,
+        ..*options
+    };
+    match parse_any_with(lines, &options) {
+        Ok(document) => ParseReport::from_document(document),
+        Err(e) => ParseReport::from_error(&e),
+    }
+}
+
+fn parse_td3(
+    line1: &[u8],
+    line2: &[u8],
+    options: &ParseOptions,
+    readings: Readings<'_>,
+) -> Result<ParsedMRZ, MRZParseError> {
     const DOC_NUM_START: usize = 0;
     const DOC_NUM_END: usize = 9;
     const DOC_NUM_CHECK: usize = 9;
@@ -193,77 +1018,103 @@ fn parse_td3(line1: &[u8], line2: &[u8]) -> Result<ParsedMRZ, MRZParseError> {
     const EXPIRY_DATE_END: usize = 27;
     const EXPIRY_DATE_CHECK: usize = 27;
 
+    const OPTIONAL1_START: usize = 28;
+    const OPTIONAL1_END: usize = 43;
+
     const FINAL_CHECK_POS: usize = 43;
 
+    const NATIONALITY_START: usize = 10;
+    const NATIONALITY_END: usize = 13;
+
+    const SEX_POS: usize = 20;
+
     const NAME_START: usize = 5;
     const NAME_END: usize = 44;
 
-    let document_number: CheckedField<String<ICAO_COMMON_DOC_NUM_MAX_LEN>> =
-        parse_correctable_checked_field::<
-            ICAO_COMMON_DOC_NUM_MAX_LEN,
-            String<ICAO_COMMON_DOC_NUM_MAX_LEN>,
-        >(
-            core::str::from_utf8(&line2[DOC_NUM_START..DOC_NUM_END])
-                .map_err(|_| MRZParseError::Utf8Error)?,
-            line2[DOC_NUM_CHECK] as char,
-            MRZChecksumError::DocumentNumber,
-        )?;
+    let document_number = parse_document_number(
+        line2,
+        FieldLocation::new(
+            1,
+            DOC_NUM_START,
+            DOC_NUM_END,
+            DOC_NUM_CHECK,
+            CharClass::Alphanumeric,
+        ),
+        options,
+        readings,
+    )?;
+    let optional_data1 =
+        decode_range::<ICAO_TD3_OPTIONAL1_MAX_LEN>(&line2[OPTIONAL1_START..OPTIONAL1_END]);
 
     let birth_date = parse_checked_field_with_correction::<ICAO_COMMON_DATE_LEN>(
         line2,
-        BIRTH_DATE_START,
-        BIRTH_DATE_END,
-        BIRTH_DATE_CHECK,
+        FieldLocation::new(
+            1,
+            BIRTH_DATE_START,
+            BIRTH_DATE_END,
+            BIRTH_DATE_CHECK,
+            CharClass::Digit,
+        ),
         MRZChecksumError::BirthDate,
+        options,
+        readings,
     )?;
 
     let expiry_date = parse_checked_field_with_correction::<ICAO_COMMON_DATE_LEN>(
         line2,
-        EXPIRY_DATE_START,
-        EXPIRY_DATE_END,
-        EXPIRY_DATE_CHECK,
+        FieldLocation::new(
+            1,
+            EXPIRY_DATE_START,
+            EXPIRY_DATE_END,
+            EXPIRY_DATE_CHECK,
+            CharClass::Digit,
+        ),
         MRZChecksumError::ExpiryDate,
+        options,
+        readings,
     )?;
 
     let final_check_char = line2.get(FINAL_CHECK_POS).copied().unwrap_or(b'<');
-    let final_check = if final_check_char == b'<' {
-        None
-    } else {
-        let docnum_with_check = document_number.as_slice_with_check();
-        let birth_date_with_check = birth_date.as_slice_with_check();
-        let expiry_date_with_check = expiry_date.as_slice_with_check();
-
-        let segments = &[
-            &docnum_with_check,
-            &birth_date_with_check,
-            &expiry_date_with_check,
-            &line2[EXPIRY_DATE_CHECK + 1..=FINAL_CHECK_POS - 1],
-        ];
-        Some(CheckedField::new(
-            (),
-            match validate_final_check(segments, final_check_char, MRZChecksumError::Final) {
-                Ok(Some(true)) => None,
-                Ok(Some(false)) => Some(MRZChecksumError::Final),
-                Ok(None) => None,
-                Err(e) => return Err(e),
-            },
-            final_check_char,
-        ))
+    let mut composite = Composite {
+        format: MRZFormat::MrzIcaoTd3,
+        document_number,
+        birth_date,
+        expiry_date,
+        optional_data: [optional_data1, String::new()],
+        optional_corrections: [None, None],
     };
+    composite.correct(final_check_char, options);
+    let final_check = final_check_field(
+        &[&composite.encode().0],
+        final_check_char,
+        ErrorPosition::at(1, FINAL_CHECK_POS),
+        options,
+    )?;
+    let Composite {
+        document_number,
+        birth_date,
+        expiry_date,
+        optional_data: [optional_data1, _],
+        optional_corrections,
+        ..
+    } = composite;
 
-    let raw_name = decode_range::<ICAO_TD3_NAME_MAX_LEN>(&line1[NAME_START..NAME_END]);
+    let raw_name =
+        decode_name_range::<ICAO_TD3_NAME_MAX_LEN>(&line1[NAME_START..NAME_END], options);
     let name = decode_mrz_td_name::<ICAO_TD3_NAME_MAX_LEN>(&raw_name);
 
-    let optional_data1 =
-        decode_range::<ICAO_TD3_OPTIONAL1_MAX_LEN>(&line2[28..43.min(line2.len())]);
     let optional_data2 =
         decode_range::<ICAO_TD3_OPTIONAL2_MAX_LEN>(&line1[28..43.min(line1.len())]);
-    let sex = line2.get(20).copied().unwrap_or(b'<');
+    let sex = class_normalized_char(line2[SEX_POS], CharClass::Sex, options);
 
     Ok(ParsedMRZ::MrzIcaoTd3(MrzIcaoTd3 {
         document_code: fixed_slice::<2>(&line1[0..2]),
-        issuing_state: fixed_slice::<3>(&line1[2..5]),
-        nationality: fixed_slice::<3>(&line2[15..18]),
+        issuing_state: class_normalized::<3>(&line1[2..5], CharClass::Letter, options),
+        nationality: class_normalized::<3>(
+            &line2[NATIONALITY_START..NATIONALITY_END],
+            CharClass::Letter,
+            options,
+        ),
         name,
         document_number,
         birth_date,
@@ -272,11 +1123,19 @@ fn parse_td3(line1: &[u8], line2: &[u8]) -> Result<ParsedMRZ, MRZParseError> {
         sex,
         optional_data1: optional_data1.clone(),
         optional_data2: optional_data2.clone(),
+        line_order: None,
+        optional_data_corrections: optional_corrections,
     }))
 }
 
 // Parse ICAO TD1 MRZ format
-fn parse_td1(line1: &[u8], line2: &[u8], line3: &[u8]) -> Result<ParsedMRZ, MRZParseError> {
+fn parse_td1(
+    line1: &[u8],
+    line2: &[u8],
+    line3: &[u8],
+    options: &ParseOptions,
+    readings: Readings<'_>,
+) -> Result<ParsedMRZ, MRZParseError> {
     use crate::{MrzIcaoTd1, ICAO_TD1_NAME_MAX_LEN};
 
     const DOC_CODE_START: usize = 0;
@@ -315,76 +1174,95 @@ fn parse_td1(line1: &[u8], line2: &[u8], line3: &[u8]) -> Result<ParsedMRZ, MRZP
     const NAME_END: usize = 30;
 
     let document_code = fixed_slice::<DOC_CODE_LEN>(&line1[DOC_CODE_START..DOC_CODE_END]);
-    let issuing_state =
-        fixed_slice::<ICAO_COMMON_COUNTRY_CODE_LEN>(&line1[ISSUER_START..ISSUER_END]);
-
-    let document_number: CheckedField<String<ICAO_COMMON_DOC_NUM_MAX_LEN>> =
-        parse_correctable_checked_field::<
-            ICAO_COMMON_DOC_NUM_MAX_LEN,
-            String<ICAO_COMMON_DOC_NUM_MAX_LEN>,
-        >(
-            core::str::from_utf8(&line1[DOC_NUM_START..DOC_NUM_END])
-                .map_err(|_| MRZParseError::Utf8Error)?,
-            line1[DOC_NUM_CHECK] as char,
-            MRZChecksumError::DocumentNumber,
-        )?;
+    let issuing_state = class_normalized::<ICAO_COMMON_COUNTRY_CODE_LEN>(
+        &line1[ISSUER_START..ISSUER_END],
+        CharClass::Letter,
+        options,
+    );
 
-    let optional_data1 =
-        decode_range::<ICAO_TD1_OPTIONAL1_MAX_LEN>(&line1[OPTIONAL1_START..OPTIONAL1_END]);
+    let (document_number, optional_data1) = parse_extended_document_number(
+        line1,
+        FieldLocation::new(
+            0,
+            DOC_NUM_START,
+            DOC_NUM_END,
+            DOC_NUM_CHECK,
+            CharClass::Alphanumeric,
+        ),
+        OPTIONAL1_START,
+        OPTIONAL1_END,
+        options,
+        readings,
+    )?;
+    let optional_data1 = decode_range::<ICAO_TD1_OPTIONAL1_MAX_LEN>(optional_data1);
 
-    let nationality =
-        fixed_slice::<ICAO_COMMON_COUNTRY_CODE_LEN>(&line2[NATIONALITY_START..NATIONALITY_END]);
+    let nationality = class_normalized::<ICAO_COMMON_COUNTRY_CODE_LEN>(
+        &line2[NATIONALITY_START..NATIONALITY_END],
+        CharClass::Letter,
+        options,
+    );
 
     let birth_date = parse_checked_field_with_correction::<ICAO_COMMON_DATE_LEN>(
         line2,
-        BIRTH_DATE_START,
-        BIRTH_DATE_END,
-        BIRTH_DATE_CHECK,
+        FieldLocation::new(
+            1,
+            BIRTH_DATE_START,
+            BIRTH_DATE_END,
+            BIRTH_DATE_CHECK,
+            CharClass::Digit,
+        ),
         MRZChecksumError::BirthDate,
+        options,
+        readings,
     )?;
 
-    let sex = line2[SEX_POS];
+    let sex = class_normalized_char(line2[SEX_POS], CharClass::Sex, options);
 
     let expiry_date = parse_checked_field_with_correction::<ICAO_COMMON_DATE_LEN>(
         line2,
-        EXPIRY_DATE_START,
-        EXPIRY_DATE_END,
-        EXPIRY_DATE_CHECK,
+        FieldLocation::new(
+            1,
+            EXPIRY_DATE_START,
+            EXPIRY_DATE_END,
+            EXPIRY_DATE_CHECK,
+            CharClass::Digit,
+        ),
         MRZChecksumError::ExpiryDate,
+        options,
+        readings,
     )?;
 
-    dbg!(&expiry_date.error());
-
     let optional_data2 =
         decode_range::<ICAO_TD1_OPTIONAL2_MAX_LEN>(&line2[OPTIONAL2_START..OPTIONAL2_END]);
 
     let final_check_char = line2.get(FINAL_CHECK_POS).copied().unwrap_or(b'<');
-    let final_check = if final_check_char == b'<' {
-        None
-    } else {
-        let docnum_with_check = document_number.as_slice_with_check();
-        let birth_date_with_check = birth_date.as_slice_with_check();
-        let expiry_date_with_check = expiry_date.as_slice_with_check();
-
-        let segments = &[
-            &docnum_with_check,
-            &birth_date_with_check,
-            &expiry_date_with_check,
-            &line2[OPTIONAL2_START..FINAL_CHECK_POS],
-        ];
-        Some(CheckedField::new(
-            (),
-            match validate_final_check(segments, final_check_char, MRZChecksumError::Final) {
-                Ok(Some(true)) => None,
-                Ok(Some(false)) => Some(MRZChecksumError::Final),
-                Ok(None) => None,
-                Err(e) => return Err(e),
-            },
-            final_check_char,
-        ))
+    let mut composite = Composite {
+        format: MRZFormat::MrzIcaoTd1,
+        document_number,
+        birth_date,
+        expiry_date,
+        optional_data: [optional_data1, decode_range(optional_data2.as_bytes())],
+        optional_corrections: [None, None],
     };
+    composite.correct(final_check_char, options);
+    let final_check = final_check_field(
+        &[&composite.encode().0],
+        final_check_char,
+        ErrorPosition::at(1, FINAL_CHECK_POS),
+        options,
+    )?;
+    let Composite {
+        document_number,
+        birth_date,
+        expiry_date,
+        optional_data: [optional_data1, optional_data2],
+        optional_corrections,
+        ..
+    } = composite;
+    let optional_data2 = decode_range::<ICAO_TD1_OPTIONAL2_MAX_LEN>(optional_data2.as_bytes());
 
-    let raw_name = decode_range::<ICAO_TD1_NAME_MAX_LEN>(&line3[NAME_START..NAME_END]);
+    let raw_name =
+        decode_name_range::<ICAO_TD1_NAME_MAX_LEN>(&line3[NAME_START..NAME_END], options);
     let full_name = decode_mrz_td_name::<ICAO_TD1_NAME_MAX_LEN>(&raw_name);
 
     Ok(ParsedMRZ::MrzIcaoTd1(MrzIcaoTd1 {
@@ -394,6 +1272,8 @@ fn parse_td1(line1: &[u8], line2: &[u8], line3: &[u8]) -> Result<ParsedMRZ, MRZP
         nationality,
         optional_data1: optional_data1.clone(),
         optional_data2: optional_data2.clone(),
+        line_order: None,
+        optional_data_corrections: optional_corrections,
         document_number,
         birth_date,
         expiry_date,
@@ -401,3 +1281,509 @@ fn parse_td1(line1: &[u8], line2: &[u8], line3: &[u8]) -> Result<ParsedMRZ, MRZP
         sex,
     }))
 }
+
+// Parse ICAO TD2 MRZ format
+fn parse_td2(
+    line1: &[u8],
+    line2: &[u8],
+    options: &ParseOptions,
+    readings: Readings<'_>,
+) -> Result<ParsedMRZ, MRZParseError> {
+    use crate::{MrzIcaoTd2, ICAO_TD2_NAME_MAX_LEN, ICAO_TD2_OPTIONAL1_MAX_LEN};
+
+    const DOC_CODE_START: usize = 0;
+    const DOC_CODE_END: usize = 2;
+    const DOC_CODE_LEN: usize = 2;
+
+    const ISSUER_START: usize = 2;
+    const ISSUER_END: usize = 5;
+
+    const NAME_START: usize = 5;
+    const NAME_END: usize = 36;
+
+    const DOC_NUM_START: usize = 0;
+    const DOC_NUM_END: usize = 9;
+    const DOC_NUM_CHECK: usize = 9;
+
+    const NATIONALITY_START: usize = 10;
+    const NATIONALITY_END: usize = 13;
+
+    const BIRTH_DATE_START: usize = 13;
+    const BIRTH_DATE_END: usize = 19;
+    const BIRTH_DATE_CHECK: usize = 19;
+
+    const SEX_POS: usize = 20;
+
+    const EXPIRY_DATE_START: usize = 21;
+    const EXPIRY_DATE_END: usize = 27;
+    const EXPIRY_DATE_CHECK: usize = 27;
+
+    const OPTIONAL1_START: usize = 28;
+    const OPTIONAL1_END: usize = 35;
+
+    const FINAL_CHECK_POS: usize = 35;
+
+    let document_code = fixed_slice::<DOC_CODE_LEN>(&line1[DOC_CODE_START..DOC_CODE_END]);
+    let issuing_state = class_normalized::<ICAO_COMMON_COUNTRY_CODE_LEN>(
+        &line1[ISSUER_START..ISSUER_END],
+        CharClass::Letter,
+        options,
+    );
+
+    let (document_number, optional_data1) = parse_extended_document_number(
+        line2,
+        FieldLocation::new(
+            1,
+            DOC_NUM_START,
+            DOC_NUM_END,
+            DOC_NUM_CHECK,
+            CharClass::Alphanumeric,
+        ),
+        OPTIONAL1_START,
+        OPTIONAL1_END,
+        options,
+        readings,
+    )?;
+    let optional_data1 = decode_range::<ICAO_TD2_OPTIONAL1_MAX_LEN>(optional_data1);
+
+    let nationality = class_normalized::<ICAO_COMMON_COUNTRY_CODE_LEN>(
+        &line2[NATIONALITY_START..NATIONALITY_END],
+        CharClass::Letter,
+        options,
+    );
+
+    let birth_date = parse_checked_field_with_correction::<ICAO_COMMON_DATE_LEN>(
+        line2,
+        FieldLocation::new(
+            1,
+            BIRTH_DATE_START,
+            BIRTH_DATE_END,
+            BIRTH_DATE_CHECK,
+            CharClass::Digit,
+        ),
+        MRZChecksumError::BirthDate,
+        options,
+        readings,
+    )?;
+
+    let sex = class_normalized_char(line2[SEX_POS], CharClass::Sex, options);
+
+    let expiry_date = parse_checked_field_with_correction::<ICAO_COMMON_DATE_LEN>(
+        line2,
+        FieldLocation::new(
+            1,
+            EXPIRY_DATE_START,
+            EXPIRY_DATE_END,
+            EXPIRY_DATE_CHECK,
+            CharClass::Digit,
+        ),
+        MRZChecksumError::ExpiryDate,
+        options,
+        readings,
+    )?;
+
+    let final_check_char = line2.get(FINAL_CHECK_POS).copied().unwrap_or(b'<');
+    let (docnum_with_check, optional_area) =
+        encode_document_number(&document_number, &optional_data1);
+    let birth_date_with_check = birth_date.as_slice_with_check();
+    let expiry_date_with_check = expiry_date.as_slice_with_check();
+
+    let segments = &[
+        &docnum_with_check[..],
+        &birth_date_with_check,
+        &expiry_date_with_check,
+        &optional_area,
+    ];
+    let final_check = final_check_field(
+        segments,
+        final_check_char,
+        ErrorPosition::at(1, FINAL_CHECK_POS),
+        options,
+    )?;
+
+    let raw_name =
+        decode_name_range::<ICAO_TD2_NAME_MAX_LEN>(&line1[NAME_START..NAME_END], options);
+    let full_name = decode_mrz_td_name::<ICAO_TD2_NAME_MAX_LEN>(&raw_name);
+
+    Ok(ParsedMRZ::MrzIcaoTd2(MrzIcaoTd2 {
+        document_code,
+        issuing_state,
+        name: full_name,
+        nationality,
+        optional_data1,
+        optional_data2: String::new(),
+        line_order: None,
+        optional_data_corrections: [None, None],
+        document_number,
+        birth_date,
+        expiry_date,
+        final_check,
+        sex,
+    }))
+}
+
+// Parse ICAO MRV-A (2x44 visa) MRZ format
+fn parse_mrva(
+    line1: &[u8],
+    line2: &[u8],
+    options: &ParseOptions,
+    readings: Readings<'_>,
+) -> Result<ParsedMRZ, MRZParseError> {
+    use crate::{ICAO_MRVA_NAME_MAX_LEN, ICAO_MRVA_OPTIONAL1_MAX_LEN};
+
+    parse_mrv::<ICAO_MRVA_NAME_MAX_LEN, ICAO_MRVA_OPTIONAL1_MAX_LEN>(
+        line1, line2, options, readings,
+    )
+    .map(ParsedMRZ::MrzIcaoMrvA)
+}
+
+// Parse ICAO MRV-B (2x36 visa) MRZ format
+fn parse_mrvb(
+    line1: &[u8],
+    line2: &[u8],
+    options: &ParseOptions,
+    readings: Readings<'_>,
+) -> Result<ParsedMRZ, MRZParseError> {
+    use crate::{ICAO_MRVB_NAME_MAX_LEN, ICAO_MRVB_OPTIONAL1_MAX_LEN};
+
+    parse_mrv::<ICAO_MRVB_NAME_MAX_LEN, ICAO_MRVB_OPTIONAL1_MAX_LEN>(
+        line1, line2, options, readings,
+    )
+    .map(ParsedMRZ::MrzIcaoMrvB)
+}
+
+// Shared layout of MRV-A and MRV-B: both visas use the same field offsets and
+// differ only in line length, which widens the name and optional data fields.
+fn parse_mrv<const NAME_LEN: usize, const OPT1_LEN: usize>(
+    line1: &[u8],
+    line2: &[u8],
+    options: &ParseOptions,
+    readings: Readings<'_>,
+) -> Result<MrzIcao<NAME_LEN, OPT1_LEN, 0>, MRZParseError> {
+    const DOC_CODE_START: usize = 0;
+    const DOC_CODE_END: usize = 2;
+    const DOC_CODE_LEN: usize = 2;
+
+    const ISSUER_START: usize = 2;
+    const ISSUER_END: usize = 5;
+
+    const NAME_START: usize = 5;
+    let name_end = NAME_START + NAME_LEN;
+
+    const DOC_NUM_START: usize = 0;
+    const DOC_NUM_END: usize = 9;
+    const DOC_NUM_CHECK: usize = 9;
+
+    const NATIONALITY_START: usize = 10;
+    const NATIONALITY_END: usize = 13;
+
+    const BIRTH_DATE_START: usize = 13;
+    const BIRTH_DATE_END: usize = 19;
+    const BIRTH_DATE_CHECK: usize = 19;
+
+    const SEX_POS: usize = 20;
+
+    const EXPIRY_DATE_START: usize = 21;
+    const EXPIRY_DATE_END: usize = 27;
+    const EXPIRY_DATE_CHECK: usize = 27;
+
+    const OPTIONAL1_START: usize = 28;
+    let optional1_end = OPTIONAL1_START + OPT1_LEN;
+
+    let document_code = fixed_slice::<DOC_CODE_LEN>(&line1[DOC_CODE_START..DOC_CODE_END]);
+    let issuing_state = class_normalized::<ICAO_COMMON_COUNTRY_CODE_LEN>(
+        &line1[ISSUER_START..ISSUER_END],
+        CharClass::Letter,
+        options,
+    );
+
+    let doc_num_location = FieldLocation::new(
+        1,
+        DOC_NUM_START,
+        DOC_NUM_END,
+        DOC_NUM_CHECK,
+        CharClass::Alphanumeric,
+    );
+    let document_number: CheckedField<String<ICAO_EXTENDED_DOC_NUM_MAX_LEN>> =
+        parse_correctable_checked_field::<
+            ICAO_EXTENDED_DOC_NUM_MAX_LEN,
+            String<ICAO_EXTENDED_DOC_NUM_MAX_LEN>,
+        >(
+            decode_field(&line2[DOC_NUM_START..DOC_NUM_END], &doc_num_location)?,
+            line2[DOC_NUM_CHECK] as char,
+            MRZChecksumError::DocumentNumber,
+            doc_num_location,
+            options,
+            readings,
+        )?;
+
+    let nationality = class_normalized::<ICAO_COMMON_COUNTRY_CODE_LEN>(
+        &line2[NATIONALITY_START..NATIONALITY_END],
+        CharClass::Letter,
+        options,
+    );
+
+    let birth_date = parse_checked_field_with_correction::<ICAO_COMMON_DATE_LEN>(
+        line2,
+        FieldLocation::new(
+            1,
+            BIRTH_DATE_START,
+            BIRTH_DATE_END,
+            BIRTH_DATE_CHECK,
+            CharClass::Digit,
+        ),
+        MRZChecksumError::BirthDate,
+        options,
+        readings,
+    )?;
+
+    let sex = class_normalized_char(line2[SEX_POS], CharClass::Sex, options);
+
+    let expiry_date = parse_checked_field_with_correction::<ICAO_COMMON_DATE_LEN>(
+        line2,
+        FieldLocation::new(
+            1,
+            EXPIRY_DATE_START,
+            EXPIRY_DATE_END,
+            EXPIRY_DATE_CHECK,
+            CharClass::Digit,
+        ),
+        MRZChecksumError::ExpiryDate,
+        options,
+        readings,
+    )?;
+
+    // Visas carry no composite check digit; the whole tail is optional data.
+    let optional_data1 = decode_range::<OPT1_LEN>(&line2[OPTIONAL1_START..optional1_end]);
+
+    let raw_name = decode_name_range::<NAME_LEN>(&line1[NAME_START..name_end], options);
+    let full_name = decode_mrz_td_name::<NAME_LEN>(&raw_name);
+
+    Ok(MrzIcao {
+        document_code,
+        issuing_state,
+        name: full_name,
+        nationality,
+        optional_data1,
+        optional_data2: String::new(),
+        line_order: None,
+        optional_data_corrections: [None, None],
+        document_number,
+        birth_date,
+        expiry_date,
+        final_check: None,
+        sex,
+    })
+}
+
+// Parse the legacy French national ID card ("IDFRA", 2x36) format
+fn parse_fra_id(
+    line1: &[u8],
+    line2: &[u8],
+    options: &ParseOptions,
+    readings: Readings<'_>,
+) -> Result<ParsedMRZ, MRZParseError> {
+    use crate::{
+        MrzFraId, FRA_ID_ADMIN_CODE_LEN, FRA_ID_CARD_NUMBER_LEN, FRA_ID_GIVEN_NAMES_MAX_LEN,
+        FRA_ID_SURNAME_MAX_LEN,
+    };
+
+    const DOC_CODE_START: usize = 0;
+    const DOC_CODE_END: usize = 2;
+
+    const ISSUER_START: usize = 2;
+    const ISSUER_END: usize = 5;
+
+    const SURNAME_START: usize = 5;
+    const SURNAME_END: usize = 30;
+
+    const DEPARTMENT_START: usize = 30;
+    const DEPARTMENT_END: usize = 33;
+
+    const OFFICE_START: usize = 33;
+    const OFFICE_END: usize = 36;
+
+    const CARD_NUM_START: usize = 0;
+    const CARD_NUM_END: usize = 12;
+    const CARD_NUM_CHECK: usize = 12;
+
+    const GIVEN_NAMES_START: usize = 13;
+    const GIVEN_NAMES_END: usize = 27;
+
+    const BIRTH_DATE_START: usize = 27;
+    const BIRTH_DATE_END: usize = 33;
+    const BIRTH_DATE_CHECK: usize = 33;
+
+    const SEX_POS: usize = 34;
+
+    const FINAL_CHECK_POS: usize = 35;
+
+    let card_num_location = FieldLocation::new(
+        1,
+        CARD_NUM_START,
+        CARD_NUM_END,
+        CARD_NUM_CHECK,
+        CharClass::Alphanumeric,
+    );
+    let card_number: CheckedField<String<FRA_ID_CARD_NUMBER_LEN>> =
+        parse_correctable_checked_field::<FRA_ID_CARD_NUMBER_LEN, String<FRA_ID_CARD_NUMBER_LEN>>(
+            decode_field(&line2[CARD_NUM_START..CARD_NUM_END], &card_num_location)?,
+            line2[CARD_NUM_CHECK] as char,
+            MRZChecksumError::DocumentNumber,
+            card_num_location,
+            options,
+            readings,
+        )?;
+
+    let birth_date = parse_checked_field_with_correction::<ICAO_COMMON_DATE_LEN>(
+        line2,
+        FieldLocation::new(
+            1,
+            BIRTH_DATE_START,
+            BIRTH_DATE_END,
+            BIRTH_DATE_CHECK,
+            CharClass::Digit,
+        ),
+        MRZChecksumError::BirthDate,
+        options,
+        readings,
+    )?;
+
+    // The composite check digit covers the whole first line and the second line
+    // up to the sex field, using the corrected card number and birth date.
+    let final_check_char = line2[FINAL_CHECK_POS];
+    let card_number_with_check = card_number.as_slice_with_check();
+    let birth_date_with_check = birth_date.as_slice_with_check();
+    let segments = &[
+        line1,
+        &card_number_with_check,
+        &line2[GIVEN_NAMES_START..GIVEN_NAMES_END],
+        &birth_date_with_check,
+        &line2[SEX_POS..FINAL_CHECK_POS],
+    ];
+    let final_check = final_check_field(
+        segments,
+        final_check_char,
+        ErrorPosition::at(1, FINAL_CHECK_POS),
+        options,
+    )?
+    .unwrap_or_else(|| CheckedField::new((), None, b'<'));
+
+    Ok(ParsedMRZ::MrzFraId(MrzFraId {
+        document_code: fixed_slice::<2>(&line1[DOC_CODE_START..DOC_CODE_END]),
+        issuing_state: fixed_slice::<ICAO_COMMON_COUNTRY_CODE_LEN>(
+            &line1[ISSUER_START..ISSUER_END],
+        ),
+        surname: decode_range::<FRA_ID_SURNAME_MAX_LEN>(&line1[SURNAME_START..SURNAME_END]),
+        department: fixed_slice::<FRA_ID_ADMIN_CODE_LEN>(&line1[DEPARTMENT_START..DEPARTMENT_END]),
+        office: fixed_slice::<FRA_ID_ADMIN_CODE_LEN>(&line1[OFFICE_START..OFFICE_END]),
+        card_number,
+        given_names: decode_range::<FRA_ID_GIVEN_NAMES_MAX_LEN>(
+            &line2[GIVEN_NAMES_START..GIVEN_NAMES_END],
+        ),
+        birth_date,
+        sex: line2[SEX_POS],
+        final_check,
+        line_order: None,
+    }))
+}
+
+// Parse the single-line driving licence (1x30) format
+fn parse_driving_licence(
+    line: &[u8],
+    options: &ParseOptions,
+    readings: Readings<'_>,
+) -> Result<ParsedMRZ, MRZParseError> {
+    use crate::{MrzDrivingLicence, DL_LICENCE_NUMBER_LEN};
+
+    const DOC_CODE_START: usize = 0;
+    const DOC_CODE_END: usize = 2;
+
+    const ISSUER_START: usize = 2;
+    const ISSUER_END: usize = 5;
+
+    // The check digit covers the configuration code and the licence number.
+    const CHECKED_START: usize = 5;
+    const CHECKED_END: usize = 29;
+    const CHECKED_LEN: usize = CHECKED_END - CHECKED_START;
+    const CHECK_POS: usize = 29;
+
+    let checked_location = FieldLocation::new(
+        0,
+        CHECKED_START,
+        CHECKED_END,
+        CHECK_POS,
+        CharClass::Alphanumeric,
+    );
+    let checked: CheckedField<String<CHECKED_LEN>> =
+        parse_correctable_checked_field::<CHECKED_LEN, String<CHECKED_LEN>>(
+            decode_field(&line[CHECKED_START..CHECKED_END], &checked_location)?,
+            line[CHECK_POS] as char,
+            MRZChecksumError::DocumentNumber,
+            checked_location,
+            options,
+            readings,
+        )?;
+
+    let configuration = checked.value().as_bytes().first().copied().unwrap_or(b'<');
+    let licence_number = checked.map(|value| {
+        let mut number = String::<DL_LICENCE_NUMBER_LEN>::new();
+        let _ = number.push_str(value.get(1..).unwrap_or(""));
+        number
+    });
+
+    Ok(ParsedMRZ::MrzDrivingLicence(MrzDrivingLicence {
+        document_code: fixed_slice::<2>(&line[DOC_CODE_START..DOC_CODE_END]),
+        issuing_state: fixed_slice::<ICAO_COMMON_COUNTRY_CODE_LEN>(&line[ISSUER_START..ISSUER_END]),
+        configuration,
+        licence_number,
+    }))
+}
+
+// Parse the Swiss driving licence (9 + 30 + 30) format
+fn parse_swiss_driving_licence(
+    line1: &[u8],
+    line2: &[u8],
+    line3: &[u8],
+) -> Result<ParsedMRZ, MRZParseError> {
+    use crate::{
+        MrzSwissDrivingLicence, CH_DL_LICENCE_NUMBER_LEN, CH_DL_PIN_LEN, CH_DL_VERSION_LEN,
+    };
+
+    const LICENCE_NUMBER_START: usize = 0;
+    const LICENCE_NUMBER_END: usize = 6;
+    const LANGUAGE_POS: usize = 6;
+
+    const DOC_CODE_START: usize = 0;
+    const DOC_CODE_END: usize = 2;
+
+    const ISSUER_START: usize = 2;
+    const ISSUER_END: usize = 5;
+
+    const PIN_START: usize = 5;
+    const PIN_END: usize = 14;
+
+    const VERSION_START: usize = 14;
+    const VERSION_END: usize = 17;
+
+    const BIRTH_DATE_START: usize = 17;
+    const BIRTH_DATE_END: usize = 23;
+
+    let licence_number: String<CH_DL_LICENCE_NUMBER_LEN> =
+        decode_range(&line1[LICENCE_NUMBER_START..LICENCE_NUMBER_END]);
+    let pin_code: String<CH_DL_PIN_LEN> = decode_range(&line2[PIN_START..PIN_END]);
+    let name: String<CH_DL_LINE_LEN> = decode_range(line3);
+
+    Ok(ParsedMRZ::MrzSwissDrivingLicence(MrzSwissDrivingLicence {
+        licence_number,
+        language_code: line1[LANGUAGE_POS],
+        document_code: fixed_slice::<2>(&line2[DOC_CODE_START..DOC_CODE_END]),
+        issuing_state: fixed_slice::<ICAO_COMMON_COUNTRY_CODE_LEN>(
+            &line2[ISSUER_START..ISSUER_END],
+        ),
+        pin_code,
+        version: fixed_slice::<CH_DL_VERSION_LEN>(&line2[VERSION_START..VERSION_END]),
+        birth_date: fixed_slice::<ICAO_COMMON_DATE_LEN>(&line2[BIRTH_DATE_START..BIRTH_DATE_END]),
+        name,
+    }))
+}
@@ -0,0 +1,524 @@
+use crate::checksum::compute_checksum;
+use crate::extract::MAX_MRZ_LINE_LEN;
+use crate::ParseOptions;
+use heapless::Vec;
+
+/// Maximum number of characters inserted or deleted in a line carrying check digits.
+pub const MAX_REALIGN_EDITS: usize = 2;
+/// Maximum number of edits recorded in [`Realigned::edits`].
+pub const MAX_LINE_EDITS: usize = 8;
+
+// Failed check digits, then characters that do not fit their field, then insertions
+// away from a filler run; compared in order.
+type Cost = (usize, usize, usize);
+
+/// Whether characters were inserted into or deleted from a line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LineEditKind {
+    /// Characters were inserted before `column`.
+    Inserted,
+    /// Characters were deleted starting at `column`.
+    Deleted,
+}
+
+/// A change made by [`realign`] to one line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LineEdit {
+    /// Index of the line.
+    pub line: usize,
+    /// Column in the original line.
+    pub column: usize,
+    /// Insertion or deletion.
+    pub kind: LineEditKind,
+    /// The inserted or deleted character.
+    pub character: u8,
+    /// Number of characters inserted or deleted.
+    pub count: usize,
+}
+
+/// Lines brought back to the length of their layout by [`realign`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Realigned {
+    /// The realigned lines.
+    pub lines: Vec<Vec<u8, MAX_MRZ_LINE_LEN>, 3>,
+    /// Edits in line order; empty if the lines already had the right lengths.
+    pub edits: Vec<LineEdit, MAX_LINE_EDITS>,
+    /// Number of check digits that still fail after realignment.
+    pub failed_checks: usize,
+}
+
+impl Realigned {
+    /// Returns the lines as byte slices, as expected by [`crate::parser::parse_any`].
+    pub fn line_bytes(&self) -> Vec<&[u8], 3> {
+        self.lines.iter().map(|line| line.as_slice()).collect()
+    }
+}
+
+// A check digit and the line spans it covers, as `(line, start, end)`.
+struct Check {
+    line: usize,
+    segments: &'static [(usize, usize, usize)],
+    column: usize,
+}
+
+// Expected characters of a data line, one class per column:
+// `x` any, `9` digit, `c` check digit or `<`, `a` letter or `<`, `s` sex.
+struct Layout {
+    line_len: usize,
+    patterns: &'static [Option<&'static [u8]>],
+    checks: &'static [Check],
+}
+
+const TD3: Layout = Layout {
+    line_len: 44,
+    patterns: &[None, Some(b"xxxxxxxxxcaaa999999cs999999cxxxxxxxxxxxxxxcc")],
+    checks: &[
+        Check {
+            line: 1,
+            segments: &[(1, 0, 9)],
+            column: 9,
+        },
+        Check {
+            line: 1,
+            segments: &[(1, 13, 19)],
+            column: 19,
+        },
+        Check {
+            line: 1,
+            segments: &[(1, 21, 27)],
+            column: 27,
+        },
+        Check {
+            line: 1,
+            segments: &[(1, 28, 42)],
+            column: 42,
+        },
+        Check {
+            line: 1,
+            segments: &[(1, 0, 10), (1, 13, 20), (1, 21, 43)],
+            column: 43,
+        },
+    ],
+};
+
+const MRVA: Layout = Layout {
+    line_len: 44,
+    patterns: &[None, Some(b"xxxxxxxxxcaaa999999cs999999cxxxxxxxxxxxxxxxx")],
+    checks: &[
+        Check {
+            line: 1,
+            segments: &[(1, 0, 9)],
+            column: 9,
+        },
+        Check {
+            line: 1,
+            segments: &[(1, 13, 19)],
+            column: 19,
+        },
+        Check {
+            line: 1,
+            segments: &[(1, 21, 27)],
+            column: 27,
+        },
+    ],
+};
+
+const TD2: Layout = Layout {
+    line_len: 36,
+    patterns: &[None, Some(b"xxxxxxxxxcaaa999999cs999999cxxxxxxxc")],
+    checks: &[
+        Check {
+            line: 1,
+            segments: &[(1, 0, 9)],
+            column: 9,
+        },
+        Check {
+            line: 1,
+            segments: &[(1, 13, 19)],
+            column: 19,
+        },
+        Check {
+            line: 1,
+            segments: &[(1, 21, 27)],
+            column: 27,
+        },
+        Check {
+            line: 1,
+            segments: &[(1, 0, 10), (1, 13, 20), (1, 21, 35)],
+            column: 35,
+        },
+    ],
+};
+
+const MRVB: Layout = Layout {
+    line_len: 36,
+    patterns: &[None, Some(b"xxxxxxxxxcaaa999999cs999999cxxxxxxxx")],
+    checks: MRVA.checks,
+};
+
+const TD1: Layout = Layout {
+    line_len: 30,
+    patterns: &[
+        Some(b"aaaaaxxxxxxxxxcxxxxxxxxxxxxxxx"),
+        Some(b"999999cs999999caaaxxxxxxxxxxxc"),
+        None,
+    ],
+    checks: &[
+        Check {
+            line: 0,
+            segments: &[(0, 5, 14)],
+            column: 14,
+        },
+        Check {
+            line: 1,
+            segments: &[(1, 0, 6)],
+            column: 6,
+        },
+        Check {
+            line: 1,
+            segments: &[(1, 8, 14)],
+            column: 14,
+        },
+        Check {
+            line: 1,
+            segments: &[(0, 5, 30), (1, 0, 7), (1, 8, 15), (1, 18, 29)],
+            column: 29,
+        },
+    ],
+};
+
+// Insertions and deletions applied to one line, in original columns.
+#[derive(Clone, Copy)]
+struct Edit {
+    column: usize,
+    kind: LineEditKind,
+}
+
+/// Brings TD1, TD2, TD3 and MRV lines with dropped, duplicated or shifted characters
+/// back to the length of their layout.
+///
+/// The layout is chosen from the line count and the length of the lines carrying check
+/// digits, which may be off by up to [`MAX_REALIGN_EDITS`]. In those lines a `<` may be
+/// inserted at any column, and a `<` or a repeated character may be deleted. Lines of
+/// the right length with a failing check digit are also tried with one insertion and one
+/// deletion, which undoes a shifted span, unless a single check digit fails: that is more
+/// likely a misread digit, left to OCR correction. The edits leaving the fewest failed
+/// check digits win, then the fewest characters that do not fit the fixed field offsets
+/// (e.g. a letter in a date), then those inserting next to a filler run or at the end of
+/// the line, where OCR most often drops a `<`. Remaining ties go to the rightmost edits,
+/// which move the fewest characters.
+///
+/// A `<` in place of a check digit only passes if the data it covers is all fillers, so
+/// an edit cannot pass a check by pushing its digit out of place.
+///
+/// Name lines carry no check digits and are padded with `<` or stripped of surplus
+/// characters at the end of the line.
+///
+/// Returns `None` if no layout matches the lines.
+pub fn realign(lines: &[&[u8]]) -> Option<Realigned> {
+    let layout = select_layout(lines)?;
+    let mut realigned = Realigned {
+        lines: Vec::new(),
+        edits: Vec::new(),
+        failed_checks: 0,
+    };
+
+    for (index, line) in lines.iter().enumerate() {
+        let (fixed, edits) = match layout.patterns[index] {
+            Some(pattern) => realign_data_line(line, index, pattern, layout, &realigned.lines)?,
+            None => match pad_or_trim(line, index, layout.line_len) {
+                Some((fixed, edit)) => {
+                    if let Some(edit) = edit {
+                        let _ = realigned.edits.push(edit);
+                    }
+                    realigned.lines.push(fixed).ok()?;
+                    continue;
+                }
+                // Surplus characters inside the line, e.g. a duplicated letter.
+                None => best_edits(line, layout.line_len, |_| (0, 0))?,
+            },
+        };
+        for edit in edits.iter().copied() {
+            let character = match edit.kind {
+                LineEditKind::Inserted => b'<',
+                LineEditKind::Deleted => line[edit.column],
+            };
+            push_edit(&mut realigned.edits, index, edit, character);
+        }
+        realigned.lines.push(fixed).ok()?;
+    }
+    realigned.failed_checks = layout
+        .checks
+        .iter()
+        .filter(|check| !check_passes(check, &realigned.lines, None))
+        .count();
+    Some(realigned)
+}
+
+/// Realigns the lines as [`realign`] does, honouring [`ParseOptions::strict_lengths`]:
+/// when it is set, only edits that keep the length of every line are applied.
+pub fn realign_with(lines: &[&[u8]], options: &ParseOptions) -> Option<Realigned> {
+    realign(lines).filter(|realigned| {
+        !options.strict_lengths
+            || realigned
+                .lines
+                .iter()
+                .zip(lines)
+                .all(|(fixed, line)| fixed.len() == line.len())
+    })
+}
+
+fn select_layout(lines: &[&[u8]]) -> Option<&'static Layout> {
+    let near = |len: usize, target: usize| len.abs_diff(target) <= MAX_REALIGN_EDITS;
+    match lines {
+        // French identity cards share the TD2 size but not its field offsets.
+        [first, _] if first.starts_with(b"IDFRA") => None,
+        [first, second] if near(second.len(), 44) => Some(if first.first() == Some(&b'V') {
+            &MRVA
+        } else {
+            &TD3
+        }),
+        [first, second] if near(second.len(), 36) => Some(if first.first() == Some(&b'V') {
+            &MRVB
+        } else {
+            &TD2
+        }),
+        [first, second, _] if near(first.len(), 30) && near(second.len(), 30) => Some(&TD1),
+        _ => None,
+    }
+}
+
+// Merges consecutive edits of the same kind and character into one.
+fn push_edit(edits: &mut Vec<LineEdit, MAX_LINE_EDITS>, line: usize, edit: Edit, character: u8) {
+    if let Some(last) = edits.last_mut() {
+        let next_column = match edit.kind {
+            LineEditKind::Inserted => last.column,
+            LineEditKind::Deleted => last.column + last.count,
+        };
+        if last.line == line
+            && last.kind == edit.kind
+            && last.character == character
+            && edit.column == next_column
+        {
+            last.count += 1;
+            return;
+        }
+    }
+    let _ = edits.push(LineEdit {
+        line,
+        column: edit.column,
+        kind: edit.kind,
+        character,
+        count: 1,
+    });
+}
+
+// Pads a name line with `<` or strips surplus fillers from its end. Returns `None` if
+// the surplus holds other characters.
+fn pad_or_trim(
+    line: &[u8],
+    index: usize,
+    line_len: usize,
+) -> Option<(Vec<u8, MAX_MRZ_LINE_LEN>, Option<LineEdit>)> {
+    let (fixed, kind, character) = if line.len() <= line_len {
+        let mut fixed: Vec<u8, MAX_MRZ_LINE_LEN> = Vec::from_slice(line).ok()?;
+        fixed.resize(line_len, b'<').ok()?;
+        (fixed, LineEditKind::Inserted, b'<')
+    } else if line[line_len..].iter().all(|&c| c == b'<') {
+        (
+            Vec::from_slice(&line[..line_len]).ok()?,
+            LineEditKind::Deleted,
+            b'<',
+        )
+    } else {
+        return None;
+    };
+    let edit = (line.len() != line_len).then_some(LineEdit {
+        line: index,
+        column: line.len().min(line_len),
+        kind,
+        character,
+        count: line.len().abs_diff(line_len),
+    });
+    Some((fixed, edit))
+}
+
+fn realign_data_line(
+    line: &[u8],
+    index: usize,
+    pattern: &[u8],
+    layout: &Layout,
+    previous: &[Vec<u8, MAX_MRZ_LINE_LEN>],
+) -> Option<(Vec<u8, MAX_MRZ_LINE_LEN>, Vec<Edit, MAX_REALIGN_EDITS>)> {
+    let failing = |candidate: &[u8]| -> Vec<&Check, 5> {
+        layout
+            .checks
+            .iter()
+            .filter(|check| check.line == index)
+            .filter(|check| !check_passes(check, previous, Some((index, candidate))))
+            .collect()
+    };
+    if line.len() == layout.line_len {
+        if let [check] = failing(line).as_slice() {
+            if line[check.column].is_ascii_digit() {
+                return Some((Vec::from_slice(line).ok()?, Vec::new()));
+            }
+        }
+    }
+    best_edits(line, layout.line_len, |candidate| {
+        let failed = failing(candidate).len();
+        let violations = candidate
+            .iter()
+            .zip(pattern)
+            .filter(|(&c, &class)| !fits(c, class))
+            .count();
+        (failed, violations)
+    })
+}
+
+// Searches the insertions and deletions bringing `line` to `target` characters at the
+// lowest cost. A line of the right length is only shifted if that fixes a check digit.
+fn best_edits(
+    line: &[u8],
+    target: usize,
+    cost: impl Fn(&[u8]) -> (usize, usize),
+) -> Option<(Vec<u8, MAX_MRZ_LINE_LEN>, Vec<Edit, MAX_REALIGN_EDITS>)> {
+    let len = line.len();
+    if len.abs_diff(target) > MAX_REALIGN_EDITS {
+        return None;
+    }
+    let anchored = |p: usize| p == len || line[p] == b'<' || (p > 0 && line[p - 1] == b'<');
+    let insertions = 0..=len;
+    let deletions = (0..len).filter(|&p| line[p] == b'<' || (p > 0 && line[p] == line[p - 1]));
+    let insert = |column| Edit {
+        column,
+        kind: LineEditKind::Inserted,
+    };
+    let delete = |column| Edit {
+        column,
+        kind: LineEditKind::Deleted,
+    };
+
+    let mut max_failed = usize::MAX;
+    if len == target {
+        let failed = cost(line).0;
+        if failed == 0 {
+            return Some((Vec::from_slice(line).ok()?, Vec::new()));
+        }
+        max_failed = failed - 1;
+    }
+
+    let mut best: Option<(
+        Cost,
+        Vec<u8, MAX_MRZ_LINE_LEN>,
+        Vec<Edit, MAX_REALIGN_EDITS>,
+    )> = None;
+    // Later candidates move fewer characters and win ties.
+    let mut consider = |edits: &[Edit]| {
+        let Some(candidate) = apply(line, edits) else {
+            return;
+        };
+        let (failed, violations) = cost(&candidate);
+        let unanchored = edits
+            .iter()
+            .filter(|e| e.kind == LineEditKind::Inserted && !anchored(e.column))
+            .count();
+        let candidate_cost = (failed, violations, unanchored);
+        if failed > max_failed {
+            return;
+        }
+        if best
+            .as_ref()
+            .is_none_or(|(best_cost, ..)| candidate_cost <= *best_cost)
+        {
+            best = Some((candidate_cost, candidate, edits.iter().copied().collect()));
+        }
+    };
+
+    if len == target {
+        for i in insertions.clone() {
+            for d in deletions.clone().filter(|&d| d != i && d + 1 != i) {
+                consider(&[insert(i), delete(d)]);
+            }
+        }
+        if best.is_none() {
+            return Some((Vec::from_slice(line).ok()?, Vec::new()));
+        }
+    } else if len + 1 == target {
+        insertions.for_each(|i| consider(&[insert(i)]));
+    } else if len < target {
+        for i in insertions.clone() {
+            for j in insertions.clone().filter(|&j| j >= i) {
+                consider(&[insert(i), insert(j)]);
+            }
+        }
+    } else if len == target + 1 {
+        deletions.for_each(|d| consider(&[delete(d)]));
+    } else {
+        for d in deletions.clone() {
+            for e in deletions.clone().filter(|&e| e > d) {
+                consider(&[delete(d), delete(e)]);
+            }
+        }
+    }
+    best.map(|(_, line, edits)| (line, edits))
+}
+
+// Applies edits given in original columns; insertions go before the column.
+fn apply(line: &[u8], edits: &[Edit]) -> Option<Vec<u8, MAX_MRZ_LINE_LEN>> {
+    let mut out = Vec::new();
+    for column in 0..=line.len() {
+        for edit in edits {
+            if edit.column == column && edit.kind == LineEditKind::Inserted {
+                out.push(b'<').ok()?;
+            }
+        }
+        let deleted = edits
+            .iter()
+            .any(|e| e.column == column && e.kind == LineEditKind::Deleted);
+        if column < line.len() && !deleted {
+            out.push(line[column]).ok()?;
+        }
+    }
+    Some(out)
+}
+
+fn fits(c: u8, class: u8) -> bool {
+    match class {
+        b'9' => c.is_ascii_digit(),
+        b'c' => c.is_ascii_digit() || c == b'<',
+        b'a' => c.is_ascii_uppercase() || c == b'<',
+        b's' => matches!(c, b'M' | b'F' | b'X' | b'<'),
+        _ => c.is_ascii_uppercase() || c.is_ascii_digit() || c == b'<',
+    }
+}
+
+// Evaluates a check digit over the realigned lines, with `candidate` standing in for
+// the line being realigned.
+fn check_passes(
+    check: &Check,
+    lines: &[Vec<u8, MAX_MRZ_LINE_LEN>],
+    candidate: Option<(usize, &[u8])>,
+) -> bool {
+    let line = |index: usize| -> Option<&[u8]> {
+        match candidate {
+            Some((candidate_index, candidate)) if candidate_index == index => Some(candidate),
+            _ => lines.get(index).map(|line| line.as_slice()),
+        }
+    };
+    let mut data: Vec<u8, 64> = Vec::new();
+    for &(index, start, end) in check.segments {
+        let Some(segment) = line(index).and_then(|line| line.get(start..end)) else {
+            return false;
+        };
+        if data.extend_from_slice(segment).is_err() {
+            return false;
+        }
+    }
+    let expected = match line(check.line).and_then(|line| line.get(check.column)) {
+        Some(b'<') => return data.iter().all(|&c| c == b'<'),
+        Some(&c) if c.is_ascii_digit() => c - b'0',
+        _ => return false,
+    };
+    compute_checksum(&data) == Some(expected)
+}
@@ -0,0 +1,385 @@
+use crate::normalize::{Normalization, NormalizedInput, MAX_NORMALIZATION_CHANGES};
+use crate::realign::{LineEdit, Realigned, MAX_LINE_EDITS};
+use crate::{
+    is_calendar_date, CorrectionOutcome, LineOrder, MRZChecksumError, MRZParseError,
+    MrzDrivingLicence, MrzFraId, MrzIcaoCommonFields, MrzSwissDrivingLicence, ParsedMRZ,
+    ICAO_COMMON_DATE_LEN,
+};
+use heapless::Vec;
+
+/// Maximum number of issues kept in a [`ParseReport`]; further issues are dropped.
+pub const MAX_REPORT_ISSUES: usize = 16;
+
+/// MRZ field an issue refers to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MrzField {
+    /// The input as a whole: line count, line lengths or format detection.
+    Layout,
+    /// Document number (or card / licence number).
+    DocumentNumber,
+    /// Date of birth.
+    BirthDate,
+    /// Sex of the holder.
+    Sex,
+    /// Date of expiry.
+    ExpiryDate,
+    /// Composite check digit.
+    Final,
+    /// Document code.
+    DocumentCode,
+    /// Issuing state or organization.
+    IssuingState,
+    /// Name of the holder.
+    Name,
+    /// Nationality of the holder.
+    Nationality,
+    /// Optional data fields.
+    OptionalData,
+}
+
+impl From<MRZChecksumError> for MrzField {
+    fn from(e: MRZChecksumError) -> Self {
+        match e {
+            MRZChecksumError::DocumentNumber => MrzField::DocumentNumber,
+            MRZChecksumError::BirthDate => MrzField::BirthDate,
+            MRZChecksumError::ExpiryDate => MrzField::ExpiryDate,
+            MRZChecksumError::Final => MrzField::Final,
+        }
+    }
+}
+
+/// How serious an issue is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    /// The value is implausible, but the MRZ is structurally sound.
+    Warning,
+    /// A check digit failed or the input could not be parsed.
+    Error,
+}
+
+/// What went wrong with a field.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IssueKind {
+    /// The check digit does not match the field data.
+    ChecksumMismatch,
+    /// The field holds a value outside its allowed range (e.g. month 13, sex `Q`).
+    InvalidValue,
+    /// A character is not allowed at its position.
+    InvalidCharacter,
+    /// The lines do not have the length required by the format.
+    InvalidLength,
+    /// No supported format matches the input.
+    UnknownFormat,
+    /// The format is recognized but not supported.
+    UnsupportedFormat,
+    /// The input is not valid UTF-8.
+    InvalidEncoding,
+    /// A misread check digit, rather than the field data, was repaired by OCR correction.
+    CheckDigitCorrected,
+    /// Several corrections of equal cost satisfy the check digit, so the corrected
+    /// value may be wrong; see [`crate::checked_field::CheckedField::candidates`].
+    AmbiguousCorrection,
+    /// The lines were parsed in a different order than given, see
+    /// [`ParseReport::line_order`].
+    LinesReordered,
+    /// The input was canonicalized before parsing, see [`ParseReport::normalization`].
+    InputNormalized,
+    /// Characters were inserted into or deleted from the lines before parsing, see
+    /// [`ParseReport::realignment`].
+    LinesRealigned,
+    /// The composite check digit rewrote optional data under
+    /// [`crate::ParseOptions::correct_optional_data`], see
+    /// [`MrzIcaoCommonFields::optional_data_correction`].
+    OptionalDataCorrected,
+}
+
+/// A single problem found while parsing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParseIssue {
+    /// Affected field.
+    pub field: MrzField,
+    /// Severity of the problem.
+    pub severity: Severity,
+    /// Kind of problem.
+    pub kind: IssueKind,
+}
+
+impl ParseIssue {
+    /// Creates an error-level issue.
+    pub const fn error(field: MrzField, kind: IssueKind) -> Self {
+        ParseIssue {
+            field,
+            severity: Severity::Error,
+            kind,
+        }
+    }
+
+    /// Creates a warning-level issue.
+    pub const fn warning(field: MrzField, kind: IssueKind) -> Self {
+        ParseIssue {
+            field,
+            severity: Severity::Warning,
+            kind,
+        }
+    }
+}
+
+impl From<&MRZParseError> for ParseIssue {
+    fn from(e: &MRZParseError) -> Self {
+        match e {
+            MRZParseError::InvalidChecksumField(kind, _) => {
+                ParseIssue::error(kind.clone().into(), IssueKind::ChecksumMismatch)
+            }
+            MRZParseError::InvalidCharacter { .. } => {
+                ParseIssue::error(MrzField::Layout, IssueKind::InvalidCharacter)
+            }
+            MRZParseError::InvalidLength(_) => {
+                ParseIssue::error(MrzField::Layout, IssueKind::InvalidLength)
+            }
+            MRZParseError::UnknownFormat => {
+                ParseIssue::error(MrzField::Layout, IssueKind::UnknownFormat)
+            }
+            MRZParseError::UnsupportedFormat => {
+                ParseIssue::error(MrzField::Layout, IssueKind::UnsupportedFormat)
+            }
+            MRZParseError::Utf8Error(_) => {
+                ParseIssue::error(MrzField::Layout, IssueKind::InvalidEncoding)
+            }
+        }
+    }
+}
+
+/// Best-effort parse result together with every issue found.
+///
+/// Unlike [`crate::parser::parse_any`], building a report never stops at the first
+/// problem: check digit failures are recorded and the document is still returned.
+/// `document` is `None` only when the input could not be parsed at all.
+#[derive(Debug)]
+pub struct ParseReport<D = ParsedMRZ> {
+    /// The parsed document, if the layout could be parsed.
+    pub document: Option<D>,
+    /// Issues found, in field order.
+    pub issues: Vec<ParseIssue, MAX_REPORT_ISSUES>,
+    /// The order the lines were parsed in, if it differs from the input order.
+    pub line_order: Option<LineOrder>,
+    /// Changes made by input normalization, see [`crate::ParseOptions::normalize_input`].
+    pub normalization: Vec<Normalization, MAX_NORMALIZATION_CHANGES>,
+    /// `true` if normalization made more changes than `normalization` holds.
+    pub normalization_truncated: bool,
+    /// Characters inserted or deleted by [`crate::ParseOptions::realign_lines`].
+    pub realignment: Vec<LineEdit, MAX_LINE_EDITS>,
+}
+
+impl<D> ParseReport<D> {
+    /// Creates a report for a document without issues.
+    pub fn new(document: Option<D>) -> Self {
+        ParseReport {
+            document,
+            issues: Vec::new(),
+            line_order: None,
+            normalization: Vec::new(),
+            normalization_truncated: false,
+            realignment: Vec::new(),
+        }
+    }
+
+    /// Records the changes made by input normalization, along with an
+    /// [`IssueKind::InputNormalized`] warning if there are any.
+    pub fn record_normalization(&mut self, normalized: &NormalizedInput) {
+        if normalized.is_unchanged() {
+            return;
+        }
+        self.normalization = normalized.changes.clone();
+        self.normalization_truncated = normalized.truncated;
+        self.push(ParseIssue::warning(
+            MrzField::Layout,
+            IssueKind::InputNormalized,
+        ));
+    }
+
+    /// Records the edits made by line realignment, along with an
+    /// [`IssueKind::LinesRealigned`] warning if there are any.
+    pub fn record_realignment(&mut self, realigned: &Realigned) {
+        if realigned.edits.is_empty() {
+            return;
+        }
+        self.realignment = realigned.edits.clone();
+        self.push(ParseIssue::warning(
+            MrzField::Layout,
+            IssueKind::LinesRealigned,
+        ));
+    }
+
+    /// Records an issue. Issues beyond [`MAX_REPORT_ISSUES`] are dropped.
+    pub fn push(&mut self, issue: ParseIssue) {
+        let _ = self.issues.push(issue);
+    }
+
+    /// Returns `true` if no issue was found.
+    pub fn is_clean(&self) -> bool {
+        self.issues.is_empty()
+    }
+
+    /// Returns `true` if at least one error-level issue was found.
+    pub fn has_errors(&self) -> bool {
+        self.issues.iter().any(|i| i.severity == Severity::Error)
+    }
+
+    /// Returns the issues affecting the given field.
+    pub fn issues_for(&self, field: MrzField) -> impl Iterator<Item = &ParseIssue> {
+        self.issues.iter().filter(move |i| i.field == field)
+    }
+
+    /// Converts the document, keeping the issues.
+    pub fn map<E, F: FnOnce(D) -> E>(self, f: F) -> ParseReport<E> {
+        ParseReport {
+            document: self.document.map(f),
+            issues: self.issues,
+            line_order: self.line_order,
+            normalization: self.normalization,
+            normalization_truncated: self.normalization_truncated,
+            realignment: self.realignment,
+        }
+    }
+}
+
+impl ParseReport<ParsedMRZ> {
+    /// Builds a report for an already parsed document by inspecting its fields.
+    pub fn from_document(document: ParsedMRZ) -> Self {
+        let mut report = ParseReport::new(None);
+        match &document {
+            ParsedMRZ::MrzIcaoTd3(mrz) => common_field_issues(mrz, &mut report),
+            ParsedMRZ::MrzIcaoTd1(mrz) => common_field_issues(mrz, &mut report),
+            ParsedMRZ::MrzIcaoTd2(mrz) => common_field_issues(mrz, &mut report),
+            ParsedMRZ::MrzIcaoMrvA(mrz) => common_field_issues(mrz, &mut report),
+            ParsedMRZ::MrzIcaoMrvB(mrz) => common_field_issues(mrz, &mut report),
+            ParsedMRZ::MrzFraId(fra) => fra_id_issues(fra, &mut report),
+            ParsedMRZ::MrzDrivingLicence(dl) => driving_licence_issues(dl, &mut report),
+            ParsedMRZ::MrzSwissDrivingLicence(dl) => swiss_driving_licence_issues(dl, &mut report),
+            // Boarding passes carry no check digits.
+            ParsedMRZ::Bcbp(_) | ParsedMRZ::Unknown => {}
+        }
+        report.document = Some(document);
+        report
+    }
+
+    /// Builds a report for input that could not be parsed.
+    pub fn from_error(error: &MRZParseError) -> Self {
+        let mut report = ParseReport::new(None);
+        report.push(error.into());
+        report
+    }
+}
+
+/// Records every problem of the fields shared by ICAO documents.
+pub fn common_field_issues<D>(raw: &impl MrzIcaoCommonFields, report: &mut ParseReport<D>) {
+    if !raw.is_document_number_valid() {
+        report.push(ParseIssue::error(
+            MrzField::DocumentNumber,
+            IssueKind::ChecksumMismatch,
+        ));
+    }
+    date_issues(
+        raw.birth_date(),
+        raw.is_birth_date_valid(),
+        MrzField::BirthDate,
+        report,
+    );
+    if !matches!(raw.sex(), b'M' | b'F' | b'X' | b'<') {
+        report.push(ParseIssue::warning(MrzField::Sex, IssueKind::InvalidValue));
+    }
+    date_issues(
+        raw.expiry_date(),
+        raw.is_expiry_date_valid(),
+        MrzField::ExpiryDate,
+        report,
+    );
+    if raw.is_final_check_valid() == Some(false) {
+        report.push(ParseIssue::error(
+            MrzField::Final,
+            IssueKind::ChecksumMismatch,
+        ));
+    }
+    for field in raw.corrected_check_digits() {
+        report.push(ParseIssue::warning(
+            field.into(),
+            IssueKind::CheckDigitCorrected,
+        ));
+    }
+    for field in [
+        MRZChecksumError::DocumentNumber,
+        MRZChecksumError::BirthDate,
+        MRZChecksumError::ExpiryDate,
+    ] {
+        if raw.correction_outcome(field.clone()) == Some(CorrectionOutcome::Ambiguous) {
+            report.push(ParseIssue::warning(
+                field.into(),
+                IssueKind::AmbiguousCorrection,
+            ));
+        }
+    }
+    if (0..2).any(|index| raw.optional_data_correction(index).is_some()) {
+        report.push(ParseIssue::warning(
+            MrzField::OptionalData,
+            IssueKind::OptionalDataCorrected,
+        ));
+    }
+}
+
+/// Records every problem of a legacy French ID card.
+pub fn fra_id_issues<D>(raw: &MrzFraId, report: &mut ParseReport<D>) {
+    if !raw.is_card_number_valid() {
+        report.push(ParseIssue::error(
+            MrzField::DocumentNumber,
+            IssueKind::ChecksumMismatch,
+        ));
+    }
+    date_issues(
+        raw.birth_date(),
+        raw.is_birth_date_valid(),
+        MrzField::BirthDate,
+        report,
+    );
+    if !matches!(raw.sex, b'M' | b'F' | b'<') {
+        report.push(ParseIssue::warning(MrzField::Sex, IssueKind::InvalidValue));
+    }
+    if !raw.is_final_check_valid() {
+        report.push(ParseIssue::error(
+            MrzField::Final,
+            IssueKind::ChecksumMismatch,
+        ));
+    }
+}
+
+fn driving_licence_issues<D>(raw: &MrzDrivingLicence, report: &mut ParseReport<D>) {
+    if !raw.is_licence_number_valid() {
+        report.push(ParseIssue::error(
+            MrzField::DocumentNumber,
+            IssueKind::ChecksumMismatch,
+        ));
+    }
+}
+
+fn swiss_driving_licence_issues<D>(raw: &MrzSwissDrivingLicence, report: &mut ParseReport<D>) {
+    if !raw.is_birth_date_valid() {
+        report.push(ParseIssue::error(
+            MrzField::BirthDate,
+            IssueKind::InvalidValue,
+        ));
+    }
+}
+
+// A date with a failed check digit is an error; a date whose check digit matches
+// but which cannot be a calendar date (e.g. month 13) is only a warning.
+fn date_issues<D>(
+    date: &[u8; ICAO_COMMON_DATE_LEN],
+    checksum_valid: bool,
+    field: MrzField,
+    report: &mut ParseReport<D>,
+) {
+    if !checksum_valid {
+        report.push(ParseIssue::error(field, IssueKind::ChecksumMismatch));
+    } else if !is_calendar_date(date) {
+        report.push(ParseIssue::warning(field, IssueKind::InvalidValue));
+    }
+}
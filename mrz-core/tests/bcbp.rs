@@ -0,0 +1,126 @@
+use mrz_core::parser::{detect_format, parse_any};
+use mrz_core::{MRZFormat, MRZParseError, ParsedMRZ};
+
+const SINGLE_LEG: &[u8] = b"M1DESMARAIS/LUC       EABC123 YULFRAAC 0834 326J001A0025 100";
+
+const MULTI_LEG: &[u8] = b"M2DESMARAIS/LUC       EABC123 YULFRAAC 0834 326J001A0025 14D>5181WW6225BAC 00141234560032A014123456789011AC AC 1234567890123   020KYLX58ZDEF456 FRAGVALH 3664 327C012C0002 12C2A220123456789000LH LH                 01PCN^10AGIWVC5EH7J";
+
+#[test]
+fn test_detect_bcbp_format() {
+    assert_eq!(detect_format(&[SINGLE_LEG]), MRZFormat::BCBP);
+    assert_eq!(detect_format(&[MULTI_LEG]), MRZFormat::BCBP);
+    assert_eq!(
+        detect_format(&[b"M5DESMARAIS/LUC       E"]),
+        MRZFormat::Unknown
+    );
+}
+
+#[test]
+fn test_single_leg_mandatory_items() {
+    let result = parse_any(&[SINGLE_LEG]);
+    assert!(
+        matches!(result, Ok(ParsedMRZ::Bcbp(_))),
+        "Expected ParsedMRZ::Bcbp, got {:?}",
+        result
+    );
+    if let Ok(ParsedMRZ::Bcbp(bcbp)) = result {
+        assert_eq!(bcbp.surname(), "DESMARAIS");
+        assert_eq!(bcbp.given_names(), "LUC");
+        assert!(bcbp.is_electronic_ticket());
+        assert!(bcbp.conditional.is_none());
+        assert!(bcbp.security_data.is_none());
+        assert_eq!(bcbp.legs.len(), 1);
+
+        let leg = &bcbp.legs[0];
+        assert_eq!(leg.pnr, "ABC123 ");
+        assert_eq!(&leg.from_airport, b"YUL");
+        assert_eq!(&leg.to_airport, b"FRA");
+        assert_eq!(leg.operating_carrier, "AC ");
+        assert_eq!(leg.flight_number, "0834 ");
+        assert_eq!(leg.flight_day_of_year(), Some(326));
+        assert_eq!(leg.compartment, b'J');
+        assert_eq!(leg.seat, "001A");
+        assert_eq!(leg.sequence_number, "0025 ");
+        assert_eq!(leg.passenger_status, b'1');
+        assert!(leg.conditional.is_none());
+        assert_eq!(leg.airline_data, "");
+    }
+}
+
+#[test]
+fn test_multi_leg_with_conditional_and_security_data() {
+    let result = parse_any(&[MULTI_LEG]);
+    assert!(
+        matches!(result, Ok(ParsedMRZ::Bcbp(_))),
+        "Expected ParsedMRZ::Bcbp, got {:?}",
+        result
+    );
+    if let Ok(ParsedMRZ::Bcbp(bcbp)) = result {
+        assert_eq!(bcbp.legs.len(), 2);
+
+        let unique = bcbp.conditional.as_ref().expect("unique conditional items");
+        assert_eq!(unique.version, b'5');
+        assert_eq!(unique.passenger_description, Some(b'1'));
+        assert_eq!(unique.source_of_check_in, Some(b'W'));
+        assert_eq!(unique.source_of_issuance, Some(b'W'));
+        assert_eq!(unique.issue_date, "6225");
+        assert_eq!(unique.document_type, Some(b'B'));
+        assert_eq!(unique.issuer_designator, "AC ");
+        assert_eq!(unique.baggage_tag, "0014123456003");
+        assert_eq!(unique.first_non_consecutive_baggage_tag, "");
+
+        let first = &bcbp.legs[0];
+        let repeated = first.conditional.as_ref().expect("repeated items, leg 1");
+        assert_eq!(repeated.airline_numeric_code, "014");
+        assert_eq!(repeated.document_serial_number, "1234567890");
+        assert_eq!(repeated.marketing_carrier, "AC ");
+        assert_eq!(repeated.frequent_flyer_number, "1234567890123   ");
+        assert_eq!(repeated.free_baggage_allowance, "20K");
+        assert_eq!(repeated.fast_track, Some(b'Y'));
+        assert_eq!(first.airline_data, "LX58Z");
+
+        let second = &bcbp.legs[1];
+        assert_eq!(second.pnr, "DEF456 ");
+        assert_eq!(&second.from_airport, b"FRA");
+        assert_eq!(&second.to_airport, b"GVA");
+        assert_eq!(second.operating_carrier, "LH ");
+        assert_eq!(second.flight_day_of_year(), Some(327));
+        assert_eq!(second.seat, "012C");
+        let repeated = second.conditional.as_ref().expect("repeated items, leg 2");
+        assert_eq!(repeated.airline_numeric_code, "220");
+        assert_eq!(repeated.free_baggage_allowance, "1PC");
+        assert_eq!(second.airline_data, "");
+
+        let security = bcbp.security_data.as_ref().expect("security data");
+        assert_eq!(security.kind, b'1');
+        assert_eq!(security.data, "GIWVC5EH7J");
+    }
+}
+
+#[test]
+fn test_truncated_bcbp() {
+    let result = parse_any(&[&SINGLE_LEG[..50]]);
+    assert!(matches!(result, Err(MRZParseError::InvalidLength(_))));
+}
+
+#[test]
+fn test_bcbp_with_variable_size_exceeding_data() {
+    let line = b"M1DESMARAIS/LUC       EABC123 YULFRAAC 0834 326J001A0025 110ABC";
+    let result = parse_any(&[line]);
+    assert!(matches!(result, Err(MRZParseError::InvalidLength(_))));
+}
+
+#[test]
+fn test_bcbp_with_invalid_hex_size() {
+    let line = b"M1DESMARAIS/LUC       EABC123 YULFRAAC 0834 326J001A0025 1ZZ";
+    let result = parse_any(&[line]);
+    assert!(matches!(result, Err(MRZParseError::InvalidLength(_))));
+}
+
+#[test]
+fn test_boarding_pass_is_boxed_in_parsed_mrz() {
+    use core::mem::size_of;
+    use mrz_core::BcbpStorage;
+
+    assert_eq!(size_of::<BcbpStorage>(), size_of::<usize>());
+}
@@ -40,3 +40,18 @@ fn test_display_checked_field() {
     let invalid = CheckedField::new("XYZ", Some(MRZChecksumError::DocumentNumber), b'<');
     assert_eq!(format!("{}", invalid), "XYZ (invalid: DocumentNumber)");
 }
+
+#[test]
+fn test_correction_stores_only_substitutions() {
+    use core::mem::size_of;
+    use mrz_core::checked_field::{Correction, MAX_SUBSTITUTIONS};
+
+    // Every checked field of a parsed document carries a correction, so it holds three
+    // bytes per substitution, its length and padding, and rebuilds the value as read
+    // on demand.
+    assert!(size_of::<Correction>() <= 3 * MAX_SUBSTITUTIONS + 2 * size_of::<usize>());
+
+    let correction = Correction::new("L8989O2C3", "L898902C3");
+    assert_eq!(correction.raw("L898902C3"), "L8989O2C3");
+    assert_eq!(correction.raw("L898902C3<<"), "L8989O2C3<<");
+}
@@ -1,5 +1,6 @@
 use mrz_core::MrzIcaoCommonFields;
 
+#[allow(clippy::bool_assert_comparison)]
 pub fn assert_checksum_matches<T: MrzIcaoCommonFields>(mrz: &T) {
     assert_eq!(
         mrz.is_document_number_valid(),
@@ -0,0 +1,85 @@
+use mrz_core::confidence::{scored_confusions, ScoredLine};
+use mrz_core::parser::{parse_any_with, parse_scored};
+use mrz_core::{CharClass, IcaoOcrB, MrzIcaoCommonFields, ParseOptions, ParsedMRZ};
+
+const LINE1: &[u8] = b"P<UTOERIKSSON<<ANNA<MARIA<<<<<<<<<<<<<<<<<<<";
+// Document number L85B1S7O5 with its `7` read as `T`.
+const LINE2: &[u8] = b"L85B1STO50UTO7408122F1204159ZE184226B<<<<<12";
+
+fn confidences(uncertain: usize) -> [u8; 44] {
+    let mut confidences = [99; 44];
+    confidences[uncertain] = 35;
+    confidences
+}
+
+#[test]
+fn test_scored_confusions() {
+    // Confident characters are kept as read.
+    assert!(scored_confusions(&IcaoOcrB, 'O', CharClass::Digit, 95, b"", 90).is_empty());
+
+    // Engine alternatives come first, then the model, with a penalty per 10 points.
+    let confusions = scored_confusions(&IcaoOcrB, 'O', CharClass::Alphanumeric, 40, b"D", 90);
+    let replacements: Vec<(char, u16)> =
+        confusions.iter().map(|c| (c.replacement, c.cost)).collect();
+    assert_eq!(replacements, [('D', 5), ('0', 5)]);
+
+    // Alternatives outside the field's class are ignored, even for legal characters.
+    let confusions = scored_confusions(&IcaoOcrB, '3', CharClass::Digit, 0, b"8B", 90);
+    assert_eq!(confusions.len(), 1);
+    assert_eq!(confusions[0].replacement, '8');
+}
+
+#[test]
+fn test_correction_spends_budget_on_uncertain_characters() {
+    // Plain correction exhausts its budget on the many confusable characters.
+    assert!(parse_any_with(&[LINE1, LINE2], &ParseOptions::default()).is_err());
+
+    let line1 = [99; 44];
+    let line2 = confidences(6);
+    let lines = [
+        ScoredLine::new(LINE1, &line1),
+        ScoredLine::new(LINE2, &line2),
+    ];
+    match parse_scored(&lines, &ParseOptions::default()) {
+        Ok(ParsedMRZ::MrzIcaoTd3(mrz)) => {
+            assert_eq!(mrz.document_number(), "L85B1S7O5");
+            assert_eq!(mrz.is_final_check_valid(), Some(true));
+        }
+        other => panic!("Expected ParsedMRZ::MrzIcaoTd3, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_confident_characters_are_never_substituted() {
+    let line1 = [99; 44];
+    // Only the `O` is uncertain, but the error is elsewhere.
+    let line2 = confidences(7);
+    let lines = [
+        ScoredLine::new(LINE1, &line1),
+        ScoredLine::new(LINE2, &line2),
+    ];
+    match parse_scored(&lines, &ParseOptions::lenient()) {
+        Ok(ParsedMRZ::MrzIcaoTd3(mrz)) => {
+            assert_eq!(mrz.document_number(), "L85B1STO5");
+            assert!(!mrz.is_document_number_valid());
+        }
+        other => panic!("Expected ParsedMRZ::MrzIcaoTd3, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_alternative_glyphs_are_tried() {
+    let line1 = [99; 44];
+    let mut line2 = [99; 44];
+    line2[6] = 60;
+    let mut alternatives: [&[u8]; 44] = [b""; 44];
+    alternatives[6] = b"7";
+    let lines = [
+        ScoredLine::new(LINE1, &line1),
+        ScoredLine::new(LINE2, &line2).with_alternatives(&alternatives),
+    ];
+    match parse_scored(&lines, &ParseOptions::default()) {
+        Ok(ParsedMRZ::MrzIcaoTd3(mrz)) => assert_eq!(mrz.document_number(), "L85B1S7O5"),
+        other => panic!("Expected ParsedMRZ::MrzIcaoTd3, got {:?}", other),
+    }
+}
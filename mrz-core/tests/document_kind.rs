@@ -0,0 +1,79 @@
+use mrz_core::parser::parse_any;
+use mrz_core::{DocumentKind, MrzIcaoCommonFields, ParsedMRZ};
+
+#[test]
+fn test_document_kind_from_code() {
+    let cases: &[(&[u8], DocumentKind)] = &[
+        (b"P<", DocumentKind::Passport),
+        (b"PP", DocumentKind::Passport),
+        (b"PN", DocumentKind::Passport),
+        (b"P", DocumentKind::Passport),
+        (b"PD", DocumentKind::DiplomaticPassport),
+        (b"PO", DocumentKind::OfficialPassport),
+        (b"PS", DocumentKind::OfficialPassport),
+        (b"PE", DocumentKind::EmergencyPassport),
+        (b"ID", DocumentKind::IdentityCard),
+        (b"I<", DocumentKind::IdentityCard),
+        (b"C<", DocumentKind::IdentityCard),
+        (b"IR", DocumentKind::ResidencePermit),
+        (b"AR", DocumentKind::ResidencePermit),
+        (b"AC", DocumentKind::CrewMemberCertificate),
+        (b"V<", DocumentKind::Visa),
+        (b"VB", DocumentKind::Visa),
+        (b"IT", DocumentKind::TravelPermit),
+        (b"PT", DocumentKind::TravelPermit),
+        (b"IP", DocumentKind::PassportCard),
+        (b"D1", DocumentKind::DrivingLicence),
+        (b"XX", DocumentKind::Other(*b"XX")),
+        (b"", DocumentKind::Other(*b"<<")),
+    ];
+
+    for (code, expected) in cases {
+        assert_eq!(
+            DocumentKind::from_code(code),
+            *expected,
+            "code {:?}",
+            core::str::from_utf8(code)
+        );
+    }
+}
+
+#[test]
+fn test_document_kind_is_passport() {
+    assert!(DocumentKind::DiplomaticPassport.is_passport());
+    assert!(!DocumentKind::Visa.is_passport());
+    assert!(!DocumentKind::PassportCard.is_passport());
+}
+
+#[test]
+fn test_document_kind_on_parsed_documents() {
+    let td3 = parse_any(&[
+        b"P<UTOERIKSSON<<ANNA<MARIA<<<<<<<<<<<<<<<<<<<",
+        b"L898902C36UTO7408122F1204159ZE184226B<<<<<10",
+    ])
+    .expect("valid TD3");
+    assert_eq!(td3.document_kind(), Some(DocumentKind::Passport));
+    if let ParsedMRZ::MrzIcaoTd3(mrz) = &td3 {
+        assert_eq!(mrz.document_kind(), DocumentKind::Passport);
+    }
+
+    let td1 = parse_any(&[
+        b"I<UTOD231458907<<<<<<<<<<<<<<<",
+        b"7408122F1204159UTO<<<<<<<<<<<6",
+        b"ERIKSSON<<ANNA<MARIA<<<<<<<<<<",
+    ])
+    .expect("valid TD1");
+    assert_eq!(td1.document_kind(), Some(DocumentKind::IdentityCard));
+
+    let mrvb = parse_any(&[
+        b"V<UTOERIKSSON<<ANNA<MARIA<<<<<<<<<<<",
+        b"L8988901C4XXX4009078F9612109<<<<<<<<",
+    ])
+    .expect("valid MRV-B");
+    assert_eq!(mrvb.document_kind(), Some(DocumentKind::Visa));
+
+    let dl = parse_any(&[b"D1NLD15094962111659VW87MG13B84"]).expect("valid driving licence");
+    assert_eq!(dl.document_kind(), Some(DocumentKind::DrivingLicence));
+
+    assert_eq!(ParsedMRZ::Unknown.document_kind(), None);
+}
@@ -0,0 +1,75 @@
+use mrz_core::parser::{detect_format, parse_any};
+use mrz_core::{MRZChecksumError, MRZFormat, MRZParseError, ParsedMRZ};
+
+#[test]
+fn test_detect_driving_licence_format() {
+    assert_eq!(
+        detect_format(&[b"D1NLD15094962111659VW87MG13B84"]),
+        MRZFormat::MrzDrivingLicence
+    );
+    assert_eq!(
+        detect_format(&[b"D<DEU1B072RRE2I55<<<<<<<<<<<<1"]),
+        MRZFormat::MrzDrivingLicence
+    );
+    assert_eq!(
+        detect_format(&[b"D1NLD15094962111659VW87MG13B8"]),
+        MRZFormat::Unknown
+    );
+}
+
+#[test]
+fn test_valid_dutch_driving_licence() {
+    let result = parse_any(&[b"D1NLD15094962111659VW87MG13B84"]);
+    assert!(
+        matches!(result, Ok(ParsedMRZ::MrzDrivingLicence(_))),
+        "Expected ParsedMRZ::MrzDrivingLicence, got {:?}",
+        result
+    );
+    if let Ok(ParsedMRZ::MrzDrivingLicence(dl)) = result {
+        assert_eq!(&dl.document_code, b"D1");
+        assert_eq!(&dl.issuing_state, b"NLD");
+        assert_eq!(dl.configuration, b'1');
+        assert_eq!(dl.licence_number(), "5094962111659VW87MG13B8");
+        assert!(dl.is_licence_number_valid());
+    }
+}
+
+#[test]
+fn test_valid_padded_driving_licence() {
+    let result = parse_any(&[b"D<DEU1B072RRE2I55<<<<<<<<<<<<1"]);
+    if let Ok(ParsedMRZ::MrzDrivingLicence(dl)) = result {
+        assert_eq!(&dl.document_code, b"D<");
+        assert_eq!(&dl.issuing_state, b"DEU");
+        assert_eq!(dl.licence_number(), "B072RRE2I55");
+        assert!(dl.is_licence_number_valid());
+    } else {
+        panic!("Expected ParsedMRZ::MrzDrivingLicence, got {:?}", result);
+    }
+}
+
+#[test]
+fn test_driving_licence_with_ocr_error() {
+    let result = parse_any(&[b"D<DEU1BO72RRE2I55<<<<<<<<<<<<1"]); // 'O' instead of '0'
+    if let Ok(ParsedMRZ::MrzDrivingLicence(dl)) = result {
+        assert_eq!(dl.licence_number(), "B072RRE2I55");
+        assert!(dl.is_licence_number_valid());
+    } else {
+        panic!("Expected corrected driving licence, got {:?}", result);
+    }
+}
+
+#[test]
+fn test_driving_licence_invalid_check_digit() {
+    let result = parse_any(&[b"D<DEU1ACDEFGHJKL<<<<<<<<<<<<<3"]);
+    assert!(
+        matches!(
+            result,
+            Err(MRZParseError::InvalidChecksumField(
+                MRZChecksumError::DocumentNumber,
+                _
+            ))
+        ),
+        "Expected checksum error, got {:?}",
+        result
+    );
+}
@@ -0,0 +1,184 @@
+use mrz_core::parser::{parse_any, parse_any_with, parse_scored};
+use mrz_core::{
+    CharClass, ChecksumPolicy, ErrorPosition, MRZChecksumError, MRZParseError, ParseOptions,
+    ScoredLine,
+};
+
+const TD3_LINE1: &[u8] = b"P<UTOERIKSSON<<ANNA<MARIA<<<<<<<<<<<<<<<<<<<";
+
+#[test]
+fn test_invalid_character_in_date() {
+    let line2 = b"L898902C36UTO74x8122F1204159ZE184226B<<<<<10";
+
+    let err = parse_any(&[TD3_LINE1, line2]).unwrap_err();
+    assert_eq!(
+        err,
+        MRZParseError::InvalidCharacter {
+            position: ErrorPosition::at(1, 15),
+            found: b'x',
+            expected: CharClass::Digit,
+        }
+    );
+    assert_eq!(err.position(), Some(ErrorPosition::new(1, 15, 16)));
+}
+
+#[test]
+fn test_invalid_check_digit_character() {
+    let line2 = b"L898902C3AUTO7408122F1204159ZE184226B<<<<<10";
+
+    let err = parse_any(&[TD3_LINE1, line2]).unwrap_err();
+    assert_eq!(
+        err,
+        MRZParseError::InvalidCharacter {
+            position: ErrorPosition::at(1, 9),
+            found: b'A',
+            expected: CharClass::Digit,
+        }
+    );
+}
+
+#[test]
+fn test_checksum_error_spans_field_and_check_digit() {
+    let line2 = b"L898902C36UTO7408122F1204159ZE184226B<<<<<19";
+
+    let err = parse_any(&[TD3_LINE1, line2]).unwrap_err();
+    assert_eq!(err.checksum_error(), Some(&MRZChecksumError::Final));
+    assert_eq!(err.position(), Some(ErrorPosition::at(1, 43)));
+}
+
+#[test]
+fn test_utf8_error_position() {
+    let mut line2 = b"L898902C36UTO7408122F1204159ZE184226B<<<<<10".to_vec();
+    line2[3] = 0xFF;
+
+    let err = parse_any(&[TD3_LINE1, &line2]).unwrap_err();
+    assert_eq!(err, MRZParseError::Utf8Error(Some(ErrorPosition::at(1, 3))));
+}
+
+#[test]
+fn test_invalid_length_position() {
+    let line2 = b"L898902C36UTO7408122F1204159ZE184226B<<<<<";
+
+    let err = parse_any(&[TD3_LINE1, line2]).unwrap_err();
+    assert_eq!(
+        err,
+        MRZParseError::InvalidLength(Some(ErrorPosition::new(1, 42, 44)))
+    );
+}
+
+#[test]
+fn test_truncated_boarding_pass_position() {
+    let err = parse_any(&[b"M1DESMARAIS/LUC"]).unwrap_err();
+    assert_eq!(
+        err,
+        MRZParseError::InvalidLength(Some(ErrorPosition::new(0, 2, 22)))
+    );
+}
+
+#[test]
+fn test_boarding_pass_utf8_error_position() {
+    let mut line = b"M1DESMARAIS/LUC       EABC123 YULFRAAC 0834 326J001A0025 100".to_vec();
+    line[8] = 0xFF;
+
+    let err = parse_any(&[&line]).unwrap_err();
+    assert_eq!(err, MRZParseError::Utf8Error(Some(ErrorPosition::at(0, 8))));
+}
+
+#[test]
+fn test_line_count_error_position() {
+    let lines: [ScoredLine<'_>; 4] = [ScoredLine::new(b"P<", &[100, 100]); 4];
+
+    let err = parse_scored(&lines, &ParseOptions::default()).unwrap_err();
+    assert_eq!(
+        err,
+        MRZParseError::InvalidLength(Some(ErrorPosition::new(3, 0, 2)))
+    );
+}
+
+#[test]
+fn test_checksum_position_matches_parse_errors() {
+    let lines: [(&[u8], &[u8], MRZChecksumError); 3] = [
+        (
+            TD3_LINE1,
+            b"L898902C35UTO7408122F1204159ZE184226B<<<<<10",
+            MRZChecksumError::DocumentNumber,
+        ),
+        (
+            TD3_LINE1,
+            b"L898902C36UTO7408121F1204159ZE184226B<<<<<10",
+            MRZChecksumError::BirthDate,
+        ),
+        (
+            TD3_LINE1,
+            b"L898902C36UTO7408122F1204159ZE184226B<<<<<19",
+            MRZChecksumError::Final,
+        ),
+    ];
+    let strict = ParseOptions {
+        ocr_correction: false,
+        ..ParseOptions::default()
+    };
+    let recorded = ParseOptions {
+        checksum_policy: ChecksumPolicy::Record,
+        ..strict
+    };
+
+    for (line1, line2, kind) in lines {
+        let err = parse_any_with(&[line1, line2], &strict).unwrap_err();
+        let parsed = parse_any_with(&[line1, line2], &recorded).unwrap();
+        assert_eq!(err.checksum_error(), Some(&kind));
+        assert_eq!(
+            parsed.checksum_position(&kind),
+            err.position(),
+            "{:?}",
+            kind
+        );
+    }
+}
+
+#[test]
+fn test_display() {
+    let err = MRZParseError::InvalidCharacter {
+        position: ErrorPosition::at(1, 15),
+        found: b'x',
+        expected: CharClass::Digit,
+    };
+    assert_eq!(
+        err.to_string(),
+        "invalid character 'x', expected digit at line 2, column 16"
+    );
+
+    let err = MRZParseError::InvalidChecksumField(
+        MRZChecksumError::BirthDate,
+        Some(ErrorPosition::new(1, 13, 20)),
+    );
+    assert_eq!(
+        err.to_string(),
+        "check digit mismatch in BirthDate at line 2, columns 14-20"
+    );
+    assert_eq!(
+        MRZParseError::UnknownFormat.to_string(),
+        "unknown MRZ format"
+    );
+}
+
+#[test]
+fn test_errors_implement_error_trait() {
+    fn source_of(e: &dyn core::error::Error) -> Option<&dyn core::error::Error> {
+        e.source()
+    }
+
+    assert!(source_of(&MRZParseError::UnknownFormat).is_none());
+    assert!(source_of(&MRZChecksumError::Final).is_none());
+}
+
+#[test]
+fn test_char_class() {
+    assert_eq!(CharClass::of(b'7'), CharClass::Digit);
+    assert_eq!(CharClass::of(b'Q'), CharClass::Letter);
+    assert_eq!(CharClass::of(b'<'), CharClass::Filler);
+    assert_eq!(CharClass::of(b'q'), CharClass::Other);
+    assert!(CharClass::Alphanumeric.contains(b'<'));
+    assert!(!CharClass::Alphanumeric.contains(b' '));
+    assert!(!CharClass::Digit.contains(b'O'));
+}
@@ -0,0 +1,94 @@
+use mrz_core::parser::parse_any;
+use mrz_core::{
+    CharClass, ErrorPosition, MRZChecksumError, MRZParseError, MrzIcaoCommonFields, ParsedMRZ,
+};
+
+#[test]
+fn test_td1_extended_document_number() {
+    // ICAO 9303 Part 5 example of a document number longer than 9 characters
+    let line1 = b"I<UTOD23145890<7349<<<<<<<<<<<";
+    let line2 = b"3407127M9507122UTO<<<<<<<<<<<2";
+    let line3 = b"STEVENSON<<PETER<JOHN<<<<<<<<<";
+
+    let result = parse_any(&[line1, line2, line3]);
+    assert!(
+        matches!(result, Ok(ParsedMRZ::MrzIcaoTd1(_))),
+        "Expected ParsedMRZ::MrzIcaoTd1, got {:?}",
+        result
+    );
+    if let Ok(ParsedMRZ::MrzIcaoTd1(mrz)) = result {
+        assert_eq!(mrz.document_number(), "D23145890734");
+        assert!(mrz.is_document_number_valid());
+        assert_eq!(mrz.document_number.check_digit(), Some(b'9'));
+        assert_eq!(mrz.optional_data1(), "<<<<<<<<<<<");
+        assert_eq!(mrz.is_final_check_valid(), Some(true));
+        assert_eq!(mrz.surname(), "STEVENSON");
+        assert_eq!(mrz.given_names(), "PETER JOHN");
+    }
+}
+
+#[test]
+fn test_td1_extended_document_number_invalid_check_digit() {
+    let line1 = b"I<UTOD23145890<7341<<<<<<<<<<<";
+    let line2 = b"3407127M9507122UTO<<<<<<<<<<<2";
+    let line3 = b"STEVENSON<<PETER<JOHN<<<<<<<<<";
+
+    // OCR correction may repair the document number into a different valid number,
+    // in which case the composite check catches the mismatch.
+    let result = parse_any(&[line1, line2, line3]);
+    assert!(
+        matches!(
+            result,
+            Err(MRZParseError::InvalidChecksumField(
+                MRZChecksumError::DocumentNumber | MRZChecksumError::Final,
+                _
+            ))
+        ),
+        "Expected checksum error, got {:?}",
+        result
+    );
+}
+
+#[test]
+fn test_td1_regular_document_number_keeps_optional_data() {
+    let line1 = b"I<UTOD231458907<<<<<<<<<<<<<<<";
+    let line2 = b"7408122F1204159UTO<<<<<<<<<<<6";
+    let line3 = b"ERIKSSON<<ANNA<MARIA<<<<<<<<<<";
+
+    let result = parse_any(&[line1, line2, line3]);
+    assert!(
+        matches!(result, Ok(ParsedMRZ::MrzIcaoTd1(_))),
+        "Expected ParsedMRZ::MrzIcaoTd1, got {:?}",
+        result
+    );
+    if let Ok(ParsedMRZ::MrzIcaoTd1(mrz)) = result {
+        assert_eq!(mrz.document_number(), "D23145890");
+        assert_eq!(mrz.optional_data1(), "<<<<<<<<<<<<<<<");
+    }
+}
+
+#[test]
+fn test_td3_has_no_extended_document_number() {
+    // TD3 document numbers never continue in the optional data (ICAO 9303 Part 4),
+    // so a `<` check digit is a checksum failure rather than an extension marker.
+    let line1 = b"P<UTOERIKSSON<<ANNA<MARIA<<<<<<<<<<<<<<<<<<<";
+    let line2 = b"L898902C3<UTO7408122F1204159AB9<<<<<<<<<<<20";
+
+    let result = parse_any(&[line1, line2]);
+    assert!(
+        matches!(
+            result,
+            Err(MRZParseError::InvalidCharacter {
+                position: ErrorPosition {
+                    line: 1,
+                    start: 9,
+                    ..
+                },
+                found: b'<',
+                expected: CharClass::Digit,
+            })
+        ),
+        "Expected document number error, got {:?}",
+        result
+    );
+}
@@ -0,0 +1,100 @@
+use mrz_core::extract::{extract_mrz, scan};
+use mrz_core::parser::parse_any;
+use mrz_core::{MRZFormat, MrzIcaoCommonFields, ParsedMRZ};
+
+const PAGE: &str = "\
+PASSPORT  PASSEPORT  UTOPIA
+Type / Type   Code / Code   Passport No. / No. du passeport
+P   UTO   L898902C3
+Surname / Nom
+ERIKSSON
+Given names / Prénoms
+ANNA MARIA
+
+P<UTOERIKSSON<<ANNA<MARIA<<<<<<<<<<<<<<<<<<<
+L898902C36UTO7408122F1204159ZE184226B<<<<<10
+";
+
+#[test]
+fn test_extract_td3_from_page() {
+    let found = extract_mrz(PAGE);
+    assert_eq!(found.len(), 1);
+
+    let mrz = &found[0];
+    assert_eq!(mrz.format, MRZFormat::MrzIcaoTd3);
+    assert_eq!((mrz.first_line, mrz.last_line), (8, 9));
+    assert_eq!((mrz.checks_passed, mrz.checks_total), (4, 4));
+
+    let result = parse_any(&mrz.line_bytes());
+    assert!(
+        matches!(result, Ok(ParsedMRZ::MrzIcaoTd3(_))),
+        "Expected ParsedMRZ::MrzIcaoTd3, got {:?}",
+        result
+    );
+}
+
+#[test]
+fn test_extract_noisy_td1() {
+    // Lowercase OCR output with spaces for fillers, trailing fillers lost and a
+    // blank line inside the MRZ.
+    let text = "Identity card\r\n\
+                i utod231458907\r\n\
+                7408122f1204159uto           6\r\n\
+                \r\n\
+                eriksson  anna maria\r\n\
+                Signature";
+
+    let found = extract_mrz(text);
+    assert_eq!(found.len(), 1);
+    let mrz = &found[0];
+    assert_eq!(mrz.format, MRZFormat::MrzIcaoTd1);
+    assert_eq!((mrz.first_line, mrz.last_line), (1, 4));
+    assert_eq!(mrz.lines[0].as_str(), "I<UTOD231458907<<<<<<<<<<<<<<<");
+    assert_eq!(mrz.lines[2].as_str(), "ERIKSSON<<ANNA<MARIA<<<<<<<<<<");
+
+    let lines = mrz.line_bytes();
+    match parse_any(&lines) {
+        Ok(ParsedMRZ::MrzIcaoTd1(td1)) => {
+            assert_eq!(td1.document_number(), "D23145890");
+            assert_eq!(td1.surname(), "ERIKSSON");
+        }
+        other => panic!("Expected ParsedMRZ::MrzIcaoTd1, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_extract_several_documents() {
+    let text = format!(
+        "{}\n--- page 2 ---\n\
+         IDFRADOUEL<<<<<<<<<<<<<<<<<<<<932013\n\
+         0506932020438CHRISTIANE<<NI2906209F3\n",
+        PAGE
+    );
+
+    let found = extract_mrz(&text);
+    assert_eq!(found.len(), 2);
+    assert_eq!(found[0].format, MRZFormat::MrzIcaoTd3);
+    assert_eq!(found[1].format, MRZFormat::MrzFraId);
+    assert!(found[0].first_line < found[1].first_line);
+}
+
+#[test]
+fn test_incomplete_mrz_is_ignored() {
+    // A TD1 followed by the first two lines of another one.
+    let text = "I<UTOD231458907<<<<<<<<<<<<<<<\n\
+                7408122F1204159UTO<<<<<<<<<<<6\n\
+                ERIKSSON<<ANNA<MARIA<<<<<<<<<<\n\
+                I<UTOD231458907<<<<<<<<<<<<<<<\n\
+                7408122F1204159UTO<<<<<<<<<<<6";
+
+    assert_eq!(scan(text).count(), 1);
+    let found = extract_mrz(text);
+    assert_eq!(found.len(), 1);
+    assert_eq!((found[0].first_line, found[0].last_line), (0, 2));
+}
+
+#[test]
+fn test_text_without_mrz() {
+    assert!(extract_mrz("").is_empty());
+    assert!(extract_mrz("Hello\nWORLD<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<\n").is_empty());
+}
@@ -1,5 +1,8 @@
-use mrz_core::field_correction::correct_checked_field;
-use mrz_core::MRZChecksumError;
+use mrz_core::confusion::IcaoOcrB;
+use mrz_core::field_correction::{
+    correct_checked_field, correct_checked_field_as, correct_checked_field_within,
+};
+use mrz_core::{CharClass, CorrectionOutcome, MRZChecksumError, Substitution};
 
 #[test]
 fn test_document_number_ocr_s_as_5() {
@@ -23,3 +26,187 @@ fn test_document_number_ocr_s_as_5() {
         "Corrected string mismatch"
     );
 }
+
+#[test]
+fn test_date_correction_stays_numeric() {
+    use heapless::String;
+
+    let corrected = correct_checked_field_as::<6, 16, String<6>>(
+        "74O8I2",
+        '2',
+        CharClass::Digit,
+        &IcaoOcrB,
+        4,
+        16,
+        MRZChecksumError::BirthDate,
+    );
+    assert_eq!(corrected.unwrap().value().as_str(), "740812");
+
+    // `Z` is confused with `2` by the ICAO OCR-B model.
+    let corrected = correct_checked_field_as::<6, 16, String<6>>(
+        "74O8IZ",
+        '2',
+        CharClass::Digit,
+        &IcaoOcrB,
+        4,
+        16,
+        MRZChecksumError::BirthDate,
+    );
+    assert_eq!(corrected.unwrap().value().as_str(), "740812");
+
+    // A valid date is never turned into letters to satisfy a wrong check digit.
+    let corrected = correct_checked_field_as::<6, 16, String<6>>(
+        "740812",
+        '3',
+        CharClass::Digit,
+        &IcaoOcrB,
+        4,
+        16,
+        MRZChecksumError::BirthDate,
+    );
+    assert!(corrected.is_err());
+}
+
+#[test]
+fn test_misread_check_digit_is_repaired() {
+    use heapless::String;
+
+    // `T` read in place of the check digit `7`.
+    let corrected = correct_checked_field::<9, 8, String<9>>(
+        "D23145890",
+        'T',
+        2,
+        MRZChecksumError::DocumentNumber,
+    )
+    .unwrap();
+    assert_eq!(corrected.value().as_str(), "D23145890");
+    assert_eq!(corrected.check_digit(), Some(b'7'));
+    assert_eq!(corrected.raw_check_digit(), Some(b'T'));
+    assert!(corrected.is_check_digit_corrected());
+
+    // A check digit without a numeric lookalike cannot be repaired.
+    let corrected = correct_checked_field::<9, 8, String<9>>(
+        "D23145890",
+        'X',
+        2,
+        MRZChecksumError::DocumentNumber,
+    );
+    assert!(corrected.is_err());
+}
+
+#[test]
+fn test_corrections_are_ranked_by_cost() {
+    use heapless::String;
+
+    let corrected = correct_checked_field_within::<9, 64, String<9>>(
+        "D2314589O",
+        '7',
+        2,
+        64,
+        MRZChecksumError::DocumentNumber,
+    )
+    .unwrap();
+    assert_eq!(corrected.value().as_str(), "D23145890");
+    assert_eq!(corrected.correction_outcome(), CorrectionOutcome::Corrected);
+    assert!(!corrected.is_ambiguous());
+
+    let candidates = corrected.candidates();
+    assert_eq!(candidates[0].value.as_str(), "D23145890");
+    assert_eq!(candidates[0].cost, 1);
+    assert!(candidates.len() > 1);
+    assert!(candidates.windows(2).all(|w| w[0].cost <= w[1].cost));
+}
+
+#[test]
+fn test_correction_examines_budget_candidates_only() {
+    use heapless::String;
+    use mrz_core::ocr::OcrPermutations;
+
+    // The fix is the `rank`-th cheapest permutation: a budget of `rank` candidates
+    // misses it, one more finds it, however large `M` is.
+    let rank = OcrPermutations::<9>::new("D2314589O", &IcaoOcrB, CharClass::Alphanumeric, 2)
+        .position(|permutation| permutation.text == "D23145890")
+        .unwrap();
+    assert!(rank > 0);
+
+    let within = |budget| {
+        correct_checked_field_within::<9, { 1 << 20 }, String<9>>(
+            "D2314589O",
+            '7',
+            2,
+            budget,
+            MRZChecksumError::DocumentNumber,
+        )
+    };
+    assert!(within(rank).is_err());
+    assert_eq!(within(rank + 1).unwrap().value().as_str(), "D23145890");
+}
+
+#[test]
+fn test_correction_records_raw_value_and_substitutions() {
+    use heapless::String;
+
+    let corrected = correct_checked_field_within::<9, 64, String<9>>(
+        "D2314S89O<",
+        '7',
+        2,
+        64,
+        MRZChecksumError::DocumentNumber,
+    )
+    .unwrap();
+    let correction = corrected.correction().unwrap();
+    assert_eq!(correction.raw(corrected.value()), "D2314S89O");
+    assert_eq!(
+        correction.substitutions,
+        [
+            Substitution {
+                position: 5,
+                from: b'S',
+                to: b'5'
+            },
+            Substitution {
+                position: 8,
+                from: b'O',
+                to: b'0'
+            },
+        ]
+    );
+
+    // Only the check digit was misread.
+    let repaired = correct_checked_field_within::<9, 64, String<9>>(
+        "D23145890",
+        'T',
+        2,
+        64,
+        MRZChecksumError::DocumentNumber,
+    )
+    .unwrap();
+    assert_eq!(repaired.raw_check_digit(), Some(b'T'));
+    let correction = repaired.correction().unwrap();
+    assert!(correction.substitutions.is_empty());
+}
+
+#[test]
+fn test_tied_corrections_are_ambiguous() {
+    use heapless::String;
+
+    // `8` -> `B` and `5` -> `S` both satisfy the check digit at the same cost.
+    let corrected = correct_checked_field_within::<9, 64, String<9>>(
+        "X1S987654",
+        '6',
+        2,
+        64,
+        MRZChecksumError::DocumentNumber,
+    )
+    .unwrap();
+    assert!(corrected.is_ambiguous());
+    assert_eq!(corrected.correction_outcome(), CorrectionOutcome::Ambiguous);
+
+    let values: Vec<&str> = corrected
+        .candidates()
+        .iter()
+        .map(|c| c.value.as_str())
+        .collect();
+    assert_eq!(&values[..2], ["X1S9B7654", "X1S9876S4"]);
+    assert_eq!(corrected.value().as_str(), values[0]);
+}
@@ -0,0 +1,128 @@
+use mrz_core::parser::{detect_format, parse_any};
+use mrz_core::{MRZChecksumError, MRZFormat, MRZParseError, ParsedMRZ};
+
+// Lines, surname, given names, card number, birth date and sex.
+type Specimen = (
+    [&'static [u8]; 2],
+    &'static str,
+    &'static str,
+    &'static str,
+    &'static [u8; 6],
+    u8,
+);
+
+// French national ID card specimens (pre-2021 layout).
+const SPECIMENS: &[Specimen] = &[
+    (
+        [
+            b"IDFRADOUEL<<<<<<<<<<<<<<<<<<<<932013",
+            b"0506932020438CHRISTIANE<<NI2906209F3",
+        ],
+        "DOUEL",
+        "CHRISTIANE NI",
+        "050693202043",
+        b"290620",
+        b'F',
+    ),
+    (
+        [
+            b"IDFRABERTHIER<<<<<<<<<<<<<<<<<923000",
+            b"9409923102854CORINNE<<<<<<<6512068F6",
+        ],
+        "BERTHIER",
+        "CORINNE",
+        "940992310285",
+        b"651206",
+        b'F',
+    ),
+];
+
+#[test]
+fn test_detect_fra_id_format() {
+    for (lines, ..) in SPECIMENS {
+        assert_eq!(detect_format(lines), MRZFormat::MrzFraId);
+    }
+}
+
+#[test]
+fn test_fra_id_specimens() {
+    for (lines, surname, given_names, card_number, birth_date, sex) in SPECIMENS {
+        let result = parse_any(lines);
+        assert!(
+            matches!(result, Ok(ParsedMRZ::MrzFraId(_))),
+            "Expected ParsedMRZ::MrzFraId, got {:?}",
+            result
+        );
+        if let Ok(ParsedMRZ::MrzFraId(mrz)) = result {
+            assert_eq!(&mrz.document_code, b"ID");
+            assert_eq!(&mrz.issuing_state, b"FRA");
+            assert_eq!(mrz.surname(), *surname);
+            assert_eq!(mrz.given_names(), *given_names);
+            assert_eq!(mrz.card_number(), *card_number);
+            assert_eq!(mrz.birth_date(), *birth_date);
+            assert_eq!(mrz.sex, *sex);
+            assert!(mrz.is_card_number_valid());
+            assert!(mrz.is_birth_date_valid());
+            assert!(mrz.is_final_check_valid());
+        }
+    }
+}
+
+#[test]
+fn test_fra_id_administrative_codes() {
+    let (lines, ..) = SPECIMENS[0];
+    if let Ok(ParsedMRZ::MrzFraId(mrz)) = parse_any(&lines) {
+        assert_eq!(&mrz.department, b"932");
+        assert_eq!(&mrz.office, b"013");
+        assert_eq!(mrz.issue_year_month(), b"0506");
+    } else {
+        panic!("Expected ParsedMRZ::MrzFraId");
+    }
+}
+
+#[test]
+fn test_fra_id_with_ocr_error_in_card_number() {
+    let line1 = b"IDFRADOUEL<<<<<<<<<<<<<<<<<<<<932013";
+    let line2 = b"O506932020438CHRISTIANE<<NI2906209F3"; // 'O' instead of '0'
+
+    let result = parse_any(&[line1, line2]);
+    assert!(
+        matches!(result, Ok(ParsedMRZ::MrzFraId(_))),
+        "Expected successful parse with corrected OCR error, got {:?}",
+        result
+    );
+    if let Ok(ParsedMRZ::MrzFraId(mrz)) = result {
+        assert_eq!(mrz.card_number(), "050693202043");
+        assert!(mrz.is_final_check_valid());
+    }
+}
+
+#[test]
+fn test_fra_id_invalid_final_check() {
+    let line1 = b"IDFRADOUEL<<<<<<<<<<<<<<<<<<<<932013";
+    let line2 = b"0506932020438CHRISTIANE<<NI2906209F4";
+
+    let result = parse_any(&[line1, line2]);
+    assert!(
+        matches!(
+            result,
+            Err(MRZParseError::InvalidChecksumField(
+                MRZChecksumError::Final,
+                _
+            ))
+        ),
+        "Expected final checksum error, got {:?}",
+        result
+    );
+}
+
+#[test]
+fn test_fra_id_is_not_parsed_as_td2() {
+    let line1 = b"IDFRADOUEL<<<<<<<<<<<<<<<<<<<<932013";
+    let line2 = b"0506932020438CHRISTIANE<<NI2906209F3";
+
+    assert!(!matches!(
+        parse_any(&[line1, line2]),
+        Ok(ParsedMRZ::MrzIcaoTd2(_))
+    ));
+}
@@ -0,0 +1,87 @@
+use mrz_core::line_order::detect_line_order;
+use mrz_core::parser::{parse_any, parse_any_report, parse_any_with};
+use mrz_core::{IssueKind, MrzField, MrzIcaoCommonFields, ParseOptions, ParsedMRZ, Severity};
+
+const TD3_LINE1: &[u8] = b"P<UTOERIKSSON<<ANNA<MARIA<<<<<<<<<<<<<<<<<<<";
+const TD3_LINE2: &[u8] = b"L898902C36UTO7408122F1204159ZE184226B<<<<<10";
+
+const TD1_LINE1: &[u8] = b"I<UTOD231458907<<<<<<<<<<<<<<<";
+const TD1_LINE2: &[u8] = b"7408122F1204159UTO<<<<<<<<<<<6";
+const TD1_LINE3: &[u8] = b"ERIKSSON<<ANNA<MARIA<<<<<<<<<<";
+
+fn reordering() -> ParseOptions {
+    ParseOptions {
+        reorder_lines: true,
+        ..ParseOptions::default()
+    }
+}
+
+#[test]
+fn test_swapped_td3_lines() {
+    let lines = [TD3_LINE2, TD3_LINE1];
+    assert!(parse_any(&lines).is_err());
+
+    let order = detect_line_order(&lines);
+    assert_eq!(order.as_slice(), &[1, 0]);
+    assert!(!order.is_identity());
+
+    match parse_any_with(&lines, &reordering()) {
+        Ok(ParsedMRZ::MrzIcaoTd3(td3)) => {
+            assert_eq!(td3.document_number(), "L898902C3");
+            assert_eq!(td3.surname(), "ERIKSSON");
+            assert_eq!(td3.line_order, Some(order));
+        }
+        other => panic!("Expected ParsedMRZ::MrzIcaoTd3, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_shuffled_td1_lines() {
+    let lines = [TD1_LINE3, TD1_LINE1, TD1_LINE2];
+    let order = detect_line_order(&lines);
+    assert_eq!(order.as_slice(), &[1, 2, 0]);
+
+    match parse_any_with(&lines, &reordering()) {
+        Ok(ParsedMRZ::MrzIcaoTd1(td1)) => {
+            assert_eq!(td1.document_number(), "D23145890");
+            assert_eq!(td1.is_final_check_valid(), Some(true));
+            assert_eq!(td1.line_order().unwrap().as_slice(), &[1, 2, 0]);
+        }
+        other => panic!("Expected ParsedMRZ::MrzIcaoTd1, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_ordered_lines_are_kept() {
+    assert!(detect_line_order(&[TD3_LINE1, TD3_LINE2]).is_identity());
+    assert!(detect_line_order(&[TD1_LINE1, TD1_LINE2, TD1_LINE3]).is_identity());
+    assert!(detect_line_order(&[b"M1DESMARAIS/LUC"]).is_identity());
+
+    let report = parse_any_report(&[TD3_LINE1, TD3_LINE2], &reordering());
+    assert!(report.is_clean());
+    assert_eq!(report.line_order, None);
+    assert_eq!(report.document.unwrap().line_order(), None);
+
+    // More lines than any layout are left in place and rejected by the parser.
+    let lines = [TD1_LINE1, TD1_LINE2, TD1_LINE3, TD1_LINE3];
+    assert!(detect_line_order(&lines).as_slice().is_empty());
+    assert!(parse_any_with(&lines, &reordering()).is_err());
+}
+
+#[test]
+fn test_report_flags_reordered_lines() {
+    let report = parse_any_report(&[TD1_LINE2, TD1_LINE1, TD1_LINE3], &reordering());
+
+    assert_eq!(report.line_order.unwrap().as_slice(), &[1, 0, 2]);
+    match &report.document {
+        Some(document @ ParsedMRZ::MrzIcaoTd1(_)) => {
+            assert_eq!(document.line_order(), report.line_order);
+        }
+        other => panic!("Expected ParsedMRZ::MrzIcaoTd1, got {:?}", other),
+    }
+    let issues: Vec<_> = report.issues_for(MrzField::Layout).collect();
+    assert_eq!(issues.len(), 1);
+    assert_eq!(issues[0].kind, IssueKind::LinesReordered);
+    assert_eq!(issues[0].severity, Severity::Warning);
+    assert!(!report.has_errors());
+}
@@ -0,0 +1,73 @@
+use mrz_core::parser::{detect_format, parse_any};
+use mrz_core::{MRZFormat, MRZParseError, MrzIcaoCommonFields, ParsedMRZ};
+
+mod common;
+use common::assert_checksum_matches;
+
+#[test]
+fn test_detect_mrva_format() {
+    let line1 = b"V<UTOERIKSSON<<ANNA<MARIA<<<<<<<<<<<<<<<<<<<";
+    let line2 = b"L8988901C4XXX4009078F96121096ZE184226B<<<<<<";
+
+    assert_eq!(detect_format(&[line1, line2]), MRZFormat::MrzIcaoMrvA);
+}
+
+#[test]
+fn test_valid_mrva() {
+    // ICAO 9303 MRV-A sample data
+    let line1 = b"V<UTOERIKSSON<<ANNA<MARIA<<<<<<<<<<<<<<<<<<<";
+    let line2 = b"L8988901C4XXX4009078F96121096ZE184226B<<<<<<";
+
+    let result = parse_any(&[line1, line2]);
+    assert!(
+        matches!(result, Ok(ParsedMRZ::MrzIcaoMrvA(_))),
+        "Expected ParsedMRZ::MrzIcaoMrvA, got {:?}",
+        result
+    );
+    if let Ok(ParsedMRZ::MrzIcaoMrvA(mrz)) = result {
+        assert_eq!(mrz.document_code(), b"V<");
+        assert_eq!(mrz.issuing_state(), b"UTO");
+        assert_eq!(mrz.nationality(), b"XXX");
+        assert_eq!(mrz.document_number(), "L8988901C");
+        assert_eq!(mrz.birth_date(), b"400907");
+        assert_eq!(mrz.expiry_date(), b"961210");
+        assert_eq!(mrz.sex(), b'F');
+        assert_eq!(mrz.surname(), "ERIKSSON");
+        assert_eq!(mrz.given_names(), "ANNA MARIA");
+        assert_eq!(mrz.optional_data1(), "6ZE184226B<<<<<<");
+        assert!(!mrz.has_final_check());
+        assert_eq!(mrz.is_final_check_valid(), None);
+        assert_checksum_matches(&mrz);
+    }
+}
+
+#[test]
+fn test_mrva_last_character_is_not_a_final_check() {
+    // A non-filler last character belongs to the optional data, not to a composite check.
+    let line1 = b"V<UTOERIKSSON<<ANNA<MARIA<<<<<<<<<<<<<<<<<<<";
+    let line2 = b"L8988901C4XXX4009078F96121096ZE184226B<<<<<7";
+
+    let result = parse_any(&[line1, line2]);
+    assert!(
+        matches!(result, Ok(ParsedMRZ::MrzIcaoMrvA(_))),
+        "Expected ParsedMRZ::MrzIcaoMrvA, got {:?}",
+        result
+    );
+    if let Ok(ParsedMRZ::MrzIcaoMrvA(mrz)) = result {
+        assert_eq!(mrz.optional_data1(), "6ZE184226B<<<<<7");
+        assert_eq!(mrz.is_final_check_valid(), None);
+    }
+}
+
+#[test]
+fn test_invalid_mrva_checksum() {
+    let line1 = b"V<UTOERIKSSON<<ANNA<MARIA<<<<<<<<<<<<<<<<<<<";
+    let line2 = b"L8988901C4XXX4009073F96121096ZE184226B<<<<<<"; // 3 instead of 8
+
+    let result = parse_any(&[line1, line2]);
+    assert!(
+        matches!(result, Err(MRZParseError::InvalidChecksumField(_, _))),
+        "Expected Err(MRZParseError::InvalidChecksumField(_, _)), got {:?}",
+        result
+    );
+}
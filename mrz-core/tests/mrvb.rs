@@ -0,0 +1,65 @@
+use mrz_core::parser::{detect_format, parse_any};
+use mrz_core::{MRZFormat, MrzIcaoCommonFields, ParsedMRZ};
+
+mod common;
+use common::assert_checksum_matches;
+
+#[test]
+fn test_detect_mrvb_format_by_document_code() {
+    let visa = [
+        &b"V<UTOERIKSSON<<ANNA<MARIA<<<<<<<<<<<"[..],
+        &b"L8988901C4XXX4009078F9612109<<<<<<<<"[..],
+    ];
+    let id_card = [
+        &b"I<UTOERIKSSON<<ANNA<MARIA<<<<<<<<<<<"[..],
+        &b"D231458907UTO7408122F1204159<<<<<<<6"[..],
+    ];
+
+    assert_eq!(detect_format(&visa), MRZFormat::MrzIcaoMrvB);
+    assert_eq!(detect_format(&id_card), MRZFormat::MrzIcaoTd2);
+}
+
+#[test]
+fn test_valid_mrvb() {
+    // ICAO 9303 MRV-B sample data
+    let line1 = b"V<UTOERIKSSON<<ANNA<MARIA<<<<<<<<<<<";
+    let line2 = b"L8988901C4XXX4009078F9612109<<<<<<<<";
+
+    let result = parse_any(&[line1, line2]);
+    assert!(
+        matches!(result, Ok(ParsedMRZ::MrzIcaoMrvB(_))),
+        "Expected ParsedMRZ::MrzIcaoMrvB, got {:?}",
+        result
+    );
+    if let Ok(ParsedMRZ::MrzIcaoMrvB(mrz)) = result {
+        assert_eq!(mrz.document_code(), b"V<");
+        assert_eq!(mrz.issuing_state(), b"UTO");
+        assert_eq!(mrz.nationality(), b"XXX");
+        assert_eq!(mrz.document_number(), "L8988901C");
+        assert_eq!(mrz.birth_date(), b"400907");
+        assert_eq!(mrz.expiry_date(), b"961210");
+        assert_eq!(mrz.sex(), b'F');
+        assert_eq!(mrz.surname(), "ERIKSSON");
+        assert_eq!(mrz.given_names(), "ANNA MARIA");
+        assert_eq!(mrz.optional_data1(), "<<<<<<<<");
+        assert!(!mrz.has_final_check());
+        assert_checksum_matches(&mrz);
+    }
+}
+
+#[test]
+fn test_mrvb_last_character_is_optional_data() {
+    let line1 = b"V<UTOERIKSSON<<ANNA<MARIA<<<<<<<<<<<";
+    let line2 = b"L8988901C4XXX4009078F9612109AB12<<<3";
+
+    let result = parse_any(&[line1, line2]);
+    assert!(
+        matches!(result, Ok(ParsedMRZ::MrzIcaoMrvB(_))),
+        "Expected ParsedMRZ::MrzIcaoMrvB, got {:?}",
+        result
+    );
+    if let Ok(ParsedMRZ::MrzIcaoMrvB(mrz)) = result {
+        assert_eq!(mrz.optional_data1(), "AB12<<<3");
+        assert_eq!(mrz.is_final_check_valid(), None);
+    }
+}
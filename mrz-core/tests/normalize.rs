@@ -0,0 +1,227 @@
+use mrz_core::normalize::{
+    normalize, normalize_lines, MAX_NORMALIZATION_CHANGES, MAX_NORMALIZED_LINE_LEN,
+};
+use mrz_core::parser;
+use mrz_core::{
+    parse_any, parse_any_with, ErrorPosition, MRZParseError, MrzIcaoCommonFields, Normalization,
+    NormalizationKind, ParseOptions, ParsedMRZ,
+};
+
+const TD3: &str = "P<UTOERIKSSON<<ANNA<MARIA<<<<<<<<<<<<<<<<<<<\n\
+                   L898902C36UTO7408122F1204159ZE184226B<<<<<10";
+
+#[test]
+fn test_canonical_input_is_unchanged() {
+    let normalized = normalize(TD3).unwrap();
+    assert!(normalized.is_unchanged());
+    assert_eq!(normalized.lines.len(), 2);
+    assert_eq!(
+        normalized.lines[0].as_str(),
+        "P<UTOERIKSSON<<ANNA<MARIA<<<<<<<<<<<<<<<<<<<"
+    );
+}
+
+#[test]
+fn test_lowercase_and_fillers() {
+    let normalized = normalize_lines(["p«utoEriksson  anna‹maria"]).unwrap();
+    assert_eq!(normalized.lines[0].as_str(), "P<UTOERIKSSON<<ANNA<MARIA");
+    assert_eq!(
+        normalized.changes[0],
+        Normalization {
+            line: 0,
+            column: 0,
+            original: 'p',
+            replacement: Some(b'P'),
+            kind: NormalizationKind::Uppercased,
+        }
+    );
+    assert_eq!(
+        normalized.changes[1],
+        Normalization {
+            line: 0,
+            column: 1,
+            original: '«',
+            replacement: Some(b'<'),
+            kind: NormalizationKind::Filler,
+        }
+    );
+    let fillers = normalized
+        .changes
+        .iter()
+        .filter(|c| c.kind == NormalizationKind::Filler)
+        .count();
+    assert_eq!(fillers, 4);
+}
+
+#[test]
+fn test_cyrillic_and_greek_lookalikes() {
+    // Cyrillic Р and О, Greek Α.
+    let normalized = normalize_lines(["Р<UTОERIKSSΟN<<ΑNNA"]).unwrap();
+    assert_eq!(normalized.lines[0].as_str(), "P<UTOERIKSSON<<ANNA");
+    assert_eq!(normalized.changes.len(), 4);
+    assert!(normalized
+        .changes
+        .iter()
+        .all(|c| c.kind == NormalizationKind::Lookalike));
+    // Columns are byte offsets: the Cyrillic `Р` takes two bytes.
+    assert_eq!(normalized.changes[1].column, 5);
+    assert_eq!(normalized.changes[1].original, 'О');
+}
+
+#[test]
+fn test_trimming_and_line_endings() {
+    let normalized = normalize("\r\n  P<UTO\r\n\n L8989 \r\n").unwrap();
+    assert_eq!(normalized.lines.len(), 2);
+    assert_eq!(normalized.lines[0].as_str(), "P<UTO");
+    assert_eq!(normalized.lines[1].as_str(), "L8989");
+
+    let trimmed: Vec<(usize, usize, char)> = normalized
+        .changes
+        .iter()
+        .filter(|c| c.kind == NormalizationKind::Trimmed)
+        .map(|c| (c.line, c.column, c.original))
+        .collect();
+    assert_eq!(
+        trimmed,
+        [
+            (0, 0, ' '),
+            (0, 1, ' '),
+            (0, 7, '\r'),
+            (1, 0, ' '),
+            (1, 6, ' '),
+            (1, 7, '\r')
+        ]
+    );
+    assert!(normalized.changes.iter().all(|c| c.replacement.is_none()));
+}
+
+#[test]
+fn test_boarding_pass_is_only_trimmed() {
+    let line = "M1DESMARAIS/LUC       EABC123 YULFRAAC 0834 326J001A0025 100\r";
+    let normalized = normalize(line).unwrap();
+    assert_eq!(normalized.lines[0].as_str(), line.trim_end());
+    assert_eq!(normalized.changes.len(), 1);
+}
+
+#[test]
+fn test_trailing_spaces_pad_to_the_longest_line() {
+    let normalized = normalize("P<UTO   \r\nL8989   \nL8989012  \r\n").unwrap();
+    assert_eq!(normalized.lines[0].as_str(), "P<UTO<<<");
+    assert_eq!(normalized.lines[1].as_str(), "L8989<<<");
+    assert_eq!(normalized.lines[2].as_str(), "L8989012");
+
+    let changes: Vec<(usize, usize, NormalizationKind)> = normalized
+        .changes
+        .iter()
+        .map(|c| (c.line, c.column, c.kind))
+        .collect();
+    assert_eq!(
+        changes,
+        [
+            (0, 5, NormalizationKind::Filler),
+            (0, 6, NormalizationKind::Filler),
+            (0, 7, NormalizationKind::Filler),
+            (0, 8, NormalizationKind::Trimmed),
+            (1, 5, NormalizationKind::Filler),
+            (1, 6, NormalizationKind::Filler),
+            (1, 7, NormalizationKind::Filler),
+            (2, 8, NormalizationKind::Trimmed),
+            (2, 9, NormalizationKind::Trimmed),
+            (2, 10, NormalizationKind::Trimmed),
+        ]
+    );
+}
+
+#[test]
+fn test_parse_with_trailing_spaces_for_fillers() {
+    let options = ParseOptions {
+        normalize_input: true,
+        ..ParseOptions::default()
+    };
+    let td1: [&[u8]; 3] = [
+        b"I<UTOD231458907               ",
+        b"7408122F1204159UTO<<<<<<<<<<<6",
+        b"ERIKSSON<<ANNA<MARIA          ",
+    ];
+    match parser::parse_any_with(&td1, &options) {
+        Ok(ParsedMRZ::MrzIcaoTd1(mrz)) => {
+            assert_eq!(mrz.document_number(), "D23145890");
+            assert_eq!(mrz.optional_data1(), "<<<<<<<<<<<<<<<");
+            assert_eq!(mrz.is_final_check_valid(), Some(true));
+        }
+        other => panic!("Expected ParsedMRZ::MrzIcaoTd1, got {:?}", other),
+    }
+
+    let td3: [&[u8]; 2] = [
+        b"P<UTOERIKSSON<<ANNA<MARIA                   \r",
+        b"L898902C36UTO7408122F1204159ZE184226B<<<<<10\r",
+    ];
+    match parser::parse_any_with(&td3, &options) {
+        Ok(ParsedMRZ::MrzIcaoTd3(mrz)) => {
+            assert_eq!(mrz.surname(), "ERIKSSON");
+            assert_eq!(mrz.given_names(), "ANNA MARIA");
+            assert_eq!(mrz.is_final_check_valid(), Some(true));
+        }
+        other => panic!("Expected ParsedMRZ::MrzIcaoTd3, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_overflowing_changes_are_flagged() {
+    let lowercase = "x".repeat(MAX_NORMALIZATION_CHANGES + 1);
+    let normalized = normalize(&lowercase).unwrap();
+    assert!(normalized.truncated);
+    assert_eq!(normalized.changes.len(), MAX_NORMALIZATION_CHANGES);
+
+    assert!(!normalize(TD3).unwrap().truncated);
+}
+
+#[test]
+fn test_too_many_lines() {
+    assert_eq!(
+        normalize("A\nB\nC\nDD").unwrap_err(),
+        MRZParseError::InvalidLength(Some(ErrorPosition::new(3, 0, 2)))
+    );
+}
+
+#[test]
+fn test_too_long_line_spans_the_line() {
+    let long = "<".repeat(MAX_NORMALIZED_LINE_LEN + 1);
+    assert_eq!(
+        normalize(&format!("P<UTO\n{long}")).unwrap_err(),
+        MRZParseError::InvalidLength(Some(ErrorPosition::new(1, 0, long.len())))
+    );
+}
+
+#[test]
+fn test_parse_with_normalization() {
+    let raw = "  p<utoeriksson<<anna<maria<<<<<<<<<<<<<<<<<<<\r\n\
+               l898902c36utо7408122f1204159ze184226b«««««10\r\n";
+    let options = ParseOptions {
+        normalize_input: true,
+        ..ParseOptions::default()
+    };
+
+    assert!(parse_any(raw).is_err());
+    let result = parse_any_with(raw, &options);
+    assert!(
+        matches!(result, Ok(ParsedMRZ::MrzIcaoTd3(_))),
+        "Expected ParsedMRZ::MrzIcaoTd3, got {:?}",
+        result
+    );
+    if let Ok(ParsedMRZ::MrzIcaoTd3(mrz)) = result {
+        assert_eq!(mrz.document_number(), "L898902C3");
+        assert_eq!(mrz.surname(), "ERIKSSON");
+        assert_eq!(mrz.is_final_check_valid(), Some(true));
+    }
+
+    let lines: Vec<&[u8]> = raw.lines().map(str::as_bytes).collect();
+    assert!(matches!(
+        parser::parse_any_with(&lines, &options),
+        Ok(ParsedMRZ::MrzIcaoTd3(_))
+    ));
+    assert!(matches!(
+        parser::parse_any_with(&lines, &ParseOptions::lenient()),
+        Ok(ParsedMRZ::MrzIcaoTd3(_))
+    ));
+}
@@ -1,5 +1,11 @@
 use heapless::String;
-use mrz_core::ocr::{ocr_permutations, ocr_substitutions};
+use mrz_core::checksum::compute_checksum;
+use mrz_core::confusion::{confusions_for, IcaoOcrB};
+use mrz_core::ocr::{
+    ocr_permutations, ocr_permutations_for, ocr_permutations_with, ocr_substitutions,
+    ocr_substitutions_for, OcrPermutations,
+};
+use mrz_core::CharClass;
 
 #[test]
 fn test_ocr_substitutions() {
@@ -12,6 +18,108 @@ fn test_ocr_substitutions() {
     assert_eq!(ocr_substitutions('A'), []);
 }
 
+#[test]
+fn test_ocr_substitutions_for_class() {
+    assert_eq!(ocr_substitutions_for('O', CharClass::Digit), ['0']);
+    assert_eq!(ocr_substitutions_for('Z', CharClass::Digit), ['2']);
+    assert!(ocr_substitutions_for('8', CharClass::Digit).is_empty());
+    // The weighted OCR-B model also confuses `0` with the rounded `D` and `Q`, at a
+    // higher cost than `O`; substitutions are listed cheapest first.
+    assert_eq!(
+        ocr_substitutions_for('0', CharClass::Letter),
+        ['O', 'D', 'Q']
+    );
+    assert!(ocr_substitutions_for('B', CharClass::Letter).is_empty());
+    assert!(ocr_substitutions_for('<', CharClass::Letter).is_empty());
+    assert_eq!(ocr_substitutions_for('N', CharClass::Sex), ['M']);
+    assert!(ocr_substitutions_for('F', CharClass::Sex).is_empty());
+    assert_eq!(ocr_substitutions_for('S', CharClass::Alphanumeric), ['5']);
+}
+
+#[test]
+fn test_ocr_permutations_for_class() {
+    let corrected = ocr_permutations_for::<4, 16>("B8O0", CharClass::Digit, 2);
+    assert_eq!(corrected.len(), 4);
+    assert!(corrected.iter().any(|s| s == "8800"));
+    assert!(corrected
+        .iter()
+        .all(|s| s.ends_with('0') && s.contains('8')));
+}
+
+#[test]
+fn test_ocr_permutations_with_cost_order() {
+    let cost = |s: &str| -> u16 {
+        "GQ5UZ"
+            .chars()
+            .zip(s.chars())
+            .filter(|(a, b)| a != b)
+            .map(|(a, b)| {
+                confusions_for(&IcaoOcrB, a, CharClass::Alphanumeric)
+                    .iter()
+                    .find(|c| c.replacement == b)
+                    .unwrap()
+                    .cost
+            })
+            .sum()
+    };
+    let corrected = ocr_permutations_with::<5, 64>("GQ5UZ", &IcaoOcrB, CharClass::Alphanumeric, 5);
+    assert_eq!(corrected[0], "GQ5UZ");
+    assert!(corrected.windows(2).all(|w| cost(&w[0]) <= cost(&w[1])));
+    assert!(corrected.iter().any(|s| s == "60SV2"));
+
+    let mut unique = corrected.clone();
+    unique.sort_unstable();
+    assert!(unique.windows(2).all(|w| w[0] != w[1]));
+}
+
+#[test]
+fn test_ocr_permutations_with_small_capacity_keep_the_cheapest() {
+    // Collecting fewer permutations than exist keeps the cheapest ones, in order.
+    let all = ocr_permutations_with::<5, 64>("GQ5UZ", &IcaoOcrB, CharClass::Alphanumeric, 5);
+    for cheapest in [
+        ocr_permutations_with::<5, 1>("GQ5UZ", &IcaoOcrB, CharClass::Alphanumeric, 5).to_vec(),
+        ocr_permutations_with::<5, 3>("GQ5UZ", &IcaoOcrB, CharClass::Alphanumeric, 5).to_vec(),
+        ocr_permutations_with::<5, 7>("GQ5UZ", &IcaoOcrB, CharClass::Alphanumeric, 5).to_vec(),
+    ] {
+        assert_eq!(cheapest.as_slice(), &all[..cheapest.len()]);
+    }
+}
+
+#[test]
+fn test_ocr_permutations_iterator_is_exhaustive() {
+    // Well beyond the 8 permutations a fixed buffer used to hold.
+    let all: Vec<_> = OcrPermutations::<6>::new("OOSSOO", &IcaoOcrB, CharClass::Digit, 6).collect();
+    assert_eq!(all.len(), 64);
+    assert_eq!(all[0].text, "OOSSOO");
+    assert_eq!(all[63].text, "005500");
+    assert!(all.windows(2).all(|w| w[0].cost <= w[1].cost));
+
+    let mut unique: Vec<_> = all.iter().map(|p| p.text.clone()).collect();
+    unique.sort_unstable();
+    unique.dedup();
+    assert_eq!(unique.len(), 64);
+
+    let shallow = OcrPermutations::<6>::new("OOSSOO", &IcaoOcrB, CharClass::Digit, 2);
+    assert_eq!(shallow.count(), 1 + 6 + 15);
+}
+
+#[test]
+fn test_ocr_permutations_iterator_checksums() {
+    for input in ["L8989O2C3", "D2314589O", "74O8IZ", "X1S98765A"] {
+        let permutations = OcrPermutations::<9>::new(input, &IcaoOcrB, CharClass::Alphanumeric, 3);
+        for permutation in permutations {
+            assert_eq!(
+                permutation.checksum,
+                compute_checksum(permutation.text.as_bytes()),
+                "{}",
+                permutation.text
+            );
+        }
+    }
+    let mut lowercase = OcrPermutations::<3>::new("a1O", &IcaoOcrB, CharClass::Alphanumeric, 1);
+    assert_eq!(lowercase.next().unwrap().checksum, None);
+}
+
 #[test]
 fn test_ocr_permutations_single_error() {
     let input: String<9> = String::from("X5Z98765O");
@@ -0,0 +1,145 @@
+use mrz_core::confusion::{Confusion, ConfusionModel};
+use mrz_core::parser::{parse_any, parse_any_with};
+use mrz_core::{
+    ChecksumPolicy, ErrorPosition, MRZChecksumError, MRZParseError, MrzIcaoCommonFields,
+    ParseOptions, ParsedMRZ,
+};
+
+const TD3_LINE1: &[u8] = b"P<UTOERIKSSON<<ANNA<MARIA<<<<<<<<<<<<<<<<<<<";
+const TD3_LINE2: &[u8] = b"L898902C36UTO7408122F1204159ZE184226B<<<<<10";
+// Document number with the OCR confusion '0' -> 'O'.
+const TD3_LINE2_OCR: &[u8] = b"L8989O2C36UTO7408122F1204159ZE184226B<<<<<10";
+// Birth date check digit 2 replaced by 3.
+const TD3_LINE2_BAD_BIRTH_CHECK: &[u8] = b"L898902C36UTO7408123F1204159ZE184226B<<<<<10";
+
+#[test]
+fn test_default_options_match_parse_any() {
+    let lines: [&[u8]; 2] = [TD3_LINE1, TD3_LINE2_OCR];
+    let with_default = parse_any_with(&lines, &ParseOptions::default());
+    let plain = parse_any(&lines);
+    assert!(
+        matches!(
+            (&with_default, &plain),
+            (Ok(ParsedMRZ::MrzIcaoTd3(_)), Ok(ParsedMRZ::MrzIcaoTd3(_)))
+        ),
+        "Expected both to parse, got {:?} and {:?}",
+        with_default,
+        plain
+    );
+}
+
+#[test]
+fn test_strict_lengths_reject_trailing_characters() {
+    let mut long_line2 = TD3_LINE2.to_vec();
+    long_line2.push(b'<');
+    let lines: [&[u8]; 2] = [TD3_LINE1, &long_line2];
+
+    assert!(parse_any(&lines).is_ok());
+    assert_eq!(
+        parse_any_with(&lines, &ParseOptions::strict()).unwrap_err(),
+        MRZParseError::InvalidLength(Some(ErrorPosition::new(1, 44, 45)))
+    );
+}
+
+#[test]
+fn test_disabled_ocr_correction() {
+    let options = ParseOptions {
+        ocr_correction: false,
+        ..ParseOptions::default()
+    };
+
+    let result = parse_any_with(&[TD3_LINE1, TD3_LINE2_OCR], &options);
+    assert_eq!(
+        result.unwrap_err(),
+        MRZParseError::InvalidChecksumField(
+            MRZChecksumError::DocumentNumber,
+            Some(ErrorPosition::new(1, 0, 10))
+        )
+    );
+}
+
+#[test]
+fn test_zero_correction_budget() {
+    let options = ParseOptions {
+        correction_budget: 0,
+        ..ParseOptions::default()
+    };
+
+    let result = parse_any_with(&[TD3_LINE1, TD3_LINE2_OCR], &options);
+    assert_eq!(
+        result.unwrap_err(),
+        MRZParseError::InvalidChecksumField(
+            MRZChecksumError::DocumentNumber,
+            Some(ErrorPosition::new(1, 0, 10))
+        )
+    );
+}
+
+#[test]
+fn test_recorded_checksum_failures() {
+    let options = ParseOptions {
+        checksum_policy: ChecksumPolicy::Record,
+        ocr_correction: false,
+        ..ParseOptions::default()
+    };
+
+    let result = parse_any_with(&[TD3_LINE1, TD3_LINE2_BAD_BIRTH_CHECK], &options);
+    assert!(
+        matches!(result, Ok(ParsedMRZ::MrzIcaoTd3(_))),
+        "Expected ParsedMRZ::MrzIcaoTd3, got {:?}",
+        result
+    );
+    if let Ok(ParsedMRZ::MrzIcaoTd3(mrz)) = result {
+        assert_eq!(mrz.birth_date(), b"740812");
+        assert!(!mrz.is_birth_date_valid());
+        assert_eq!(mrz.birth_date.error(), Some(&MRZChecksumError::BirthDate));
+        assert!(mrz.is_document_number_valid());
+        assert!(mrz.is_expiry_date_valid());
+        assert_eq!(mrz.is_final_check_valid(), Some(false));
+    }
+}
+
+#[test]
+fn test_fatal_checksum_failures() {
+    let result = parse_any_with(
+        &[TD3_LINE1, TD3_LINE2_BAD_BIRTH_CHECK],
+        &ParseOptions::strict(),
+    );
+    assert_eq!(
+        result.unwrap_err(),
+        MRZParseError::InvalidChecksumField(
+            MRZChecksumError::BirthDate,
+            Some(ErrorPosition::new(1, 13, 20))
+        )
+    );
+}
+
+// An engine that reads `0` as `X`.
+struct CrossedZero;
+
+impl ConfusionModel for CrossedZero {
+    fn confusions(&self, c: char) -> &[Confusion] {
+        match c {
+            'X' => &[Confusion {
+                replacement: '0',
+                cost: 1,
+            }],
+            _ => &[],
+        }
+    }
+}
+
+#[test]
+fn test_custom_confusion_model() {
+    let lines: [&[u8]; 2] = [TD3_LINE1, b"L8989X2C36UTO7408122F1204159ZE184226B<<<<<10"];
+    assert!(parse_any_with(&lines, &ParseOptions::default()).is_err());
+
+    let options = ParseOptions {
+        confusion_model: &CrossedZero,
+        ..ParseOptions::default()
+    };
+    match parse_any_with(&lines, &options) {
+        Ok(ParsedMRZ::MrzIcaoTd3(mrz)) => assert_eq!(mrz.document_number(), "L898902C3"),
+        other => panic!("Expected ParsedMRZ::MrzIcaoTd3, got {:?}", other),
+    }
+}
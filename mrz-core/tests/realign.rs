@@ -0,0 +1,197 @@
+use mrz_core::parser::{parse_any, parse_any_report, parse_any_with};
+use mrz_core::realign::{realign, realign_with, LineEdit, LineEditKind};
+use mrz_core::{
+    IssueKind, MRZChecksumError, MRZParseError, MrzField, MrzIcaoCommonFields, ParseIssue,
+    ParseOptions, ParsedMRZ,
+};
+
+const TD3_LINE1: &str = "P<UTOERIKSSON<<ANNA<MARIA<<<<<<<<<<<<<<<<<<<";
+const TD3_LINE2: &str = "L898902C36UTO7408122F1204159ZE184226B<<<<<10";
+
+const TD1_LINE1: &str = "I<UTOD231458907<<<<<<<<<<<<<<<";
+const TD1_LINE2: &str = "7408122F1204159UTO<<<<<<<<<<<6";
+const TD1_LINE3: &str = "ERIKSSON<<ANNA<MARIA<<<<<<<<<<";
+
+#[test]
+fn test_dropped_filler_is_restored() {
+    let line2 = "L898902C36UTO7408122F1204159ZE184226B<<<<10";
+    let realigned = realign(&[TD3_LINE1.as_bytes(), line2.as_bytes()]).unwrap();
+
+    assert_eq!(realigned.lines[1].as_slice(), TD3_LINE2.as_bytes());
+    assert_eq!(realigned.failed_checks, 0);
+    assert_eq!(
+        realigned.edits.as_slice(),
+        &[LineEdit {
+            line: 1,
+            column: 41,
+            kind: LineEditKind::Inserted,
+            character: b'<',
+            count: 1,
+        }]
+    );
+}
+
+#[test]
+fn test_duplicated_character_is_removed() {
+    let line2 = "L898902C36UTO77408122F1204159ZE184226B<<<<<10";
+    let realigned = realign(&[TD3_LINE1.as_bytes(), line2.as_bytes()]).unwrap();
+
+    assert_eq!(realigned.lines[1].as_slice(), TD3_LINE2.as_bytes());
+    assert_eq!(realigned.edits.len(), 1);
+    assert_eq!(realigned.edits[0].kind, LineEditKind::Deleted);
+    assert_eq!(realigned.edits[0].character, b'7');
+}
+
+#[test]
+fn test_dropped_filler_inside_field_is_restored() {
+    // The filler ending an eight-character document number is lost, moving the check
+    // digit into the field.
+    let line1 = "I<UTOD23145897<<<<<<<<<<<<<<<";
+    let realigned =
+        realign(&[line1.as_bytes(), TD1_LINE2.as_bytes(), TD1_LINE3.as_bytes()]).unwrap();
+
+    assert_eq!(
+        realigned.lines[0].as_slice(),
+        b"I<UTOD2314589<7<<<<<<<<<<<<<<<"
+    );
+    assert_eq!(realigned.edits[0].column, 13);
+    assert_eq!(realigned.failed_checks, 0);
+}
+
+#[test]
+fn test_shifted_span_is_restored() {
+    // Same length, but a filler moved past the final check digit.
+    let line2 = "7408122F1204159UTO<<<<<<<<<<6<";
+    let realigned =
+        realign(&[TD1_LINE1.as_bytes(), line2.as_bytes(), TD1_LINE3.as_bytes()]).unwrap();
+
+    assert_eq!(realigned.lines[1].as_slice(), TD1_LINE2.as_bytes());
+    assert_eq!(realigned.edits.len(), 2);
+}
+
+#[test]
+fn test_valid_lines_are_unchanged() {
+    let realigned = realign(&[TD3_LINE1.as_bytes(), TD3_LINE2.as_bytes()]).unwrap();
+    assert!(realigned.edits.is_empty());
+
+    // Short name lines are padded as a single edit.
+    let realigned = realign(&[b"P<UTOERIKSSON<<ANNA<MARIA", TD3_LINE2.as_bytes()]).unwrap();
+    assert_eq!(realigned.lines[0].as_slice(), TD3_LINE1.as_bytes());
+    assert_eq!(realigned.edits.len(), 1);
+    assert_eq!(realigned.edits[0].count, 19);
+}
+
+#[test]
+fn test_parse_with_realignment() {
+    let line2 = "L898902C36UTO7408122F1204159ZE184226B<<<10";
+    let lines = [TD3_LINE1.as_bytes(), line2.as_bytes()];
+    assert!(parse_any(&lines).is_err());
+
+    match parse_any_with(&lines, &ParseOptions::lenient()) {
+        Ok(ParsedMRZ::MrzIcaoTd3(td3)) => {
+            assert!(td3.optional_data1().starts_with("ZE184226B"));
+            assert_eq!(td3.is_final_check_valid(), Some(true));
+        }
+        other => panic!("Expected ParsedMRZ::MrzIcaoTd3, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_single_wrong_check_digit_is_not_realigned() {
+    // Only the final check digit is wrong (6 instead of 0); shifting the line would push
+    // it out of place instead.
+    let line2 = "L898902C36UTO7408122F1204159ZE184226B<<<<<16";
+    let lines = [TD3_LINE1.as_bytes(), line2.as_bytes()];
+    let realigned = realign(&lines).unwrap();
+    assert!(realigned.edits.is_empty());
+    assert_eq!(realigned.failed_checks, 1);
+
+    let options = ParseOptions {
+        realign_lines: true,
+        ocr_correction: false,
+        ..ParseOptions::default()
+    };
+    assert!(matches!(
+        parse_any_with(&lines, &options),
+        Err(MRZParseError::InvalidChecksumField(
+            MRZChecksumError::Final,
+            _
+        ))
+    ));
+
+    for composite in ["3", "9"] {
+        let line2 = format!("7408122F1204159UTO<<<<<<<<<<<{composite}");
+        let lines = [TD1_LINE1.as_bytes(), line2.as_bytes(), TD1_LINE3.as_bytes()];
+        assert!(realign(&lines).unwrap().edits.is_empty());
+
+        let options = ParseOptions {
+            realign_lines: true,
+            ..ParseOptions::default()
+        };
+        match parse_any_with(&lines, &options) {
+            Ok(ParsedMRZ::MrzIcaoTd1(td1)) => {
+                assert_eq!(td1.optional_data2(), "<<<<<<<<<<<");
+                assert_ne!(td1.is_final_check_valid(), None);
+            }
+            Err(MRZParseError::InvalidChecksumField(MRZChecksumError::Final, _)) => {}
+            other => panic!("Unexpected result {:?}", other),
+        }
+    }
+}
+
+#[test]
+fn test_filler_check_digit_requires_filler_data() {
+    // The composite of this line is 0, but a `<` only stands for an absent check digit
+    // when the data it covers is all fillers.
+    let line2 = "L898902C36UTO7408122F1204159ZE184226B<<<<<1<";
+    let realigned = realign(&[TD3_LINE1.as_bytes(), line2.as_bytes()]).unwrap();
+    assert_eq!(realigned.failed_checks, 1);
+    assert!(realigned.edits.is_empty());
+}
+
+#[test]
+fn test_strict_lengths_keep_line_lengths() {
+    let line2 = "L898902C36UTO7408122F1204159ZE184226B<<<<10";
+    let lines = [TD3_LINE1.as_bytes(), line2.as_bytes()];
+    let options = ParseOptions {
+        strict_lengths: true,
+        realign_lines: true,
+        ..ParseOptions::default()
+    };
+    assert!(realign_with(&lines, &options).is_none());
+    assert!(matches!(
+        parse_any_with(&lines, &options),
+        Err(MRZParseError::InvalidLength(_))
+    ));
+
+    // Shifting a span keeps the lengths.
+    let line2 = "7408122F1204159UTO<<<<<<<<<<6<";
+    let lines = [TD1_LINE1.as_bytes(), line2.as_bytes(), TD1_LINE3.as_bytes()];
+    assert_eq!(realign_with(&lines, &options).unwrap().edits.len(), 2);
+}
+
+#[test]
+fn test_report_lists_realignment_edits() {
+    let line2 = "L898902C36UTO7408122F1204159ZE184226B<<<<10";
+    let options = ParseOptions {
+        realign_lines: true,
+        ..ParseOptions::default()
+    };
+    let report = parse_any_report(&[TD3_LINE1.as_bytes(), line2.as_bytes()], &options);
+
+    assert_eq!(
+        report.issues.as_slice(),
+        &[ParseIssue::warning(
+            MrzField::Layout,
+            IssueKind::LinesRealigned
+        )]
+    );
+    assert_eq!(report.realignment.len(), 1);
+    assert_eq!(report.realignment[0].column, 41);
+}
+
+#[test]
+fn test_unknown_layout() {
+    assert!(realign(&[b"P<UTO"]).is_none());
+    assert!(realign(&[b"IDFRADOUEL", b"0506932020438CHRISTIANE<<NI2906209F3"]).is_none());
+}
@@ -0,0 +1,105 @@
+use mrz_core::parser::parse_any_report;
+use mrz_core::{
+    IssueKind, MrzField, MrzIcaoCommonFields, ParseIssue, ParseOptions, ParsedMRZ, Severity,
+};
+
+const TD3_LINE1: &[u8] = b"P<UTOERIKSSON<<ANNA<MARIA<<<<<<<<<<<<<<<<<<<";
+
+#[test]
+fn test_report_for_valid_document_is_clean() {
+    let report = parse_any_report(
+        &[TD3_LINE1, b"L898902C36UTO7408122F1204159ZE184226B<<<<<10"],
+        &ParseOptions::default(),
+    );
+    assert!(report.is_clean(), "Unexpected issues: {:?}", report.issues);
+    assert!(matches!(report.document, Some(ParsedMRZ::MrzIcaoTd3(_))));
+}
+
+#[test]
+fn test_report_lists_every_failed_check() {
+    // Document number, birth date and composite check digits are all wrong.
+    let line2 = b"L898902C37UTO7408123F1204159ZE184226B<<<<<11";
+
+    let report = parse_any_report(&[TD3_LINE1, line2], &ParseOptions::strict());
+    assert_eq!(
+        report.issues.as_slice(),
+        &[
+            ParseIssue::error(MrzField::DocumentNumber, IssueKind::ChecksumMismatch),
+            ParseIssue::error(MrzField::BirthDate, IssueKind::ChecksumMismatch),
+            ParseIssue::error(MrzField::Final, IssueKind::ChecksumMismatch),
+        ]
+    );
+    assert!(report.has_errors());
+
+    match report.document {
+        Some(ParsedMRZ::MrzIcaoTd3(mrz)) => {
+            assert_eq!(mrz.document_number(), "L898902C3");
+            assert!(!mrz.is_document_number_valid());
+            assert!(!mrz.is_birth_date_valid());
+            assert!(mrz.is_expiry_date_valid());
+        }
+        other => panic!("Expected a best-effort TD3 document, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_report_warns_about_implausible_values() {
+    // Sex 'Q' and expiry month 13 with a matching check digit.
+    let line2 = b"L898902C36UTO7408122Q1213153ZE184226B<<<<<<<";
+
+    let report = parse_any_report(&[TD3_LINE1, line2], &ParseOptions::default());
+    assert!(
+        !report.has_errors(),
+        "Unexpected errors: {:?}",
+        report.issues
+    );
+    assert_eq!(
+        report.issues_for(MrzField::Sex).next(),
+        Some(&ParseIssue::warning(MrzField::Sex, IssueKind::InvalidValue))
+    );
+    assert_eq!(
+        report
+            .issues_for(MrzField::ExpiryDate)
+            .map(|i| i.severity)
+            .next(),
+        Some(Severity::Warning)
+    );
+}
+
+#[test]
+fn test_report_for_unparseable_input() {
+    let report = parse_any_report(&[b"THISISNOTVALIDMRZDATA"], &ParseOptions::default());
+    assert!(report.document.is_none());
+    assert_eq!(
+        report.issues.as_slice(),
+        &[ParseIssue::error(
+            MrzField::Layout,
+            IssueKind::UnknownFormat
+        )]
+    );
+}
+
+#[test]
+fn test_report_lists_normalization_changes() {
+    let lines: [&[u8]; 2] = [TD3_LINE1, b"l898902C36UTO7408122F1204159ZE184226B<<<<<10"];
+    let options = ParseOptions {
+        normalize_input: true,
+        ..ParseOptions::default()
+    };
+
+    let report = parse_any_report(&lines, &options);
+    assert_eq!(
+        report.issues.as_slice(),
+        &[ParseIssue::warning(
+            MrzField::Layout,
+            IssueKind::InputNormalized
+        )]
+    );
+    assert_eq!(report.normalization.len(), 1);
+    assert_eq!(
+        (report.normalization[0].line, report.normalization[0].column),
+        (1, 0)
+    );
+    assert!(!report.normalization_truncated);
+    assert!(!report.has_errors());
+}
@@ -0,0 +1,89 @@
+use mrz_core::parser::{detect_format, parse_any};
+use mrz_core::{DocumentKind, MRZFormat, MRZParseError, ParsedMRZ};
+
+const LINE1: &[u8] = b"AAA001D<<";
+const LINE2: &[u8] = b"FACHE305142267001940928<<<<<<<";
+const LINE3: &[u8] = b"MARCHAND<<FABIENNE<<<<<<<<<<<<";
+
+#[test]
+fn test_detect_swiss_driving_licence_format() {
+    assert_eq!(
+        detect_format(&[LINE1, LINE2, LINE3]),
+        MRZFormat::MrzSwissDrivingLicence
+    );
+    assert_eq!(
+        detect_format(&[b"AAA001D<", LINE2, LINE3]),
+        MRZFormat::Unknown
+    );
+    assert_eq!(
+        detect_format(&[LINE1, b"IDCHE305142267001940928<<<<<<<", LINE3]),
+        MRZFormat::Unknown
+    );
+}
+
+#[test]
+fn test_valid_swiss_driving_licence() {
+    let result = parse_any(&[LINE1, LINE2, LINE3]);
+    assert!(
+        matches!(result, Ok(ParsedMRZ::MrzSwissDrivingLicence(_))),
+        "Expected ParsedMRZ::MrzSwissDrivingLicence, got {:?}",
+        result
+    );
+    if let Ok(ParsedMRZ::MrzSwissDrivingLicence(dl)) = result {
+        assert_eq!(dl.licence_number(), "AAA001");
+        assert_eq!(dl.language_code, b'D');
+        assert_eq!(&dl.document_code, b"FA");
+        assert_eq!(&dl.issuing_state, b"CHE");
+        assert_eq!(dl.pin_code(), "305142267");
+        assert_eq!(dl.version(), "001");
+        assert_eq!(dl.birth_date(), b"940928");
+        assert!(dl.is_birth_date_valid());
+        assert_eq!(dl.surname(), "MARCHAND");
+        assert_eq!(dl.given_names(), "FABIENNE");
+        assert_eq!(dl.document_kind(), DocumentKind::DrivingLicence);
+    }
+}
+
+#[test]
+fn test_swiss_driving_licence_malformed_birth_date() {
+    let line2 = b"FACHE305142267001941328<<<<<<<";
+
+    let result = parse_any(&[LINE1, line2, LINE3]);
+    if let Ok(ParsedMRZ::MrzSwissDrivingLicence(dl)) = result {
+        assert!(!dl.is_birth_date_valid());
+    } else {
+        panic!(
+            "Expected ParsedMRZ::MrzSwissDrivingLicence, got {:?}",
+            result
+        );
+    }
+}
+
+#[test]
+fn test_swiss_driving_licence_birth_date_must_be_a_calendar_date() {
+    for (birth_date, expected) in [
+        (b"940931", false),
+        (b"950229", false),
+        (b"960229", true),
+        (b"941231", true),
+    ] {
+        let mut line2 = *b"FACHE305142267001940928<<<<<<<";
+        line2[17..23].copy_from_slice(birth_date);
+
+        match parse_any(&[LINE1, &line2, LINE3]) {
+            Ok(ParsedMRZ::MrzSwissDrivingLicence(dl)) => {
+                assert_eq!(dl.is_birth_date_valid(), expected, "{:?}", dl.birth_date());
+            }
+            other => panic!(
+                "Expected ParsedMRZ::MrzSwissDrivingLicence, got {:?}",
+                other
+            ),
+        }
+    }
+}
+
+#[test]
+fn test_swiss_driving_licence_invalid_length() {
+    let result = parse_any(&[LINE1, b"FACHE305142267001940928<<<<<<", LINE3]);
+    assert_eq!(result.unwrap_err(), MRZParseError::UnknownFormat);
+}
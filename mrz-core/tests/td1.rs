@@ -1,4 +1,8 @@
-use mrz_core::{parser::parse_any, MRZParseError, MrzIcaoCommonFields, ParsedMRZ};
+use mrz_core::parser::{parse_any, parse_any_report, parse_any_with};
+use mrz_core::{
+    ChecksumPolicy, IssueKind, MRZParseError, MrzField, MrzIcaoCommonFields, ParseOptions,
+    ParsedMRZ,
+};
 
 mod common;
 use common::assert_checksum_matches;
@@ -45,8 +49,8 @@ fn test_invalid_td1_sample() {
     let lines_ref: [&[u8]; 3] = [&lines[0][..], &lines[1][..], &lines[2][..]];
     let result = parse_any(&lines_ref);
     assert!(
-        matches!(result, Err(MRZParseError::InvalidChecksumField(_))),
-        "Expected Err(MRZParseError::InvalidChecksumField(_)), got {:?}",
+        matches!(result, Err(MRZParseError::InvalidChecksumField(_, _))),
+        "Expected Err(MRZParseError::InvalidChecksumField(_, _)), got {:?}",
         result
     );
 }
@@ -218,3 +222,90 @@ fn test_td1_with_ocr_error_in_expiry_date() {
         );
     }
 }
+
+#[test]
+fn test_td1_composite_check_digit_covers_optional_data1() {
+    let line1: &[u8] = b"I<UTOD231458907ABC1234567<<<<<";
+    let line3: &[u8] = b"ERIKSSON<<ANNA<MARIA<<<<<<<<<<";
+    let options = ParseOptions {
+        ocr_correction: false,
+        checksum_policy: ChecksumPolicy::Record,
+        ..ParseOptions::default()
+    };
+
+    // 7 covers the optional data of line 1; 6 would only cover the document number.
+    for (line2, expected) in [
+        (b"7408122F1204159UTO<<<<<<<<<<<7", Some(true)),
+        (b"7408122F1204159UTO<<<<<<<<<<<6", Some(false)),
+    ] {
+        match parse_any_with(&[line1, line2, line3], &options) {
+            Ok(ParsedMRZ::MrzIcaoTd1(mrz)) => {
+                assert_eq!(mrz.optional_data1(), "ABC1234567<<<<<");
+                assert_eq!(mrz.is_final_check_valid(), expected);
+            }
+            other => panic!("Expected ParsedMRZ::MrzIcaoTd1, got {:?}", other),
+        }
+    }
+}
+
+#[test]
+fn test_td1_optional_data_corrected_by_composite_check_digit() {
+    // `2` of the optional data misread as `Z`; only the composite check digit covers it.
+    let lines: [&[u8]; 3] = [
+        b"I<UTOD231458907AB1Z34567<<<<<<",
+        b"7408122F1204159UTO<<<<<<<<<<<1",
+        b"ERIKSSON<<ANNA<MARIA<<<<<<<<<<",
+    ];
+    match parse_any_with(&lines, &ParseOptions::lenient()) {
+        Ok(ParsedMRZ::MrzIcaoTd1(mrz)) => {
+            assert_eq!(mrz.optional_data1(), "AB1Z34567<<<<<<");
+            assert_eq!(mrz.optional_data_correction(0), None);
+            assert_eq!(mrz.is_final_check_valid(), Some(false));
+        }
+        other => panic!("Expected ParsedMRZ::MrzIcaoTd1, got {:?}", other),
+    }
+
+    let options = ParseOptions {
+        correct_optional_data: true,
+        ..ParseOptions::lenient()
+    };
+    match parse_any_with(&lines, &options) {
+        Ok(ParsedMRZ::MrzIcaoTd1(mrz)) => {
+            assert_eq!(mrz.optional_data1(), "AB1234567<<<<<<");
+            assert_eq!(mrz.optional_data2(), "<<<<<<<<<<<");
+            let correction = mrz.optional_data_correction(0).unwrap();
+            assert_eq!(correction.raw(mrz.optional_data1()), "AB1Z34567<<<<<<");
+            assert_eq!(mrz.optional_data_correction(1), None);
+            // The rewrite is recorded above; the composite check digit still passes.
+            assert_eq!(mrz.is_final_check_valid(), Some(true));
+        }
+        other => panic!("Expected ParsedMRZ::MrzIcaoTd1, got {:?}", other),
+    }
+
+    let report = parse_any_report(&lines, &options);
+    let issue = report.issues_for(MrzField::OptionalData).next().unwrap();
+    assert_eq!(issue.kind, IssueKind::OptionalDataCorrected);
+}
+
+#[test]
+fn test_td1_misread_composite_check_digit_keeps_optional_data() {
+    // The composite check digit itself is misread; no optional data rewrite may
+    // claim to satisfy it.
+    for check in [b'8', b'9'] {
+        let mut line2 = *b"7408122F1204159UTO<<<<<<<<<<<1";
+        line2[29] = check;
+        let lines: [&[u8]; 3] = [
+            b"I<UTOD231458907AB1Z34567<<<<<<",
+            &line2,
+            b"ERIKSSON<<ANNA<MARIA<<<<<<<<<<",
+        ];
+        match parse_any_with(&lines, &ParseOptions::lenient()) {
+            Ok(ParsedMRZ::MrzIcaoTd1(mrz)) => {
+                assert_eq!(mrz.optional_data1(), "AB1Z34567<<<<<<");
+                assert_eq!(mrz.optional_data_correction(0), None);
+                assert_eq!(mrz.is_final_check_valid(), Some(false));
+            }
+            other => panic!("Expected ParsedMRZ::MrzIcaoTd1, got {:?}", other),
+        }
+    }
+}
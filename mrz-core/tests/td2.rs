@@ -0,0 +1,101 @@
+use mrz_core::parser::{detect_format, parse_any};
+use mrz_core::{MRZFormat, MRZParseError, MrzIcaoCommonFields, ParsedMRZ};
+
+mod common;
+use common::assert_checksum_matches;
+
+#[test]
+fn test_detect_td2_format() {
+    let line1 = b"I<UTOERIKSSON<<ANNA<MARIA<<<<<<<<<<<";
+    let line2 = b"D231458907UTO7408122F1204159<<<<<<<6";
+
+    assert_eq!(detect_format(&[line1, line2]), MRZFormat::MrzIcaoTd2);
+}
+
+#[test]
+fn test_valid_td2_with_final_check() {
+    // ICAO 9303 TD2 sample data
+    let line1 = b"I<UTOERIKSSON<<ANNA<MARIA<<<<<<<<<<<";
+    let line2 = b"D231458907UTO7408122F1204159<<<<<<<6";
+
+    let result = parse_any(&[line1, line2]);
+    assert!(
+        matches!(result, Ok(ParsedMRZ::MrzIcaoTd2(_))),
+        "Expected ParsedMRZ::MrzIcaoTd2, got {:?}",
+        result
+    );
+    if let Ok(ParsedMRZ::MrzIcaoTd2(mrz)) = result {
+        assert_eq!(mrz.document_code(), b"I<");
+        assert_eq!(mrz.issuing_state(), b"UTO");
+        assert_eq!(mrz.nationality(), b"UTO");
+        assert_eq!(mrz.document_number(), "D23145890");
+        assert_eq!(mrz.birth_date(), b"740812");
+        assert_eq!(mrz.expiry_date(), b"120415");
+        assert_eq!(mrz.sex(), b'F');
+        assert_eq!(mrz.surname(), "ERIKSSON");
+        assert_eq!(mrz.given_names(), "ANNA MARIA");
+        assert_eq!(mrz.optional_data1(), "<<<<<<<");
+        assert_eq!(mrz.optional_data2(), "");
+        assert_eq!(
+            mrz.is_final_check_valid(),
+            Some(true),
+            "Final check digit should be present and correct"
+        );
+        assert_checksum_matches(&mrz);
+    }
+}
+
+#[test]
+fn test_td2_with_missing_final_check_digit() {
+    let line1 = b"I<UTOERIKSSON<<ANNA<MARIA<<<<<<<<<<<";
+    let line2 = b"D231458907UTO7408122F1204159<<<<<<<<";
+
+    let result = parse_any(&[line1, line2]);
+    assert!(
+        matches!(result, Ok(ParsedMRZ::MrzIcaoTd2(_))),
+        "Expected ParsedMRZ::MrzIcaoTd2 with missing final check, got {:?}",
+        result
+    );
+    if let Ok(ParsedMRZ::MrzIcaoTd2(mrz)) = result {
+        assert_eq!(mrz.is_final_check_valid(), None);
+    }
+}
+
+#[test]
+fn test_invalid_td2_final_checksum() {
+    let line1 = b"I<UTOERIKSSON<<ANNA<MARIA<<<<<<<<<<<";
+    let line2 = b"D231458907UTO7408122F1204159<<<<<<<5";
+
+    let result = parse_any(&[line1, line2]);
+    assert!(
+        matches!(result, Err(MRZParseError::InvalidChecksumField(_, _))),
+        "Expected Err(MRZParseError::InvalidChecksumField(_, _)), got {:?}",
+        result
+    );
+}
+
+#[test]
+fn test_td2_with_ocr_error_in_birth_date() {
+    let line1 = b"I<UTOERIKSSON<<ANNA<MARIA<<<<<<<<<<<";
+    let line2 = b"D231458907UTO74O8122F1204159<<<<<<<6"; // 'O' instead of '0' in birth date
+
+    let result = parse_any(&[line1, line2]);
+    assert!(
+        matches!(result, Ok(ParsedMRZ::MrzIcaoTd2(_))),
+        "Expected successful parse with corrected birth date OCR error, got {:?}",
+        result
+    );
+    if let Ok(ParsedMRZ::MrzIcaoTd2(mrz)) = result {
+        assert_eq!(mrz.birth_date(), b"740812");
+        assert!(mrz.is_birth_date_valid());
+    }
+}
+
+#[test]
+fn test_td2_invalid_length() {
+    let line1 = b"I<UTOERIKSSON<<ANNA<MARIA<<<<<<<<<<<";
+    let line2 = b"D231458907UTO7408122F1204159<<<<<<6";
+
+    let result = parse_any(&[line1, line2]);
+    assert!(matches!(result, Err(MRZParseError::UnknownFormat)));
+}
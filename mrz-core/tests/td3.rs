@@ -1,6 +1,10 @@
-use mrz_core::parser::parse_any;
-use mrz_core::{MRZChecksumError, MRZParseError};
-use mrz_core::{MrzIcaoCommonFields, ParsedMRZ};
+#![allow(clippy::bool_assert_comparison)]
+
+use mrz_core::parser::{parse_any, parse_any_report, parse_any_with};
+use mrz_core::{
+    IssueKind, MRZChecksumError, MRZParseError, MrzField, MrzIcaoCommonFields, ParseOptions,
+    ParsedMRZ,
+};
 
 #[test]
 fn test_valid_td3_wo_final_checksum() {
@@ -44,6 +48,21 @@ fn test_valid_td3_with_final_checksum() {
     }
 }
 
+#[test]
+fn test_td3_nationality_follows_document_number() {
+    // The nationality is read from line 2 columns 11 to 13, not from the birth date.
+    let line1 = b"P<UTOERIKSSON<<ANNA<MARIA<<<<<<<<<<<<<<<<<<<";
+    let line2 = b"L898902C36D<<7408122F1204159ZE184226B<<<<<10";
+
+    match parse_any(&[line1, line2]) {
+        Ok(ParsedMRZ::MrzIcaoTd3(td3)) => {
+            assert_eq!(td3.nationality(), b"D<<");
+            assert_eq!(td3.issuing_state(), b"UTO");
+        }
+        other => panic!("Expected ParsedMRZ::MrzIcaoTd3, got {:?}", other),
+    }
+}
+
 #[test]
 fn test_invalid_td3_final_checksum() {
     // Intentionally corrupted check digit in document number
@@ -226,3 +245,101 @@ fn test_td3_document_number_with_multiple_ocr_errors_corrected() {
         assert_eq!(mrz.is_final_check_valid(), Some(true));
     }
 }
+
+#[test]
+fn test_td3_unchecked_fields_are_class_normalized() {
+    // Digits read in place of letters in the country codes and the surname.
+    let line1 = b"P<UT0ERIKSS0N<<ANNA<MARIA<<<<<<<<<<<<<<<<<<<";
+    let line2 = b"L898902C36UT07408122F1204159ZE184226B<<<<<10";
+
+    match parse_any(&[line1, line2]) {
+        Ok(ParsedMRZ::MrzIcaoTd3(mrz)) => {
+            assert_eq!(mrz.issuing_state(), b"UTO");
+            assert_eq!(mrz.nationality(), b"UTO");
+            assert_eq!(mrz.surname().as_str(), "ERIKSSON");
+            assert_eq!(mrz.sex(), b'F');
+        }
+        other => panic!("Expected ParsedMRZ::MrzIcaoTd3, got {:?}", other),
+    }
+
+    // Without OCR correction the fields are kept as read.
+    match parse_any_with(&[line1, line2], &ParseOptions::strict()) {
+        Ok(ParsedMRZ::MrzIcaoTd3(mrz)) => assert_eq!(mrz.nationality(), b"UT0"),
+        other => panic!("Expected ParsedMRZ::MrzIcaoTd3, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_td3_misread_check_digits_are_repaired() {
+    // `Z` read for the birth date check digit `2`, `O` for the composite check digit `0`.
+    let line1 = b"P<UTOERIKSSON<<ANNA<MARIA<<<<<<<<<<<<<<<<<<<";
+    let line2 = b"L898902C36UTO740812ZF1204159ZE184226B<<<<<1O";
+
+    match parse_any(&[line1, line2]) {
+        Ok(ParsedMRZ::MrzIcaoTd3(mrz)) => {
+            assert_eq!(mrz.birth_date(), b"740812");
+            assert_eq!(mrz.birth_date.check_digit(), Some(b'2'));
+            assert_eq!(mrz.birth_date.raw_check_digit(), Some(b'Z'));
+            assert!(!mrz.expiry_date.is_check_digit_corrected());
+            assert_eq!(mrz.is_final_check_valid(), Some(true));
+            assert_eq!(
+                mrz.corrected_check_digits().as_slice(),
+                &[MRZChecksumError::BirthDate, MRZChecksumError::Final]
+            );
+        }
+        other => panic!("Expected ParsedMRZ::MrzIcaoTd3, got {:?}", other),
+    }
+
+    let report = parse_any_report(&[line1, line2], &ParseOptions::default());
+    assert!(!report.has_errors());
+    let issue = report.issues_for(MrzField::BirthDate).next().unwrap();
+    assert_eq!(issue.kind, IssueKind::CheckDigitCorrected);
+}
+
+#[test]
+fn test_td3_check_digit_below_digits_does_not_panic() {
+    let line1 = b"P<UTOERIKSSON<<ANNA<MARIA<<<<<<<<<<<<<<<<<<<";
+    let line2 = b"L898902C36UTO740812!F1204159ZE184226B<<<<<10";
+
+    assert!(matches!(
+        parse_any_with(&[line1, line2], &ParseOptions::strict()),
+        Err(MRZParseError::InvalidCharacter { found: b'!', .. })
+    ));
+}
+
+#[test]
+fn test_td3_optional_data_corrected_by_composite_check_digit() {
+    let line1 = b"P<UTOERIKSSON<<ANNA<MARIA<<<<<<<<<<<<<<<<<<<";
+    // `B` of the personal number misread as `8`.
+    let line2 = b"L898902C36UTO7408122F1204159ZE1842268<<<<<10";
+
+    match parse_any_with(&[line1, line2], &ParseOptions::lenient()) {
+        Ok(ParsedMRZ::MrzIcaoTd3(mrz)) => {
+            assert_eq!(mrz.optional_data1(), "ZE1842268<<<<<1");
+            assert_eq!(mrz.optional_data_correction(0), None);
+            assert_eq!(mrz.is_final_check_valid(), Some(false));
+        }
+        other => panic!("Expected ParsedMRZ::MrzIcaoTd3, got {:?}", other),
+    }
+
+    let options = ParseOptions {
+        correct_optional_data: true,
+        ..ParseOptions::lenient()
+    };
+    match parse_any_with(&[line1, line2], &options) {
+        Ok(ParsedMRZ::MrzIcaoTd3(mrz)) => {
+            assert_eq!(mrz.optional_data1(), "ZE184226B<<<<<1");
+            assert_eq!(mrz.document_number(), "L898902C3");
+            let correction = mrz.optional_data_correction(0).unwrap();
+            assert_eq!(correction.raw(mrz.optional_data1()), "ZE1842268<<<<<1");
+            assert_eq!(mrz.is_final_check_valid(), Some(true));
+        }
+        other => panic!("Expected ParsedMRZ::MrzIcaoTd3, got {:?}", other),
+    }
+
+    let strict = parse_any_with(&[line1, line2], &ParseOptions::strict());
+    assert_eq!(
+        strict.unwrap_err().checksum_error(),
+        Some(&MRZChecksumError::Final)
+    );
+}
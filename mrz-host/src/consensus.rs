@@ -0,0 +1,447 @@
+use std::time::{Duration, Instant};
+
+use mrz_core::parser::{detect_format, parse_any_with};
+use mrz_core::realign::realign_with;
+use mrz_core::{
+    ChecksumPolicy, MRZFormat, MRZParseError, MrzField, MrzIcaoCommonFields, ParseOptions,
+    ParsedMRZ,
+};
+
+use crate::parser::{parse_lines_with, prepare_lines};
+use crate::MRZ;
+
+// Extra vote weight of a character whose check digit agrees in its frame.
+const CHECK_WEIGHT: u32 = 3;
+
+/// Thresholds of a [`MrzConsensus`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ConsensusOptions {
+    /// Options used to prepare every frame and to parse the consensus.
+    pub parse: ParseOptions,
+    /// Minimum number of frames of the leading format before a result is emitted.
+    pub min_frames: usize,
+    /// Minimum share of the vote, from 0 to 1, that the leading character of every
+    /// position of a field needs for the field to be stable.
+    pub min_stability: f32,
+    /// Time after the first frame at which the best-effort result is emitted.
+    pub timeout: Duration,
+}
+
+impl Default for ConsensusOptions {
+    fn default() -> Self {
+        ConsensusOptions {
+            parse: ParseOptions {
+                normalize_input: true,
+                realign_lines: true,
+                ..ParseOptions::default()
+            },
+            min_frames: 3,
+            min_stability: 0.75,
+            timeout: Duration::from_secs(5),
+        }
+    }
+}
+
+/// How settled a field of the consensus is.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FieldStability {
+    /// The field whose positions were voted on.
+    pub field: MrzField,
+    /// Lowest share of the vote held by the leading character over the field's positions.
+    pub stability: f32,
+    /// Whether the stability reaches [`ConsensusOptions::min_stability`].
+    pub stable: bool,
+}
+
+/// Outcome of feeding a frame to a [`MrzConsensus`].
+#[derive(Debug)]
+pub enum ConsensusStatus {
+    /// More frames are needed.
+    Pending,
+    /// Every field is stable and the consensus passes its check digits.
+    Converged(MRZ),
+    /// The timeout elapsed first; the consensus parsed with recorded checksum failures,
+    /// if it can be parsed at all.
+    TimedOut(Option<MRZ>),
+}
+
+/// Accumulates successive OCR reads of one MRZ, such as the frames of a camera
+/// preview, and votes per character position.
+///
+/// Each frame is prepared as by [`crate::parse_lines_with`]. Frames of the most
+/// frequent ICAO format vote for the character they read at every position; a vote
+/// weighs more when the check digit covering the position agrees in that frame, and
+/// unchecked positions such as the name weigh by the share of the frame's check digits
+/// that agree.
+///
+/// # Examples
+///
+/// ```
+/// use mrz_host::{ConsensusStatus, MrzConsensus};
+///
+/// let mut consensus = MrzConsensus::default();
+/// let frame = [
+///     "P<UTOERIKSSON<<ANNA<MARIA<<<<<<<<<<<<<<<<<<<",
+///     "L898902C36UTO7408122F1204159ZE184226B<<<<<10",
+/// ];
+/// let mut status = ConsensusStatus::Pending;
+/// for _ in 0..3 {
+///     status = consensus.push(&frame);
+/// }
+/// assert!(matches!(status, ConsensusStatus::Converged(_)));
+/// ```
+#[derive(Debug, Default)]
+pub struct MrzConsensus {
+    options: ConsensusOptions,
+    started: Option<Instant>,
+    tallies: Vec<Tally>,
+}
+
+// Votes of the frames of one format.
+#[derive(Debug)]
+struct Tally {
+    format: MRZFormat,
+    frames: usize,
+    // Per line and column, the weight of every character read there.
+    votes: Vec<Vec<Vec<(u8, u32)>>>,
+}
+
+impl MrzConsensus {
+    /// Creates an empty consensus that converges under `options`.
+    pub fn new(options: ConsensusOptions) -> Self {
+        MrzConsensus {
+            options,
+            started: None,
+            tallies: Vec::new(),
+        }
+    }
+
+    /// Adds a frame read now.
+    pub fn push(&mut self, lines: &[&str]) -> ConsensusStatus {
+        self.push_at(lines, Instant::now())
+    }
+
+    /// Adds a frame read at `now`. Frames that cannot be prepared or are not in an ICAO
+    /// layout are ignored, but still count towards the timeout.
+    pub fn push_at(&mut self, lines: &[&str], now: Instant) -> ConsensusStatus {
+        let started = *self.started.get_or_insert(now);
+        if let Some(frame) = self.prepare(lines) {
+            self.vote(&frame);
+        }
+
+        if self.is_converged() {
+            if let Some(Ok(mrz)) = self.parse(&self.options.parse) {
+                return ConsensusStatus::Converged(mrz);
+            }
+        }
+        if now.duration_since(started) >= self.options.timeout {
+            let options = ParseOptions {
+                checksum_policy: ChecksumPolicy::Record,
+                ..self.options.parse
+            };
+            return ConsensusStatus::TimedOut(self.parse(&options).and_then(Result::ok));
+        }
+        ConsensusStatus::Pending
+    }
+
+    /// Forgets every frame, to start reading another document.
+    pub fn reset(&mut self) {
+        self.started = None;
+        self.tallies.clear();
+    }
+
+    /// Format of the most frequent frames, if any frame was accepted.
+    pub fn format(&self) -> Option<MRZFormat> {
+        self.leader().map(|tally| tally.format)
+    }
+
+    /// Number of frames of the leading format.
+    pub fn frames(&self) -> usize {
+        self.leader().map_or(0, |tally| tally.frames)
+    }
+
+    /// The leading character at every position.
+    pub fn lines(&self) -> Vec<String> {
+        let Some(tally) = self.leader() else {
+            return Vec::new();
+        };
+        tally
+            .votes
+            .iter()
+            .map(|line| {
+                line.iter()
+                    .map(|votes| leading(votes).map_or('<', |(c, _)| c as char))
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Stability of every field of the leading format.
+    pub fn stability(&self) -> Vec<FieldStability> {
+        let Some(tally) = self.leader() else {
+            return Vec::new();
+        };
+        layout(tally.format)
+            .iter()
+            .map(|span| {
+                let stability = (span.start..span.end)
+                    .map(|column| {
+                        let votes = &tally.votes[span.line][column];
+                        let total: u32 = votes.iter().map(|(_, weight)| weight).sum();
+                        leading(votes).map_or(0.0, |(_, weight)| weight as f32 / total as f32)
+                    })
+                    .fold(1.0, f32::min);
+                FieldStability {
+                    field: span.field,
+                    stability,
+                    stable: stability >= self.options.min_stability,
+                }
+            })
+            .collect()
+    }
+
+    /// Returns `true` once enough frames were read and every field is stable.
+    pub fn is_converged(&self) -> bool {
+        self.frames() >= self.options.min_frames
+            && self.stability().iter().all(|field| field.stable)
+    }
+
+    // Canonicalizes, realigns and pads a frame into the lines of an ICAO layout.
+    fn prepare(&self, lines: &[&str]) -> Option<Frame> {
+        let prepared = prepare_lines(lines, &self.options.parse).ok()?;
+        let refs = prepared.refs();
+        let realigned = self
+            .options
+            .parse
+            .realign_lines
+            .then(|| realign_with(&refs, &self.options.parse))
+            .flatten();
+        let lines: Vec<Vec<u8>> = match realigned {
+            Some(realigned) => realigned.lines.iter().map(|line| line.to_vec()).collect(),
+            None => prepared.lines.clone(),
+        };
+        let refs: Vec<&[u8]> = lines.iter().map(|line| &line[..]).collect();
+        let format = detect_format(&refs);
+        let spans = layout(format);
+        let line_len = spans.iter().map(|span| span.end).max()?;
+        if refs.iter().any(|line| line.len() != line_len) {
+            return None;
+        }
+
+        // Check digits are judged on the frame as read.
+        let options = ParseOptions {
+            strict_lengths: true,
+            normalize_input: false,
+            reorder_lines: false,
+            realign_lines: false,
+            ocr_correction: false,
+            checksum_policy: ChecksumPolicy::Record,
+            ..self.options.parse
+        };
+        let checks = match parse_any_with(&refs, &options).ok()? {
+            ParsedMRZ::MrzIcaoTd3(mrz) => Checks::of(&mrz),
+            ParsedMRZ::MrzIcaoTd1(mrz) => Checks::of(&mrz),
+            ParsedMRZ::MrzIcaoTd2(mrz) => Checks::of(&mrz),
+            ParsedMRZ::MrzIcaoMrvA(mrz) => Checks::of(&mrz),
+            ParsedMRZ::MrzIcaoMrvB(mrz) => Checks::of(&mrz),
+            _ => return None,
+        };
+        Some(Frame {
+            format,
+            lines,
+            checks,
+        })
+    }
+
+    fn vote(&mut self, frame: &Frame) {
+        let index = match self.tallies.iter().position(|t| t.format == frame.format) {
+            Some(index) => index,
+            None => {
+                self.tallies.push(Tally {
+                    format: frame.format,
+                    frames: 0,
+                    votes: frame
+                        .lines
+                        .iter()
+                        .map(|line| vec![Vec::new(); line.len()])
+                        .collect(),
+                });
+                self.tallies.len() - 1
+            }
+        };
+        let tally = &mut self.tallies[index];
+        tally.frames += 1;
+        for span in layout(frame.format) {
+            let weight = frame.checks.weight(span.field);
+            for column in span.start..span.end {
+                let c = frame.lines[span.line][column];
+                let votes = &mut tally.votes[span.line][column];
+                match votes.iter_mut().find(|(known, _)| *known == c) {
+                    Some((_, total)) => *total += weight,
+                    None => votes.push((c, weight)),
+                }
+            }
+        }
+    }
+
+    fn leader(&self) -> Option<&Tally> {
+        // The earliest format wins ties, so a stray frame cannot take over.
+        self.tallies.iter().rev().max_by_key(|tally| tally.frames)
+    }
+
+    fn parse(&self, options: &ParseOptions) -> Option<Result<MRZ, MRZParseError>> {
+        let lines = self.lines();
+        if lines.is_empty() {
+            return None;
+        }
+        let refs: Vec<&str> = lines.iter().map(String::as_str).collect();
+        Some(parse_lines_with(&refs, options))
+    }
+}
+
+struct Frame {
+    format: MRZFormat,
+    lines: Vec<Vec<u8>>,
+    checks: Checks,
+}
+
+// Check digit agreement of a frame.
+struct Checks {
+    document_number: bool,
+    birth_date: bool,
+    expiry_date: bool,
+    final_check: Option<bool>,
+}
+
+impl Checks {
+    fn of(mrz: &impl MrzIcaoCommonFields) -> Self {
+        Checks {
+            document_number: mrz.is_document_number_valid(),
+            birth_date: mrz.is_birth_date_valid(),
+            expiry_date: mrz.is_expiry_date_valid(),
+            final_check: mrz.is_final_check_valid(),
+        }
+    }
+
+    // Vote weight of the characters of `field`.
+    fn weight(&self, field: MrzField) -> u32 {
+        let agrees = |valid: bool| 1 + if valid { CHECK_WEIGHT } else { 0 };
+        match field {
+            MrzField::DocumentNumber => agrees(self.document_number),
+            MrzField::BirthDate => agrees(self.birth_date),
+            MrzField::ExpiryDate => agrees(self.expiry_date),
+            MrzField::Final => agrees(self.final_check.unwrap_or(false)),
+            _ => {
+                let checks = [
+                    Some(self.document_number),
+                    Some(self.birth_date),
+                    Some(self.expiry_date),
+                    self.final_check,
+                ];
+                let total = checks.iter().flatten().count() as u32;
+                let passed = checks.iter().flatten().filter(|valid| **valid).count() as u32;
+                1 + CHECK_WEIGHT * passed / total
+            }
+        }
+    }
+}
+
+// A field of an ICAO layout, including its check digit.
+struct Span {
+    field: MrzField,
+    line: usize,
+    start: usize,
+    end: usize,
+}
+
+const fn span(field: MrzField, line: usize, start: usize, end: usize) -> Span {
+    Span {
+        field,
+        line,
+        start,
+        end,
+    }
+}
+
+// Every position of the ICAO layouts belongs to exactly one field.
+fn layout(format: MRZFormat) -> &'static [Span] {
+    use MrzField::*;
+
+    const TD3: &[Span] = &[
+        span(DocumentCode, 0, 0, 2),
+        span(IssuingState, 0, 2, 5),
+        span(Name, 0, 5, 44),
+        span(DocumentNumber, 1, 0, 10),
+        span(Nationality, 1, 10, 13),
+        span(BirthDate, 1, 13, 20),
+        span(Sex, 1, 20, 21),
+        span(ExpiryDate, 1, 21, 28),
+        span(OptionalData, 1, 28, 43),
+        span(Final, 1, 43, 44),
+    ];
+    const TD2: &[Span] = &[
+        span(DocumentCode, 0, 0, 2),
+        span(IssuingState, 0, 2, 5),
+        span(Name, 0, 5, 36),
+        span(DocumentNumber, 1, 0, 10),
+        span(Nationality, 1, 10, 13),
+        span(BirthDate, 1, 13, 20),
+        span(Sex, 1, 20, 21),
+        span(ExpiryDate, 1, 21, 28),
+        span(OptionalData, 1, 28, 35),
+        span(Final, 1, 35, 36),
+    ];
+    const MRVA: &[Span] = &[
+        span(DocumentCode, 0, 0, 2),
+        span(IssuingState, 0, 2, 5),
+        span(Name, 0, 5, 44),
+        span(DocumentNumber, 1, 0, 10),
+        span(Nationality, 1, 10, 13),
+        span(BirthDate, 1, 13, 20),
+        span(Sex, 1, 20, 21),
+        span(ExpiryDate, 1, 21, 28),
+        span(OptionalData, 1, 28, 44),
+    ];
+    const MRVB: &[Span] = &[
+        span(DocumentCode, 0, 0, 2),
+        span(IssuingState, 0, 2, 5),
+        span(Name, 0, 5, 36),
+        span(DocumentNumber, 1, 0, 10),
+        span(Nationality, 1, 10, 13),
+        span(BirthDate, 1, 13, 20),
+        span(Sex, 1, 20, 21),
+        span(ExpiryDate, 1, 21, 28),
+        span(OptionalData, 1, 28, 36),
+    ];
+    const TD1: &[Span] = &[
+        span(DocumentCode, 0, 0, 2),
+        span(IssuingState, 0, 2, 5),
+        span(DocumentNumber, 0, 5, 15),
+        span(OptionalData, 0, 15, 30),
+        span(BirthDate, 1, 0, 7),
+        span(Sex, 1, 7, 8),
+        span(ExpiryDate, 1, 8, 15),
+        span(Nationality, 1, 15, 18),
+        span(OptionalData, 1, 18, 29),
+        span(Final, 1, 29, 30),
+        span(Name, 2, 0, 30),
+    ];
+
+    match format {
+        MRZFormat::MrzIcaoTd3 => TD3,
+        MRZFormat::MrzIcaoTd2 => TD2,
+        MRZFormat::MrzIcaoMrvA => MRVA,
+        MRZFormat::MrzIcaoMrvB => MRVB,
+        MRZFormat::MrzIcaoTd1 => TD1,
+        _ => &[],
+    }
+}
+
+fn leading(votes: &[(u8, u32)]) -> Option<(u8, u32)> {
+    // The first character read wins ties.
+    votes
+        .iter()
+        .rev()
+        .max_by_key(|(_, weight)| *weight)
+        .copied()
+}
@@ -0,0 +1,67 @@
+use mrz_core::extract::extract_mrz as core_extract_mrz;
+use mrz_core::{MRZFormat, MRZParseError, ParseOptions};
+
+use crate::parser::parse_lines_with;
+use crate::MRZ;
+
+/// An MRZ found in a block of text.
+#[derive(Debug)]
+pub struct ExtractedMrz {
+    /// Format detected for the MRZ.
+    pub format: MRZFormat,
+    /// Index of the first line of the MRZ in the text, counting blank lines.
+    pub first_line: usize,
+    /// Index of the last line of the MRZ in the text.
+    pub last_line: usize,
+    /// The MRZ lines as they appear in the text.
+    pub source_lines: Vec<String>,
+    /// Canonical lines padded to the layout length.
+    pub lines: Vec<String>,
+    /// Confidence score, see [`mrz_core::extract::scan`].
+    pub score: u32,
+    /// Number of check digits that agree with their data.
+    pub checks_passed: u8,
+    /// Number of check digits in the MRZ.
+    pub checks_total: u8,
+    /// The MRZ parsed with the options given to [`extract_mrz_with`].
+    pub result: Result<MRZ, MRZParseError>,
+}
+
+/// Finds every MRZ in a block of OCR text and parses it with the default options.
+///
+/// See [`extract_mrz_with`].
+pub fn extract_mrz(text: &str) -> Vec<ExtractedMrz> {
+    extract_mrz_with(text, &ParseOptions::default())
+}
+
+/// Finds every MRZ in a block of OCR text, such as a whole document page, and parses it.
+///
+/// MRZs are selected by [`mrz_core::extract::extract_mrz`], so they are returned in text
+/// order, overlapping readings are resolved in favour of the higher score, and at most
+/// [`mrz_core::extract::MAX_EXTRACTED_MRZ`] MRZs are returned.
+pub fn extract_mrz_with(text: &str, options: &ParseOptions) -> Vec<ExtractedMrz> {
+    let text_lines: Vec<&str> = text.split('\n').collect();
+    core_extract_mrz(text)
+        .into_iter()
+        .map(|candidate| {
+            let lines: Vec<String> = candidate.lines.iter().map(|l| l.to_string()).collect();
+            let refs: Vec<&str> = lines.iter().map(String::as_str).collect();
+            let source_lines = text_lines[candidate.first_line..=candidate.last_line]
+                .iter()
+                .filter(|line| !line.trim().is_empty())
+                .map(|line| line.trim_end_matches('\r').to_string())
+                .collect();
+            ExtractedMrz {
+                format: candidate.format,
+                first_line: candidate.first_line,
+                last_line: candidate.last_line,
+                source_lines,
+                result: parse_lines_with(&refs, options),
+                lines,
+                score: candidate.score,
+                checks_passed: candidate.checks_passed,
+                checks_total: candidate.checks_total,
+            }
+        })
+        .collect()
+}
@@ -1,8 +1,12 @@
+mod consensus;
 mod date;
+mod extract;
 mod model;
 mod parser;
 mod validation;
 
+pub use consensus::{ConsensusOptions, ConsensusStatus, FieldStability, MrzConsensus};
 pub use date::parse_mrz_date_with_reference;
-pub use model::{MrzIcaoUnified, MRZ};
-pub use parser::parse_lines;
+pub use extract::{extract_mrz, extract_mrz_with, ExtractedMrz};
+pub use model::{FieldCorrection, MrzIcaoUnified, MRZ};
+pub use parser::{parse_lines, parse_lines_report, parse_lines_with, parse_scored_lines};
@@ -1,5 +1,81 @@
+use mrz_core::checked_field::CheckedField;
+use mrz_core::{
+    Correction, CorrectionCandidate, CorrectionOutcome, DocumentKind, LineOrder, MrzField,
+};
 use time::Date;
 
+/// A field whose value OCR correction changed, with the alternatives found.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldCorrection {
+    /// The corrected field. A TD1 document may list [`MrzField::OptionalData`] twice,
+    /// optional data 1 first.
+    pub field: MrzField,
+    /// Whether the correction is certain or one of several equally likely values.
+    pub outcome: CorrectionOutcome,
+    /// Checksum-valid values, cheapest first; the first one is the field's value.
+    /// Empty for optional data, which the composite check digit chose.
+    pub candidates: Vec<CorrectionCandidate>,
+    /// The substitutions applied to the value, if any were recorded.
+    pub correction: Option<Correction>,
+    /// The value as read, without trailing fillers; `None` for the composite check
+    /// digit, which covers the values of other fields rather than holding one.
+    pub raw_value: Option<String>,
+    /// The corrected value, without trailing fillers; `None` for the composite check
+    /// digit.
+    pub corrected_value: Option<String>,
+}
+
+impl FieldCorrection {
+    // `value` is the corrected value the field holds, fillers included.
+    fn new(
+        field: MrzField,
+        outcome: CorrectionOutcome,
+        candidates: Vec<CorrectionCandidate>,
+        correction: Option<Correction>,
+        value: Option<&str>,
+    ) -> Self {
+        let raw_value = match (&correction, value) {
+            (Some(correction), Some(value)) => Some(correction.raw(value).to_string()),
+            _ => value.map(str::to_string),
+        };
+        FieldCorrection {
+            field,
+            outcome,
+            candidates,
+            correction,
+            raw_value: raw_value.map(|raw| raw.trim_end_matches('<').to_string()),
+            corrected_value: value.map(|value| value.trim_end_matches('<').to_string()),
+        }
+    }
+
+    // Records how OCR correction changed `checked`, which holds `value`, if it did.
+    pub(crate) fn from_checked<T>(
+        field: MrzField,
+        checked: &CheckedField<T>,
+        value: Option<&str>,
+    ) -> Option<Self> {
+        let outcome = checked.correction_outcome();
+        matches!(
+            outcome,
+            CorrectionOutcome::Corrected | CorrectionOutcome::Ambiguous
+        )
+        .then(|| {
+            FieldCorrection::new(
+                field,
+                outcome,
+                checked.candidates().to_vec(),
+                checked.correction().cloned(),
+                value,
+            )
+        })
+    }
+
+    /// Returns `true` if several values of equal cost satisfy the check digit.
+    pub fn is_ambiguous(&self) -> bool {
+        self.outcome == CorrectionOutcome::Ambiguous
+    }
+}
+
 #[derive(Debug)]
 pub struct MrzIcaoUnified {
     document_number: String,
@@ -15,9 +91,12 @@ pub struct MrzIcaoUnified {
     issuing_state: String,
     document_code: String,
     format: String,
+    corrections: Vec<FieldCorrection>,
+    line_order: Option<LineOrder>,
 }
 
 impl MrzIcaoUnified {
+    #[allow(clippy::too_many_arguments)]
     fn from_parts(
         document_number: String,
         surname: String,
@@ -47,9 +126,12 @@ impl MrzIcaoUnified {
             issuing_state,
             document_code,
             format,
+            corrections: Vec::new(),
+            line_order: None,
         }
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         document_number: String,
         surname: String,
@@ -118,9 +200,36 @@ impl MrzIcaoUnified {
     pub fn document_code(&self) -> &str {
         &self.document_code
     }
+    pub fn document_kind(&self) -> DocumentKind {
+        DocumentKind::from_code(self.document_code.as_bytes())
+    }
     pub fn format(&self) -> &str {
         &self.format
     }
+    /// Fields corrected by OCR correction, with their ranked candidates.
+    pub fn corrections(&self) -> &[FieldCorrection] {
+        &self.corrections
+    }
+    /// The order the lines were parsed in under `reorder_lines`, or `None` if it is the
+    /// input order; see [`mrz_core::LineOrder`].
+    pub fn line_order(&self) -> Option<LineOrder> {
+        self.line_order
+    }
+    /// Returns `true` if a correction had several equally likely values, so an
+    /// operator should choose among [`FieldCorrection::candidates`].
+    pub fn is_ambiguous(&self) -> bool {
+        self.corrections.iter().any(FieldCorrection::is_ambiguous)
+    }
+
+    pub(crate) fn with_corrections(mut self, corrections: Vec<FieldCorrection>) -> Self {
+        self.corrections = corrections;
+        self
+    }
+
+    pub(crate) fn with_line_order(mut self, line_order: Option<LineOrder>) -> Self {
+        self.line_order = line_order;
+        self
+    }
 
     pub fn full_name(&self) -> String {
         format!("{} {}", self.surname, self.given_names)
@@ -141,7 +250,54 @@ impl MrzIcaoUnified {
         fn strip_fill(s: &str) -> String {
             s.trim_end_matches('<').to_string()
         }
-        Self::from_parts(
+        let checked = [
+            mrz_core::MRZChecksumError::DocumentNumber,
+            mrz_core::MRZChecksumError::BirthDate,
+            mrz_core::MRZChecksumError::ExpiryDate,
+            mrz_core::MRZChecksumError::Final,
+        ]
+        .into_iter()
+        .filter_map(|field| {
+            let outcome = source.correction_outcome(field.clone())?;
+            let value = match field {
+                mrz_core::MRZChecksumError::DocumentNumber => Some(source.document_number()),
+                mrz_core::MRZChecksumError::BirthDate => {
+                    std::str::from_utf8(source.birth_date()).ok()
+                }
+                mrz_core::MRZChecksumError::ExpiryDate => {
+                    std::str::from_utf8(source.expiry_date()).ok()
+                }
+                mrz_core::MRZChecksumError::Final => None,
+            };
+            matches!(
+                outcome,
+                CorrectionOutcome::Corrected | CorrectionOutcome::Ambiguous
+            )
+            .then(|| {
+                FieldCorrection::new(
+                    field.clone().into(),
+                    outcome,
+                    source.correction_candidates(field.clone()).to_vec(),
+                    source.correction(field).cloned(),
+                    value,
+                )
+            })
+        });
+        let optional = [source.optional_data1(), source.optional_data2()]
+            .into_iter()
+            .enumerate()
+            .filter_map(|(index, value)| {
+                let correction = source.optional_data_correction(index)?;
+                Some(FieldCorrection::new(
+                    MrzField::OptionalData,
+                    CorrectionOutcome::Corrected,
+                    Vec::new(),
+                    Some(correction.clone()),
+                    Some(value),
+                ))
+            });
+        let corrections = checked.chain(optional).collect();
+        let mut unified = Self::from_parts(
             strip_fill(source.document_number()),
             strip_fill(surname),
             strip_fill(given_names),
@@ -150,21 +306,36 @@ impl MrzIcaoUnified {
             sex,
             strip_fill(source.optional_data1()),
             strip_fill(source.optional_data2()),
-            if source.has_final_check() {
-                Some(true)
-            } else {
-                None
-            },
+            source.is_final_check_valid(),
             strip_fill(&String::from_utf8_lossy(source.nationality())),
             strip_fill(&String::from_utf8_lossy(source.issuing_state())),
             strip_fill(&String::from_utf8_lossy(source.document_code())),
             format.to_string(),
-        )
+        );
+        unified.corrections = corrections;
+        unified.line_order = source.line_order();
+        unified
     }
 }
 
 #[derive(Debug)]
+#[allow(clippy::large_enum_variant)]
 pub enum MRZ {
     Icao(MrzIcaoUnified),
+    DrivingLicence(mrz_core::MrzDrivingLicence),
+    SwissDrivingLicence(mrz_core::MrzSwissDrivingLicence),
+    Bcbp(Box<mrz_core::bcbp::Bcbp>),
     Unknown,
 }
+
+impl MRZ {
+    pub fn document_kind(&self) -> Option<DocumentKind> {
+        match self {
+            MRZ::Icao(mrz) => Some(mrz.document_kind()),
+            MRZ::DrivingLicence(dl) => Some(dl.document_kind()),
+            MRZ::SwissDrivingLicence(dl) => Some(dl.document_kind()),
+            MRZ::Bcbp(bcbp) => Some(bcbp.document_kind()),
+            MRZ::Unknown => None,
+        }
+    }
+}
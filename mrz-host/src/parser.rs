@@ -1,19 +1,38 @@
 use crate::date::parse_mrz_date_with_reference;
-use crate::validation::validate_common_fields;
-use mrz_core::MrzIcaoCommonFields;
+use crate::model::FieldCorrection;
+use crate::validation::{validate_common_fields, validate_fra_id_fields};
+use mrz_core::{MrzFraId, MrzIcaoCommonFields, CH_DL_DOC_CODE, CH_DL_LINE1_LEN};
 
 use crate::MRZ;
-use mrz_core::{parser::parse_any, MRZParseError, ParsedMRZ};
+use mrz_core::normalize::{normalize_lines, NormalizedInput};
+use mrz_core::parser::{parse_any_report, parse_any_with, parse_scored};
+use mrz_core::{
+    ChecksumPolicy, MRZChecksumError, MRZParseError, MrzField, ParseOptions, ParseReport,
+    ParsedMRZ, ScoredLine,
+};
 
-fn normalize_lines(lines: &[&str]) -> Vec<Vec<u8>> {
+fn pad_lines(lines: &[&str], options: &ParseOptions) -> Vec<Vec<u8>> {
+    if options.strict_lengths {
+        return lines.iter().map(|line| line.as_bytes().to_vec()).collect();
+    }
+    let longest = lines.iter().map(|line| line.len()).max().unwrap_or(0);
+    // The Swiss driving licence starts with a short 9-character line, which must not be
+    // padded to the TD1 length. Its second line starts with the `FA` document code.
+    let swiss_driving_licence = lines.len() == 3
+        && lines[0].len() <= CH_DL_LINE1_LEN
+        && lines[1].as_bytes().starts_with(CH_DL_DOC_CODE);
     lines
         .iter()
-        .map(|line| {
+        .enumerate()
+        .map(|(index, line)| {
             let mut bytes = line.as_bytes().to_vec();
             let expected_len = match lines.len() {
+                2 if longest <= 36 => 36,
                 2 => 44,
+                3 if swiss_driving_licence && index == 0 => CH_DL_LINE1_LEN,
                 3 => 30,
-                1 => 60,
+                // Single-line documents (boarding passes, driving licences) are never padded:
+                // their trailing characters are data or check digits, not fillers.
                 _ => 0,
             };
             while bytes.len() < expected_len {
@@ -24,10 +43,41 @@ fn normalize_lines(lines: &[&str]) -> Vec<Vec<u8>> {
         .collect()
 }
 
-fn build_mrz_result<T: MrzIcaoCommonFields>(raw: &T, format: &str) -> Result<MRZ, MRZParseError> {
+// Lines ready for the core parser, with the changes normalization made to them.
+pub(crate) struct PreparedLines {
+    pub(crate) lines: Vec<Vec<u8>>,
+    pub(crate) normalized: Option<NormalizedInput>,
+}
+
+impl PreparedLines {
+    pub(crate) fn refs(&self) -> Vec<&[u8]> {
+        self.lines.iter().map(|line| &line[..]).collect()
+    }
+}
+
+// Canonicalizes the input first when requested, then pads short lines. Realignment is
+// left to the core parser, which reorders the lines first; a filler dropped inside a
+// padded line is restored there by shifting the span up to the padding.
+pub(crate) fn prepare_lines(
+    lines: &[&str],
+    options: &ParseOptions,
+) -> Result<PreparedLines, MRZParseError> {
+    let mut normalized = None;
+    let mut lines: Vec<&str> = lines.to_vec();
+    if options.normalize_input {
+        let input = normalized.insert(normalize_lines(lines.iter().copied())?);
+        lines = input.lines.iter().map(|line| line.as_str()).collect();
+    }
+    Ok(PreparedLines {
+        lines: pad_lines(&lines, options),
+        normalized,
+    })
+}
+
+fn build_mrz_result<T: MrzIcaoCommonFields>(raw: &T, format: &str) -> MRZ {
     let birth_date_bytes = raw.birth_date();
     let expiry_date_bytes = raw.expiry_date();
-    Ok(MRZ::Icao(crate::model::MrzIcaoUnified::from_common_fields(
+    MRZ::Icao(crate::model::MrzIcaoUnified::from_common_fields(
         raw,
         format,
         raw.surname().as_str(),
@@ -35,23 +85,152 @@ fn build_mrz_result<T: MrzIcaoCommonFields>(raw: &T, format: &str) -> Result<MRZ
         parse_mrz_date_with_reference(birth_date_bytes, Some(expiry_date_bytes)),
         parse_mrz_date_with_reference(expiry_date_bytes, None),
         raw.sex() as char,
-    )))
+    ))
+}
+
+fn build_fra_id_result(raw: &MrzFraId) -> MRZ {
+    // Birth dates precede the issue date, whose year starts the card number.
+    let issue = raw.issue_year_month();
+    let reference = match issue {
+        [y1, y2, ..] => Some([*y1, *y2, b'1', b'2', b'3', b'1']),
+        _ => None,
+    };
+    let admin_code = format!(
+        "{}{}",
+        String::from_utf8_lossy(&raw.department),
+        String::from_utf8_lossy(&raw.office)
+    );
+    let corrections = [
+        FieldCorrection::from_checked(
+            MrzField::DocumentNumber,
+            &raw.card_number,
+            Some(raw.card_number()),
+        ),
+        FieldCorrection::from_checked(
+            MrzField::BirthDate,
+            &raw.birth_date,
+            std::str::from_utf8(raw.birth_date()).ok(),
+        ),
+        FieldCorrection::from_checked(MrzField::Final, &raw.final_check, None),
+    ];
+    MRZ::Icao(
+        crate::model::MrzIcaoUnified::new(
+            raw.card_number().to_string(),
+            raw.surname().to_string(),
+            raw.given_names().to_string(),
+            parse_mrz_date_with_reference(raw.birth_date(), reference.as_ref()),
+            None,
+            raw.sex as char,
+            admin_code.trim_end_matches('<').to_string(),
+            String::new(),
+            Some(raw.is_final_check_valid()),
+            String::from_utf8_lossy(&raw.issuing_state).into_owned(),
+            String::from_utf8_lossy(&raw.issuing_state).into_owned(),
+            String::from_utf8_lossy(&raw.document_code).into_owned(),
+            "IDFRA".to_string(),
+        )
+        .with_corrections(corrections.into_iter().flatten().collect())
+        .with_line_order(raw.line_order),
+    )
 }
 
 pub fn parse_lines(lines: &[&str]) -> Result<MRZ, MRZParseError> {
-    let normalized = normalize_lines(lines);
-    let refs: Vec<&[u8]> = normalized.iter().map(|l| &l[..]).collect();
-    let parsed = parse_any(&refs)?;
+    parse_lines_with(lines, &ParseOptions::default())
+}
 
-    match parsed {
-        ParsedMRZ::MrzIcaoTd3(ref raw) => {
-            validate_common_fields(raw)?;
-            build_mrz_result(raw, "TD3")
-        }
-        ParsedMRZ::MrzIcaoTd1(ref raw) => {
-            validate_common_fields(raw)?;
-            build_mrz_result(raw, "TD1")
+/// Parses MRZ lines with the given options.
+///
+/// With `normalize_input`, the lines are first canonicalized into the MRZ alphabet
+/// (see [`mrz_core::normalize::normalize`]). With `realign_lines`, dropped or duplicated
+/// characters are then restored (see [`mrz_core::realign::realign_with`]). Unless
+/// `strict_lengths` is set, remaining short lines are padded with `<` to the length of
+/// the expected layout. With [`ChecksumPolicy::Record`], check digit failures are kept
+/// in the result instead of being returned as errors.
+pub fn parse_lines_with(lines: &[&str], options: &ParseOptions) -> Result<MRZ, MRZParseError> {
+    let prepared = prepare_lines(lines, options)?;
+    let parsed = parse_any_with(&prepared.refs(), &core_options(options))?;
+
+    if options.checksum_policy == ChecksumPolicy::Fatal {
+        validate_parsed(&parsed)?;
+    }
+    Ok(build_result(parsed))
+}
+
+/// Parses MRZ lines read with per-character confidences and alternative glyphs.
+///
+/// OCR correction only substitutes uncertain characters, least certain first; see
+/// [`mrz_core::parser::parse_scored`]. The lines are neither normalized, realigned nor
+/// padded, so every confidence stays with its character.
+pub fn parse_scored_lines(
+    lines: &[ScoredLine<'_>],
+    options: &ParseOptions,
+) -> Result<MRZ, MRZParseError> {
+    let parsed = parse_scored(lines, options)?;
+
+    if options.checksum_policy == ChecksumPolicy::Fatal {
+        validate_parsed(&parsed)?;
+    }
+    Ok(build_result(parsed))
+}
+
+/// Parses MRZ lines and reports every problem found alongside the best-effort result,
+/// including the changes made by `normalize_input`.
+pub fn parse_lines_report(lines: &[&str], options: &ParseOptions) -> ParseReport<MRZ> {
+    let prepared = match prepare_lines(lines, options) {
+        Ok(prepared) => prepared,
+        Err(e) => return ParseReport::from_error(&e).map(build_result),
+    };
+    let mut report = parse_any_report(&prepared.refs(), &core_options(options)).map(build_result);
+    if let Some(normalized) = &prepared.normalized {
+        report.record_normalization(normalized);
+    }
+    report
+}
+
+// The lines handed to the core parser are already normalized.
+fn core_options(options: &ParseOptions) -> ParseOptions {
+    ParseOptions {
+        normalize_input: false,
+        ..*options
+    }
+}
+
+// Errors point at the failed check digit, like those of the core parser.
+fn validate_parsed(parsed: &ParsedMRZ) -> Result<(), MRZParseError> {
+    let validated = match parsed {
+        ParsedMRZ::MrzIcaoTd3(raw) => validate_common_fields(raw),
+        ParsedMRZ::MrzIcaoTd1(raw) => validate_common_fields(raw),
+        ParsedMRZ::MrzIcaoTd2(raw) => validate_common_fields(raw),
+        ParsedMRZ::MrzIcaoMrvA(raw) => validate_common_fields(raw),
+        ParsedMRZ::MrzIcaoMrvB(raw) => validate_common_fields(raw),
+        ParsedMRZ::MrzFraId(raw) => validate_fra_id_fields(raw),
+        ParsedMRZ::MrzDrivingLicence(raw) if !raw.is_licence_number_valid() => Err(
+            MRZParseError::from_checksum(MRZChecksumError::DocumentNumber),
+        ),
+        _ => Ok(()),
+    };
+    validated.map_err(|e| {
+        match e
+            .checksum_error()
+            .and_then(|kind| parsed.checksum_position(kind))
+        {
+            Some(position) => e.at(position),
+            None => e,
         }
-        ParsedMRZ::Unknown => Ok(MRZ::Unknown),
+    })
+}
+
+fn build_result(parsed: ParsedMRZ) -> MRZ {
+    match parsed {
+        ParsedMRZ::MrzIcaoTd3(ref raw) => build_mrz_result(raw, "TD3"),
+        ParsedMRZ::MrzIcaoTd1(ref raw) => build_mrz_result(raw, "TD1"),
+        ParsedMRZ::MrzIcaoTd2(ref raw) => build_mrz_result(raw, "TD2"),
+        ParsedMRZ::MrzIcaoMrvA(ref raw) => build_mrz_result(raw, "MRVA"),
+        ParsedMRZ::MrzIcaoMrvB(ref raw) => build_mrz_result(raw, "MRVB"),
+        ParsedMRZ::MrzFraId(ref raw) => build_fra_id_result(raw),
+        ParsedMRZ::MrzDrivingLicence(raw) => MRZ::DrivingLicence(raw),
+        ParsedMRZ::MrzSwissDrivingLicence(raw) => MRZ::SwissDrivingLicence(raw),
+        ParsedMRZ::Bcbp(bcbp) => MRZ::Bcbp(bcbp),
+        ParsedMRZ::Unknown => MRZ::Unknown,
     }
 }
@@ -1,25 +1,35 @@
-use mrz_core::MrzIcaoCommonFields;
+use mrz_core::report::{common_field_issues, fra_id_issues};
+use mrz_core::{IssueKind, MrzField, MrzFraId, MrzIcaoCommonFields, ParseReport, Severity};
 
 use mrz_core::{MRZChecksumError, MRZParseError};
 
+// Every field is checked before failing; the first failed check digit is returned.
 pub fn validate_common_fields(raw: &impl MrzIcaoCommonFields) -> Result<(), MRZParseError> {
-    if !raw.is_document_number_valid() {
-        return Err(MRZParseError::InvalidChecksumField(
-            MRZChecksumError::DocumentNumber,
-        ));
-    }
-    if !raw.is_birth_date_valid() {
-        return Err(MRZParseError::InvalidChecksumField(
-            MRZChecksumError::BirthDate,
-        ));
-    }
-    if !raw.is_expiry_date_valid() {
-        return Err(MRZParseError::InvalidChecksumField(
-            MRZChecksumError::ExpiryDate,
-        ));
-    }
-    if raw.is_final_check_valid() == Some(false) {
-        return Err(MRZParseError::InvalidChecksumField(MRZChecksumError::Final));
+    let mut report = ParseReport::<()>::new(None);
+    common_field_issues(raw, &mut report);
+    first_checksum_error(&report)
+}
+
+pub fn validate_fra_id_fields(raw: &MrzFraId) -> Result<(), MRZParseError> {
+    let mut report = ParseReport::<()>::new(None);
+    fra_id_issues(raw, &mut report);
+    first_checksum_error(&report)
+}
+
+fn first_checksum_error(report: &ParseReport<()>) -> Result<(), MRZParseError> {
+    let failed = report
+        .issues
+        .iter()
+        .filter(|i| i.severity == Severity::Error && i.kind == IssueKind::ChecksumMismatch)
+        .find_map(|i| match i.field {
+            MrzField::DocumentNumber => Some(MRZChecksumError::DocumentNumber),
+            MrzField::BirthDate => Some(MRZChecksumError::BirthDate),
+            MrzField::ExpiryDate => Some(MRZChecksumError::ExpiryDate),
+            MrzField::Final => Some(MRZChecksumError::Final),
+            _ => None,
+        });
+    match failed {
+        Some(kind) => Err(MRZParseError::from_checksum(kind)),
+        None => Ok(()),
     }
-    Ok(())
 }
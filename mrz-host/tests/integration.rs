@@ -1,6 +1,12 @@
-use mrz_core::MRZParseError;
-use mrz_host::parse_lines;
+use mrz_core::{
+    ChecksumPolicy, CorrectionOutcome, DocumentKind, ErrorPosition, IssueKind, MRZChecksumError,
+    MRZParseError, MrzField, ParseIssue, ParseOptions, ScoredLine, Substitution,
+};
 use mrz_host::MRZ;
+use mrz_host::{
+    extract_mrz, parse_lines, parse_lines_report, parse_lines_with, parse_scored_lines,
+    ConsensusOptions, ConsensusStatus, MrzConsensus,
+};
 use serde::Deserialize;
 use std::fs;
 use std::path::Path;
@@ -103,6 +109,7 @@ fn test_samples_valid() {
 }
 
 #[test]
+#[allow(clippy::unnecessary_map_or)]
 fn test_samples_invalid() {
     let data = fs::read_to_string(Path::new("tests/fixtures/samples.json")).unwrap();
     let samples: Vec<Sample> = serde_json::from_str(&data).unwrap();
@@ -116,7 +123,7 @@ fn test_samples_invalid() {
         let parsed = parse_lines(&line_refs);
 
         match parsed {
-            Err(MRZParseError::InvalidChecksumField(err)) => {
+            Err(MRZParseError::InvalidChecksumField(err, _)) => {
                 let mut err_strings: Vec<String> = vec![err.to_string()];
                 err_strings.sort();
                 let mut expected = sample.invalid_checksums.unwrap();
@@ -130,3 +137,649 @@ fn test_samples_invalid() {
         }
     }
 }
+
+#[test]
+fn test_parse_lines_td2() {
+    let lines = [
+        "I<UTOERIKSSON<<ANNA<MARIA",
+        "D231458907UTO7408122F1204159<<<<<<<6",
+    ];
+
+    match parse_lines(&lines) {
+        Ok(MRZ::Icao(mrz)) => {
+            assert_eq!(mrz.format(), "TD2");
+            assert_eq!(mrz.document_number(), "D23145890");
+            assert_eq!(mrz.surname(), "ERIKSSON");
+            assert_eq!(mrz.given_names(), "ANNA MARIA");
+            assert_eq!(mrz.birth_date().unwrap().to_string(), "1974-08-12");
+            assert_eq!(mrz.expiry_date().unwrap().to_string(), "2012-04-15");
+        }
+        other => panic!("Expected TD2 document, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_parse_lines_with_strict_lengths_does_not_pad() {
+    let lines = [
+        "I<UTOERIKSSON<<ANNA<MARIA",
+        "D231458907UTO7408122F1204159<<<<<<<6",
+    ];
+
+    assert!(parse_lines(&lines).is_ok());
+    assert!(parse_lines_with(&lines, &ParseOptions::strict()).is_err());
+}
+
+#[test]
+fn test_parse_lines_with_recorded_checksum_failures() {
+    let lines = [
+        "P<UTOERIKSSON<<ANNA<MARIA<<<<<<<<<<<<<<<<<<<",
+        "L898902C36UTO7408122F1204159ZE184226B<<<<<19",
+    ];
+    let options = ParseOptions {
+        checksum_policy: ChecksumPolicy::Record,
+        ..ParseOptions::default()
+    };
+
+    assert_eq!(
+        parse_lines(&lines).unwrap_err(),
+        MRZParseError::InvalidChecksumField(
+            MRZChecksumError::Final,
+            Some(ErrorPosition::at(1, 43))
+        )
+    );
+    match parse_lines_with(&lines, &options) {
+        Ok(MRZ::Icao(mrz)) => {
+            assert_eq!(mrz.document_number(), "L898902C3");
+            assert_eq!(mrz.final_check(), Some(false));
+        }
+        other => panic!("Expected TD3 document, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_parse_lines_with_normalized_input() {
+    // Lowercase OCR output with spaces for fillers; trimmed lines are padded afterwards.
+    let lines = [
+        "i utod231458907               \r",
+        " 7408122f1204159utо           6",
+        "eriksson  anna maria   ",
+    ];
+    let options = ParseOptions {
+        normalize_input: true,
+        ..ParseOptions::default()
+    };
+
+    assert!(parse_lines(&lines).is_err());
+    match parse_lines_with(&lines, &options) {
+        Ok(MRZ::Icao(mrz)) => {
+            assert_eq!(mrz.document_number(), "D23145890");
+            assert_eq!(mrz.surname(), "ERIKSSON");
+            assert_eq!(mrz.given_names(), "ANNA MARIA");
+            assert_eq!(mrz.nationality(), "UTO");
+        }
+        other => panic!("Expected ICAO document, got {:?}", other),
+    }
+    let report = parse_lines_report(&lines, &options);
+    assert_eq!(
+        report.issues.as_slice(),
+        &[ParseIssue::warning(
+            MrzField::Layout,
+            IssueKind::InputNormalized
+        )]
+    );
+    assert!(!report.normalization.is_empty());
+}
+
+#[test]
+fn test_parse_lines_with_realigned_input() {
+    // A filler dropped inside the line: padding at the end would misplace the check digits.
+    let lines = [
+        "P<UTOERIKSSON<<ANNA<MARIA<<<<<<<<<<<<<<<<<<<",
+        "L898902C36UTO7408122F1204159ZE184226B<<<<10",
+    ];
+    let options = ParseOptions {
+        realign_lines: true,
+        ..ParseOptions::default()
+    };
+
+    match parse_lines_with(&lines, &options) {
+        Ok(MRZ::Icao(mrz)) => {
+            assert_eq!(mrz.document_number(), "L898902C3");
+            assert_eq!(mrz.final_check(), Some(true));
+        }
+        other => panic!("Expected ICAO document, got {:?}", other),
+    }
+
+    let strict = ParseOptions {
+        strict_lengths: true,
+        ..options
+    };
+    assert!(matches!(
+        parse_lines_with(&lines, &strict),
+        Err(MRZParseError::InvalidLength(_))
+    ));
+}
+
+#[test]
+fn test_parse_lines_with_ambiguous_correction() {
+    let lines = [
+        "P<UTOERIKSSON<<ANNA<MARIA<<<<<<<<<<<<<<<<<<<",
+        "X1S9876546UTO7408122F1204159ZE184226B<<<<<10",
+    ];
+    let options = ParseOptions {
+        correction_budget: 64,
+        checksum_policy: ChecksumPolicy::Record,
+        ..ParseOptions::default()
+    };
+
+    match parse_lines_with(&lines, &options) {
+        Ok(MRZ::Icao(mrz)) => {
+            assert!(mrz.is_ambiguous());
+            let correction = &mrz.corrections()[0];
+            assert_eq!(correction.field, MrzField::DocumentNumber);
+            assert_eq!(correction.outcome, CorrectionOutcome::Ambiguous);
+            assert_eq!(
+                mrz.document_number(),
+                correction.candidates[0].value.as_str()
+            );
+            assert!(correction.candidates.len() >= 2);
+        }
+        other => panic!("Expected ICAO document, got {:?}", other),
+    }
+
+    // A clean MRZ has nothing to choose from.
+    match parse_lines(&[
+        "P<UTOERIKSSON<<ANNA<MARIA<<<<<<<<<<<<<<<<<<<",
+        "L898902C36UTO7408122F1204159ZE184226B<<<<<10",
+    ]) {
+        Ok(MRZ::Icao(mrz)) => {
+            assert!(!mrz.is_ambiguous());
+            assert!(mrz.corrections().is_empty());
+        }
+        other => panic!("Expected ICAO document, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_parse_lines_records_correction_provenance() {
+    let lines = [
+        "P<UTOERIKSSON<<ANNA<MARIA<<<<<<<<<<<<<<<<<<<",
+        "L8989O2C36UTO74O8122F1204159ZE184226B<<<<<10",
+    ];
+
+    match parse_lines(&lines) {
+        Ok(MRZ::Icao(mrz)) => {
+            assert_eq!(mrz.document_number(), "L898902C3");
+            let fields: Vec<_> = mrz.corrections().iter().map(|c| c.field).collect();
+            assert_eq!(fields, [MrzField::DocumentNumber, MrzField::BirthDate]);
+
+            let correction = &mrz.corrections()[0];
+            assert_eq!(correction.raw_value.as_deref(), Some("L8989O2C3"));
+            assert_eq!(correction.corrected_value.as_deref(), Some("L898902C3"));
+            let correction = correction.correction.as_ref().unwrap();
+            assert_eq!(
+                correction.substitutions,
+                [Substitution {
+                    position: 5,
+                    from: b'O',
+                    to: b'0'
+                }]
+            );
+            assert_eq!(mrz.corrections()[1].raw_value.as_deref(), Some("74O812"));
+            let correction = mrz.corrections()[1].correction.as_ref().unwrap();
+            assert_eq!(correction.substitutions[0].position, 2);
+        }
+        other => panic!("Expected ICAO document, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_parse_lines_records_optional_data_provenance() {
+    let lines = [
+        "I<UTOD231458907AB1Z34567<<<<<<",
+        "7408122F1204159UTO<<<<<<<<<<<1",
+        "ERIKSSON<<ANNA<MARIA<<<<<<<<<<",
+    ];
+    let options = ParseOptions {
+        correct_optional_data: true,
+        ..ParseOptions::lenient()
+    };
+
+    match parse_lines_with(&lines, &options) {
+        Ok(MRZ::Icao(mrz)) => {
+            assert_eq!(mrz.optional_data1(), "AB1234567");
+            assert_eq!(mrz.final_check(), Some(true));
+            let [correction] = mrz.corrections() else {
+                panic!("Expected one correction, got {:?}", mrz.corrections());
+            };
+            assert_eq!(correction.field, MrzField::OptionalData);
+            assert_eq!(correction.outcome, CorrectionOutcome::Corrected);
+            assert_eq!(correction.raw_value.as_deref(), Some("AB1Z34567"));
+            assert_eq!(correction.corrected_value.as_deref(), Some("AB1234567"));
+            assert_eq!(
+                correction.correction.as_ref().unwrap().substitutions,
+                [Substitution {
+                    position: 3,
+                    from: b'Z',
+                    to: b'2'
+                }]
+            );
+        }
+        other => panic!("Expected ICAO document, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_parse_scored_lines() {
+    // The `0` of the document number was read as `O` with low confidence.
+    let line1 = "P<UTOERIKSSON<<ANNA<MARIA<<<<<<<<<<<<<<<<<<<";
+    let line2 = "L8989O2C36UTO7408122F1204159ZE184226B<<<<<10";
+    let confidences1 = [97; 44];
+    let mut confidences2 = [97; 44];
+    confidences2[5] = 20;
+    let lines = [
+        ScoredLine::new(line1.as_bytes(), &confidences1),
+        ScoredLine::new(line2.as_bytes(), &confidences2),
+    ];
+
+    match parse_scored_lines(&lines, &ParseOptions::default()) {
+        Ok(MRZ::Icao(mrz)) => {
+            assert_eq!(mrz.document_number(), "L898902C3");
+            assert_eq!(mrz.final_check(), Some(true));
+        }
+        other => panic!("Expected ICAO document, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_consensus_outvotes_misread_frames() {
+    const LINE1: &str = "P<UTOERIKSSON<<ANNA<MARIA<<<<<<<<<<<<<<<<<<<";
+    const LINE2: &str = "L898902C36UTO7408122F1204159ZE184226B<<<<<10";
+    let frames = [
+        [LINE1, LINE2],
+        // A misread surname and an unreadable birth date.
+        [
+            "P<UTOERIKSS0N<<ANNA<MARIA<<<<<<<<<<<<<<<<<<<",
+            "L898902C36UTO7408I22F1204159ZE184226B<<<<<10",
+        ],
+        [LINE1, LINE2],
+        [LINE1, LINE2],
+        [LINE1, LINE2],
+    ];
+
+    let mut consensus = MrzConsensus::new(ConsensusOptions {
+        min_frames: 4,
+        ..ConsensusOptions::default()
+    });
+    let mut statuses = frames.iter().map(|frame| consensus.push(frame));
+    for _ in 0..3 {
+        assert!(matches!(statuses.next(), Some(ConsensusStatus::Pending)));
+    }
+    match statuses.next() {
+        Some(ConsensusStatus::Converged(MRZ::Icao(mrz))) => {
+            assert_eq!(mrz.surname(), "ERIKSSON");
+            assert_eq!(mrz.document_number(), "L898902C3");
+        }
+        other => panic!("Expected converged ICAO document, got {:?}", other),
+    }
+    drop(statuses);
+
+    assert_eq!(consensus.frames(), 4);
+    assert_eq!(consensus.lines(), [LINE1, LINE2]);
+    let stability = consensus.stability();
+    let birth_date = stability
+        .iter()
+        .find(|field| field.field == MrzField::BirthDate)
+        .unwrap();
+    // The misread frame has a failed check digit and votes with less weight.
+    assert!(birth_date.stability > 0.9 && birth_date.stability < 1.0);
+    assert!(stability.iter().all(|field| field.stable));
+
+    consensus.reset();
+    assert_eq!(consensus.frames(), 0);
+    assert!(consensus.lines().is_empty());
+}
+
+#[test]
+fn test_consensus_times_out() {
+    let mut consensus = MrzConsensus::new(ConsensusOptions {
+        min_frames: 10,
+        timeout: std::time::Duration::from_secs(2),
+        ..ConsensusOptions::default()
+    });
+    let frame = [
+        "I<UTOD231458907<<<<<<<<<<<<<<<",
+        "7408122F1204159UTO<<<<<<<<<<<6",
+        "ERIKSSON<<ANNA<MARIA<<<<<<<<<<",
+    ];
+    let start = std::time::Instant::now();
+    assert!(matches!(
+        consensus.push_at(&frame, start),
+        ConsensusStatus::Pending
+    ));
+    // Frames that are not an MRZ are ignored.
+    assert!(matches!(
+        consensus.push_at(&["HELLO"], start + std::time::Duration::from_secs(1)),
+        ConsensusStatus::Pending
+    ));
+    assert_eq!(consensus.frames(), 1);
+    match consensus.push_at(&frame, start + std::time::Duration::from_secs(3)) {
+        ConsensusStatus::TimedOut(Some(MRZ::Icao(mrz))) => {
+            assert_eq!(mrz.format(), "TD1");
+            assert_eq!(mrz.document_number(), "D23145890");
+        }
+        other => panic!("Expected timed out ICAO document, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_extract_mrz_from_page() {
+    let page = "\
+REPUBLIC OF UTOPIA
+Passport No. L898902C3
+p<utoeriksson<<anna<maria
+L898902C36UTO7408122F1204159ZE184226B<<<<<10
+
+Residence permit
+I<UTOD231458907<<<<<<<<<<<<<<<
+7408122F1204159UTO<<<<<<<<<<<6
+ERIKSSON<<ANNA<MARIA<<<<<<<<<<
+";
+
+    let found = extract_mrz(page);
+    assert_eq!(found.len(), 2);
+
+    assert_eq!(found[0].source_lines[0], "p<utoeriksson<<anna<maria");
+    assert_eq!(
+        found[0].lines[0],
+        "P<UTOERIKSSON<<ANNA<MARIA<<<<<<<<<<<<<<<<<<<"
+    );
+    match &found[0].result {
+        Ok(MRZ::Icao(mrz)) => {
+            assert_eq!(mrz.format(), "TD3");
+            assert_eq!(mrz.document_number(), "L898902C3");
+        }
+        other => panic!("Expected ICAO document, got {:?}", other),
+    }
+
+    assert_eq!((found[1].first_line, found[1].last_line), (6, 8));
+    match &found[1].result {
+        Ok(MRZ::Icao(mrz)) => assert_eq!(mrz.format(), "TD1"),
+        other => panic!("Expected ICAO document, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_parse_lines_report_with_swapped_lines() {
+    let lines = [
+        "L898902C36UTO7408122F1204159ZE184226B<<<<<10",
+        "P<UTOERIKSSON<<ANNA<MARIA<<<<<<<<<<<<<<<<<<<",
+    ];
+    let report = parse_lines_report(&lines, &ParseOptions::lenient());
+
+    assert_eq!(report.line_order.unwrap().as_slice(), &[1, 0]);
+    assert!(!report.has_errors());
+    match report.document {
+        Some(MRZ::Icao(mrz)) => {
+            assert_eq!(mrz.surname(), "ERIKSSON");
+            assert_eq!(mrz.line_order(), report.line_order);
+        }
+        other => panic!("Expected ICAO document, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_parse_lines_report_lists_normalization_changes() {
+    let lines = [
+        "I<UTOD231458907               ",
+        "7408122F1204159UTO<<<<<<<<<<<6",
+        "ERIKSSON<<ANNA<MARIA<<<<<<<<<<",
+    ];
+    let report = parse_lines_report(&lines, &ParseOptions::lenient());
+
+    assert!(
+        !report.has_errors(),
+        "Unexpected issues: {:?}",
+        report.issues
+    );
+    assert!(report.issues.contains(&ParseIssue::warning(
+        MrzField::Layout,
+        IssueKind::InputNormalized
+    )));
+    assert_eq!(report.normalization.len(), 15);
+    assert!(report.normalization.iter().all(|change| change.line == 0));
+    assert!(matches!(report.document, Some(MRZ::Icao(_))));
+}
+
+#[test]
+fn test_parse_lines_report_lists_every_failed_check() {
+    let lines = [
+        "I<UTOD231458908<<<<<<<<<<<<<<<",
+        "7408122F1204158UTO<<<<<<<<<<<6",
+        "ERIKSSON<<ANNA<MARIA<<<<<<<<<<",
+    ];
+
+    let report = parse_lines_report(&lines, &ParseOptions::strict());
+    assert_eq!(
+        report.issues.as_slice(),
+        &[
+            ParseIssue::error(MrzField::DocumentNumber, IssueKind::ChecksumMismatch),
+            ParseIssue::error(MrzField::ExpiryDate, IssueKind::ChecksumMismatch),
+            ParseIssue::error(MrzField::Final, IssueKind::ChecksumMismatch),
+        ]
+    );
+    match report.document {
+        Some(MRZ::Icao(mrz)) => {
+            assert_eq!(mrz.document_number(), "D23145890");
+            assert_eq!(mrz.final_check(), Some(false));
+        }
+        other => panic!("Expected TD1 document, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_parse_lines_document_kind() {
+    let td3 = [
+        "P<UTOERIKSSON<<ANNA<MARIA<<<<<<<<<<<<<<<<<<<",
+        "L898902C36UTO7408122F1204159ZE184226B<<<<<10",
+    ];
+    let td1 = [
+        "I<UTOD231458907<<<<<<<<<<<<<<<",
+        "7408122F1204159UTO<<<<<<<<<<<6",
+        "ERIKSSON<<ANNA<MARIA<<<<<<<<<<",
+    ];
+
+    match parse_lines(&td3) {
+        Ok(MRZ::Icao(mrz)) => assert_eq!(mrz.document_kind(), DocumentKind::Passport),
+        other => panic!("Expected TD3 document, got {:?}", other),
+    }
+    match parse_lines(&td1) {
+        Ok(mrz) => assert_eq!(mrz.document_kind(), Some(DocumentKind::IdentityCard)),
+        other => panic!("Expected TD1 document, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_parse_lines_td1_extended_document_number() {
+    let lines = [
+        "I<UTOD23145890<7349<<<<<<<<<<<",
+        "3407127M9507122UTO<<<<<<<<<<<2",
+        "STEVENSON<<PETER<JOHN<<<<<<<<<",
+    ];
+
+    match parse_lines(&lines) {
+        Ok(MRZ::Icao(mrz)) => {
+            assert_eq!(mrz.format(), "TD1");
+            assert_eq!(mrz.document_number(), "D23145890734");
+            assert_eq!(mrz.optional_data1(), "");
+        }
+        other => panic!("Expected TD1 document, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_parse_lines_mrva() {
+    let lines = [
+        "V<UTOERIKSSON<<ANNA<MARIA",
+        "L8988901C4XXX4009078F96121096ZE184226B",
+    ];
+
+    match parse_lines(&lines) {
+        Ok(MRZ::Icao(mrz)) => {
+            assert_eq!(mrz.format(), "MRVA");
+            assert_eq!(mrz.document_code(), "V");
+            assert_eq!(mrz.document_number(), "L8988901C");
+            assert_eq!(mrz.optional_data1(), "6ZE184226B");
+            assert_eq!(mrz.final_check(), None);
+        }
+        other => panic!("Expected MRV-A document, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_parse_lines_mrvb() {
+    let lines = [
+        "V<UTOERIKSSON<<ANNA<MARIA<<<<<<<<<<<",
+        "L8988901C4XXX4009078F9612109<<<<<<<<",
+    ];
+
+    match parse_lines(&lines) {
+        Ok(MRZ::Icao(mrz)) => {
+            assert_eq!(mrz.format(), "MRVB");
+            assert_eq!(mrz.document_number(), "L8988901C");
+            assert_eq!(mrz.birth_date().unwrap().to_string(), "1940-09-07");
+            assert_eq!(mrz.final_check(), None);
+        }
+        other => panic!("Expected MRV-B document, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_parse_lines_bcbp_is_not_padded() {
+    let lines = ["M1DESMARAIS/LUC       EABC123 YULFRAAC 0834 326J001A0025 100"];
+
+    match parse_lines(&lines) {
+        Ok(MRZ::Bcbp(bcbp)) => {
+            assert_eq!(bcbp.surname(), "DESMARAIS");
+            assert_eq!(bcbp.legs.len(), 1);
+            assert_eq!(bcbp.legs[0].airline_data, "");
+        }
+        other => panic!("Expected boarding pass, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_parse_lines_fra_id_records_correction_provenance() {
+    let lines = [
+        "IDFRADOUEL<<<<<<<<<<<<<<<<<<<<932013",
+        "0506932020438CHRISTIANE<<NI29O6209F3", // 'O' instead of '0'
+    ];
+
+    match parse_lines(&lines) {
+        Ok(MRZ::Icao(mrz)) => {
+            assert_eq!(
+                mrz.birth_date().map(|d| d.to_string()).as_deref(),
+                Some("1929-06-20")
+            );
+            let [correction] = mrz.corrections() else {
+                panic!("Expected one correction, got {:?}", mrz.corrections());
+            };
+            assert_eq!(correction.field, MrzField::BirthDate);
+            assert_eq!(correction.outcome, CorrectionOutcome::Corrected);
+            assert_eq!(correction.raw_value.as_deref(), Some("29O620"));
+            assert_eq!(correction.corrected_value.as_deref(), Some("290620"));
+            assert_eq!(
+                correction.correction.as_ref().unwrap().substitutions,
+                [Substitution {
+                    position: 2,
+                    from: b'O',
+                    to: b'0'
+                }]
+            );
+        }
+        other => panic!("Expected French ID card, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_parse_lines_fra_id_specimens() {
+    let data = fs::read_to_string(Path::new("tests/fixtures/samples.json")).unwrap();
+    let samples: Vec<Sample> = serde_json::from_str(&data).unwrap();
+
+    for sample in samples.iter().filter(|s| s.format == "IDFRA") {
+        let line_refs: Vec<&str> = sample.lines.iter().map(String::as_str).collect();
+        match parse_lines(&line_refs) {
+            Ok(MRZ::Icao(mrz)) => {
+                assert_eq!(mrz.format(), "IDFRA");
+                assert_eq!(mrz.document_code(), "ID");
+                assert_eq!(mrz.nationality(), "FRA");
+                assert_eq!(mrz.expiry_date(), None);
+                assert_eq!(
+                    Some(mrz.document_number()),
+                    sample.document_number.as_deref()
+                );
+                assert_eq!(Some(mrz.surname()), sample.surname.as_deref());
+                assert_eq!(Some(mrz.given_names()), sample.given_names.as_deref());
+                assert_eq!(
+                    mrz.birth_date().map(|d| d.to_string()).as_deref(),
+                    sample.birth_date.as_deref()
+                );
+                assert_eq!(Some(mrz.sex().to_string().as_str()), sample.sex.as_deref());
+            }
+            other => panic!("Expected French ID card, got {:?}", other),
+        }
+    }
+}
+
+#[test]
+fn test_parse_lines_driving_licence() {
+    match parse_lines(&["D1NLD15094962111659VW87MG13B84"]) {
+        Ok(MRZ::DrivingLicence(dl)) => {
+            assert_eq!(dl.licence_number(), "5094962111659VW87MG13B8");
+        }
+        other => panic!("Expected driving licence, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_parse_lines_swiss_driving_licence() {
+    let lines = ["AAA001D", "FACHE305142267001940928", "MARCHAND<<FABIENNE"];
+
+    match parse_lines(&lines) {
+        Ok(MRZ::SwissDrivingLicence(dl)) => {
+            assert_eq!(dl.licence_number(), "AAA001");
+            assert_eq!(dl.pin_code(), "305142267");
+            assert_eq!(dl.version(), "001");
+            assert_eq!(dl.birth_date(), b"940928");
+            assert_eq!(dl.surname(), "MARCHAND");
+            assert_eq!(dl.given_names(), "FABIENNE");
+        }
+        other => panic!("Expected Swiss driving licence, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_parse_lines_pads_short_td1_line_without_swiss_document_code() {
+    // A short first line alone does not make a Swiss licence: this TD1 still gets padded.
+    let lines = [
+        "I<UTOD231458907",
+        "7408122F1204159UTO<<<<<<<<<<<6",
+        "ERIKSSON<<ANNA<MARIA<<<<<<<<<<",
+    ];
+
+    match parse_lines(&lines) {
+        Ok(MRZ::Icao(mrz)) => assert_eq!(mrz.format(), "TD1"),
+        other => panic!("Expected TD1 document, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_parse_lines_does_not_pad_single_lines() {
+    // A truncated licence must not be completed with fillers into something parseable.
+    let result = parse_lines(&["D1NLD15094962111659VW87MG13B8"]);
+    assert!(
+        matches!(result, Err(MRZParseError::UnknownFormat)),
+        "Expected unknown format, got {:?}",
+        result
+    );
+}
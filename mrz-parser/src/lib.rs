@@ -1,10 +1,25 @@
 #![cfg_attr(not(feature = "std"), no_std)]
-pub use mrz_core::{MRZFormat, MRZParseError, ParsedMRZ};
+pub use mrz_core::{
+    CharClass, ChecksumPolicy, Confusion, ConfusionModel, Correction, CorrectionCandidate,
+    CorrectionOutcome, DocumentKind, ErrorPosition, IcaoOcrB, LineOrder, MRZChecksumError,
+    MRZFormat, MRZParseError, ParseOptions, ParseReport, ParsedMRZ, ScoredLine, Substitution,
+};
 
 #[cfg(feature = "std")]
 pub use mrz_host::{
+    extract_mrz,
+    extract_mrz_with,
     parse_lines,
+    parse_lines_report,
+    parse_lines_with,
     parse_mrz_date_with_reference,
+    parse_scored_lines,
+    ConsensusOptions,
+    ConsensusStatus,
+    ExtractedMrz,
+    FieldCorrection,
+    FieldStability,
+    MrzConsensus,
     MRZ, // Rich enum
 };
 
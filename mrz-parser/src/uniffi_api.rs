@@ -1,10 +1,13 @@
-use mrz_host::{parse_lines as inner_parse_lines, MRZ};
+use mrz_host::{
+    parse_lines as inner_parse_lines, parse_mrz_date_with_reference, FieldCorrection, MRZ,
+};
 
 use uniffi::export;
 
 #[derive(uniffi::Record)]
 pub struct MrzResult {
     pub document_type: String,
+    pub document_kind: String,
     pub document_number: String,
     pub name: String,
     pub nationality: String,
@@ -16,40 +19,211 @@ pub struct MrzResult {
     pub issuing_state: String,
     pub given_names: String,
     pub surname: String,
+    /// Checked fields changed by OCR correction.
+    pub corrections: Vec<MrzFieldCorrection>,
+}
+
+/// A character replaced by OCR correction, at `position` in the field value.
+#[derive(uniffi::Record, Debug, Clone, PartialEq, Eq)]
+pub struct MrzSubstitution {
+    /// Index of the character in the field value.
+    pub position: u32,
+    /// The character as read.
+    pub from: String,
+    /// The character it was corrected to.
+    pub to: String,
+}
+
+/// MRZ field a correction applies to; see [`mrz_core::MrzField`].
+#[derive(uniffi::Enum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MrzFieldKind {
+    Layout,
+    DocumentNumber,
+    BirthDate,
+    Sex,
+    ExpiryDate,
+    Final,
+    DocumentCode,
+    IssuingState,
+    Name,
+    Nationality,
+    OptionalData,
+}
+
+impl From<mrz_core::MrzField> for MrzFieldKind {
+    fn from(field: mrz_core::MrzField) -> Self {
+        match field {
+            mrz_core::MrzField::Layout => MrzFieldKind::Layout,
+            mrz_core::MrzField::DocumentNumber => MrzFieldKind::DocumentNumber,
+            mrz_core::MrzField::BirthDate => MrzFieldKind::BirthDate,
+            mrz_core::MrzField::Sex => MrzFieldKind::Sex,
+            mrz_core::MrzField::ExpiryDate => MrzFieldKind::ExpiryDate,
+            mrz_core::MrzField::Final => MrzFieldKind::Final,
+            mrz_core::MrzField::DocumentCode => MrzFieldKind::DocumentCode,
+            mrz_core::MrzField::IssuingState => MrzFieldKind::IssuingState,
+            mrz_core::MrzField::Name => MrzFieldKind::Name,
+            mrz_core::MrzField::Nationality => MrzFieldKind::Nationality,
+            mrz_core::MrzField::OptionalData => MrzFieldKind::OptionalData,
+        }
+    }
+}
+
+/// How a field relates to its check digit; see [`mrz_core::CorrectionOutcome`].
+#[derive(uniffi::Enum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MrzCorrectionOutcome {
+    Verified,
+    Corrected,
+    Ambiguous,
+    Invalid,
+}
+
+impl From<mrz_core::CorrectionOutcome> for MrzCorrectionOutcome {
+    fn from(outcome: mrz_core::CorrectionOutcome) -> Self {
+        match outcome {
+            mrz_core::CorrectionOutcome::Verified => MrzCorrectionOutcome::Verified,
+            mrz_core::CorrectionOutcome::Corrected => MrzCorrectionOutcome::Corrected,
+            mrz_core::CorrectionOutcome::Ambiguous => MrzCorrectionOutcome::Ambiguous,
+            mrz_core::CorrectionOutcome::Invalid => MrzCorrectionOutcome::Invalid,
+        }
+    }
+}
+
+/// What OCR read in a field and how correction changed it.
+#[derive(uniffi::Record, Debug, Clone, PartialEq, Eq)]
+pub struct MrzFieldCorrection {
+    /// The corrected field; a TD1 document may list optional data twice, optional
+    /// data 1 first.
+    pub field: MrzFieldKind,
+    /// Whether the correction is certain or one of several equally likely values.
+    pub outcome: MrzCorrectionOutcome,
+    /// The value as read, without trailing fillers; `None` for the composite check
+    /// digit, which holds no value of its own.
+    pub raw_value: Option<String>,
+    /// The corrected value, without trailing fillers; `None` for the composite check
+    /// digit.
+    pub corrected_value: Option<String>,
+    /// The characters that differ between `raw_value` and `corrected_value`.
+    pub substitutions: Vec<MrzSubstitution>,
+}
+
+impl From<&FieldCorrection> for MrzFieldCorrection {
+    fn from(c: &FieldCorrection) -> Self {
+        MrzFieldCorrection {
+            field: c.field.into(),
+            outcome: c.outcome.into(),
+            raw_value: c.raw_value.clone(),
+            corrected_value: c.corrected_value.clone(),
+            substitutions: c.correction.as_ref().map_or(Vec::new(), |c| {
+                c.substitutions
+                    .iter()
+                    .map(|s| MrzSubstitution {
+                        position: u32::from(s.position),
+                        from: char::from(s.from).to_string(),
+                        to: char::from(s.to).to_string(),
+                    })
+                    .collect()
+            }),
+        }
+    }
 }
 
 use thiserror::Error;
 
+/// Zero-based line index and half-open column span of a parse error.
+#[derive(uniffi::Record, Debug, Clone, PartialEq, Eq)]
+pub struct MrzErrorPosition {
+    pub line: u32,
+    pub column_start: u32,
+    pub column_end: u32,
+}
+
+impl From<mrz_core::ErrorPosition> for MrzErrorPosition {
+    fn from(p: mrz_core::ErrorPosition) -> Self {
+        MrzErrorPosition {
+            line: p.line as u32,
+            column_start: p.start as u32,
+            column_end: p.end as u32,
+        }
+    }
+}
+
 #[derive(uniffi::Error, Debug, Error)]
 pub enum MrzParseError {
-    #[error("The MRZ has an invalid length")]
-    InvalidLength,
-    #[error("Checksum failed: {0}")]
-    InvalidChecksumField(String),
-    #[error("Unknown format")]
-    UnknownFormat,
-    #[error("Unsupported format")]
-    UnsupportedFormat,
-    #[error("UTF-8 error")]
-    Utf8Error,
+    #[error("{message}")]
+    InvalidLength {
+        message: String,
+        position: Option<MrzErrorPosition>,
+    },
+    #[error("{message}")]
+    InvalidChecksumField {
+        field: String,
+        message: String,
+        position: Option<MrzErrorPosition>,
+    },
+    #[error("{message}")]
+    InvalidCharacter {
+        found: String,
+        expected: String,
+        message: String,
+        position: MrzErrorPosition,
+    },
+    #[error("{message}")]
+    UnknownFormat { message: String },
+    #[error("{message}")]
+    UnsupportedFormat { message: String },
+    #[error("{message}")]
+    Utf8Error {
+        message: String,
+        position: Option<MrzErrorPosition>,
+    },
+}
+
+impl From<mrz_core::MRZParseError> for MrzParseError {
+    fn from(e: mrz_core::MRZParseError) -> Self {
+        let message = e.to_string();
+        match e {
+            mrz_core::MRZParseError::InvalidLength(position) => MrzParseError::InvalidLength {
+                message,
+                position: position.map(Into::into),
+            },
+            mrz_core::MRZParseError::InvalidChecksumField(field, position) => {
+                MrzParseError::InvalidChecksumField {
+                    field: field.to_string(),
+                    message,
+                    position: position.map(Into::into),
+                }
+            }
+            mrz_core::MRZParseError::InvalidCharacter {
+                position,
+                found,
+                expected,
+            } => MrzParseError::InvalidCharacter {
+                found: char::from(found).to_string(),
+                expected: expected.to_string(),
+                message,
+                position: position.into(),
+            },
+            mrz_core::MRZParseError::UnknownFormat => MrzParseError::UnknownFormat { message },
+            mrz_core::MRZParseError::UnsupportedFormat => {
+                MrzParseError::UnsupportedFormat { message }
+            }
+            mrz_core::MRZParseError::Utf8Error(position) => MrzParseError::Utf8Error {
+                message,
+                position: position.map(Into::into),
+            },
+        }
+    }
 }
 
 #[export]
 pub fn parse_lines(lines: Vec<String>) -> Result<MrzResult, MrzParseError> {
     let strs: Vec<&str> = lines.iter().map(|s| s.as_str()).collect();
     inner_parse_lines(&strs)
-        .map_err(|e| match e {
-            mrz_core::MRZParseError::InvalidLength => MrzParseError::InvalidLength,
-            mrz_core::MRZParseError::InvalidChecksumField(inner) => {
-                MrzParseError::InvalidChecksumField(format!("{:?}", inner))
-            }
-            mrz_core::MRZParseError::UnknownFormat => MrzParseError::UnknownFormat,
-            mrz_core::MRZParseError::UnsupportedFormat => MrzParseError::UnsupportedFormat,
-            mrz_core::MRZParseError::Utf8Error => MrzParseError::Utf8Error,
-        })
+        .map_err(MrzParseError::from)
         .and_then(|mrz| match mrz {
             MRZ::Icao(u) => Ok(MrzResult {
                 document_type: u.document_code().to_string(),
+                document_kind: u.document_kind().as_str().to_string(),
                 document_number: u.document_number().to_string(),
                 name: u.full_name().to_string(),
                 nationality: u.nationality().to_string(),
@@ -61,7 +235,42 @@ pub fn parse_lines(lines: Vec<String>) -> Result<MrzResult, MrzParseError> {
                 issuing_state: u.issuing_state().to_string(),
                 given_names: u.given_names().to_string(),
                 surname: u.surname().to_string(),
+                corrections: u.corrections().iter().map(Into::into).collect(),
+            }),
+            MRZ::DrivingLicence(dl) => Ok(MrzResult {
+                document_type: String::from_utf8_lossy(&dl.document_code).into_owned(),
+                document_kind: dl.document_kind().as_str().to_string(),
+                document_number: dl.licence_number().to_string(),
+                name: String::new(),
+                nationality: String::new(),
+                birth_date: String::new(),
+                sex: String::new(),
+                expiry_date: String::new(),
+                optional_data1: String::new(),
+                optional_data2: String::new(),
+                issuing_state: String::from_utf8_lossy(&dl.issuing_state).into_owned(),
+                given_names: String::new(),
+                surname: String::new(),
+                corrections: Vec::new(),
+            }),
+            MRZ::SwissDrivingLicence(dl) => Ok(MrzResult {
+                document_type: String::from_utf8_lossy(&dl.document_code).into_owned(),
+                document_kind: dl.document_kind().as_str().to_string(),
+                document_number: dl.licence_number().to_string(),
+                name: format!("{} {}", dl.surname(), dl.given_names()),
+                nationality: String::new(),
+                birth_date: parse_mrz_date_with_reference(dl.birth_date(), None)
+                    .map_or("".into(), |d| d.to_string()),
+                sex: String::new(),
+                expiry_date: String::new(),
+                optional_data1: String::new(),
+                optional_data2: String::new(),
+                issuing_state: String::from_utf8_lossy(&dl.issuing_state).into_owned(),
+                given_names: dl.given_names().to_string(),
+                surname: dl.surname().to_string(),
+                corrections: Vec::new(),
             }),
-            MRZ::Unknown => Err(MrzParseError::UnknownFormat),
+            MRZ::Bcbp(_) => Err(mrz_core::MRZParseError::UnsupportedFormat.into()),
+            MRZ::Unknown => Err(mrz_core::MRZParseError::UnknownFormat.into()),
         })
 }
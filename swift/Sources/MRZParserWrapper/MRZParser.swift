@@ -17,7 +17,11 @@ private func parseMRZDate(_ string: String) -> Date {
 public enum MRZDocumentType {
     case passport
     case idCard
+    case residencePermit
+    case crewMemberCertificate
     case visa
+    case travelPermit
+    case drivingLicence
     case unknown
 }
 
@@ -56,7 +60,7 @@ public struct MRZParser {
                 nationality: result.nationality,
                 birthDate: parseMRZDate(result.birthDate),
                 expiryDate: parseMRZDate(result.expiryDate),
-                documentType: mapDocType(result.documentType),
+                documentType: mapDocType(result.documentKind),
                 issuingState: result.issuingState,
                 sex: result.sex,
                 optionalData1: result.optionalData1,
@@ -69,11 +73,16 @@ public struct MRZParser {
         }
     }
 
-	private func mapDocType(_ type: String) -> MRZDocumentType {
-	    switch type.uppercased() {
-	    case "P": return .passport
-	    case "I": return .idCard
-	    case "V": return .visa
+	private func mapDocType(_ kind: String) -> MRZDocumentType {
+	    switch kind {
+	    case "passport", "diplomatic_passport", "official_passport", "emergency_passport":
+	        return .passport
+	    case "identity_card": return .idCard
+	    case "residence_permit": return .residencePermit
+	    case "crew_member_certificate": return .crewMemberCertificate
+	    case "visa": return .visa
+	    case "travel_permit": return .travelPermit
+	    case "driving_licence": return .drivingLicence
 	    default: return .unknown
 	    }
 }